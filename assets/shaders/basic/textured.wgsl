@@ -0,0 +1,52 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+}
+
+@group(1) @binding(0)
+var<uniform> model_uniform: ModelUniform;
+
+// オブジェクトごとの色（頂点色・テクスチャ色に乗算）
+struct MaterialUniform {
+    color: vec4<f32>,
+}
+
+@group(1) @binding(1)
+var<uniform> material: MaterialUniform;
+
+// group 2 はライト（未使用）
+@group(3) @binding(0)
+var diffuse_texture: texture_2d<f32>;
+@group(3) @binding(1)
+var diffuse_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+};
+
+@vertex
+fn vs_main(vin: VertexInput) -> VertexOutput {
+    var vout: VertexOutput;
+    vout.tex_coords = vin.tex_coords;
+
+    let world_position = model_uniform.model * vec4<f32>(vin.position, 1.0);
+    vout.clip_position = camera.view_proj * world_position;
+    return vout;
+}
+
+@fragment
+fn fs_main(fin: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(diffuse_texture, diffuse_sampler, fin.tex_coords) * material.color;
+}
@@ -12,27 +12,108 @@ struct ModelUniform {
 @group(1) @binding(0)
 var<uniform> model_uniform: ModelUniform;
 
+// オブジェクトごとの色（頂点色・テクスチャ色に乗算）
+struct MaterialUniform {
+    color: vec4<f32>,
+}
+
+@group(1) @binding(1)
+var<uniform> material: MaterialUniform;
+
+const MAX_POINT_LIGHTS: u32 = 4u;
+
+struct PointLight {
+    position: vec3<f32>,
+    // この距離で寄与がゼロになる
+    radius: f32,
+    color: vec3<f32>,
+}
+
+struct LightUniform {
+    // 光の進む向き（光源からシーンへ）
+    direction: vec3<f32>,
+    color: vec3<f32>,
+    point_light_count: u32,
+    point_lights: array<PointLight, MAX_POINT_LIGHTS>,
+}
+
+@group(2) @binding(0)
+var<uniform> light: LightUniform;
+
+// 平行光源のシャドウマップ（enabled が 0 の間は常に光が当たる）
+struct ShadowUniform {
+    light_view_proj: mat4x4<f32>,
+    bias: f32,
+    enabled: u32,
+}
+
+@group(2) @binding(1)
+var<uniform> shadow: ShadowUniform;
+@group(2) @binding(2)
+var shadow_map: texture_depth_2d;
+@group(2) @binding(3)
+var shadow_sampler: sampler_comparison;
+
+// 平行光源が届く割合（0 = 影、1 = 日なた）
+fn shadow_visibility(world_position: vec3<f32>) -> f32 {
+    if shadow.enabled == 0u {
+        return 1.0;
+    }
+    let clip = shadow.light_view_proj * vec4<f32>(world_position, 1.0);
+    let ndc = clip.xyz / clip.w;
+    let uv = ndc.xy * vec2<f32>(0.5, -0.5) + 0.5;
+    // シャドウマップの範囲外は影にしない
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) || ndc.z > 1.0 {
+        return 1.0;
+    }
+    return textureSampleCompareLevel(shadow_map, shadow_sampler, uv, ndc.z - shadow.bias);
+}
+
+// 光の当たらない面の明るさ
+const AMBIENT: f32 = 0.15;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec3<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) world_position: vec3<f32>,
 };
 
 @vertex
 fn vs_main(vin: VertexInput) -> VertexOutput {
     var vout: VertexOutput;
     vout.color = vin.color;
+    vout.world_normal = (model_uniform.model * vec4<f32>(vin.normal, 0.0)).xyz;
 
     let world_position = model_uniform.model * vec4<f32>(vin.position, 1.0);
+    vout.world_position = world_position.xyz;
     vout.clip_position = camera.view_proj * world_position;
     return vout;
 }
 
 @fragment
 fn fs_main(fin: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(fin.color, 1.0);
-}
\ No newline at end of file
+    // Lambert 拡散反射
+    let normal = normalize(fin.world_normal);
+    let diffuse = max(dot(normal, -light.direction), 0.0);
+    var lighting = vec3<f32>(AMBIENT) + diffuse * shadow_visibility(fin.world_position) * light.color;
+
+    // 点光源: (1 - (d/r)^2)^2 で半径までに滑らかに減衰
+    let count = min(light.point_light_count, MAX_POINT_LIGHTS);
+    for (var i = 0u; i < count; i = i + 1u) {
+        let point = light.point_lights[i];
+        let to_light = point.position - fin.world_position;
+        let distance = length(to_light);
+        let ratio = clamp(distance / point.radius, 0.0, 1.0);
+        let falloff = (1.0 - ratio * ratio) * (1.0 - ratio * ratio);
+        let point_diffuse = max(dot(normal, to_light / max(distance, 1e-4)), 0.0);
+        lighting += point_diffuse * falloff * point.color;
+    }
+    return vec4<f32>(fin.color * material.color.rgb * lighting, material.color.a);
+}
@@ -0,0 +1,23 @@
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+// 頂点はCPU側でクリップ座標に変換済み
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var vout: VertexOutput;
+    vout.clip_position = in.position;
+    vout.color = in.color;
+    return vout;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,76 @@
+@group(0) @binding(0)
+var scene_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var scene_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 探索方向の縮小量とスパンの上限（ピクセル単位）
+const FXAA_REDUCE_MIN: f32 = 1.0 / 128.0;
+const FXAA_REDUCE_MUL: f32 = 1.0 / 8.0;
+const FXAA_SPAN_MAX: f32 = 8.0;
+
+// 画面全体を覆う三角形
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var vout: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    vout.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    // クリップ空間はY上向き、テクスチャ座標はY下向き
+    vout.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return vout;
+}
+
+fn sample_color(uv: vec2<f32>) -> vec3<f32> {
+    return textureSampleLevel(scene_texture, scene_sampler, uv, 0.0).rgb;
+}
+
+// sRGBターゲットから読んだ線形値を知覚的な明るさに近づける
+fn luma(color: vec3<f32>) -> f32 {
+    return sqrt(dot(color, vec3<f32>(0.299, 0.587, 0.114)));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(scene_texture));
+    let center = textureSampleLevel(scene_texture, scene_sampler, in.uv, 0.0);
+
+    let luma_nw = luma(sample_color(in.uv + vec2<f32>(-1.0, -1.0) * texel));
+    let luma_ne = luma(sample_color(in.uv + vec2<f32>(1.0, -1.0) * texel));
+    let luma_sw = luma(sample_color(in.uv + vec2<f32>(-1.0, 1.0) * texel));
+    let luma_se = luma(sample_color(in.uv + vec2<f32>(1.0, 1.0) * texel));
+    let luma_m = luma(center.rgb);
+    let luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    let luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // 明るさの勾配に垂直な方向（エッジに沿う方向）に沿ってぼかす
+    var dir = vec2<f32>(
+        -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+        (luma_nw + luma_sw) - (luma_ne + luma_se),
+    );
+    let dir_reduce = max(
+        (luma_nw + luma_ne + luma_sw + luma_se) * (0.25 * FXAA_REDUCE_MUL),
+        FXAA_REDUCE_MIN,
+    );
+    let rcp_dir_min = 1.0 / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+    dir = clamp(dir * rcp_dir_min, vec2<f32>(-FXAA_SPAN_MAX), vec2<f32>(FXAA_SPAN_MAX)) * texel;
+
+    let color_a = 0.5 * (
+        sample_color(in.uv + dir * (1.0 / 3.0 - 0.5)) +
+        sample_color(in.uv + dir * (2.0 / 3.0 - 0.5))
+    );
+    let color_b = color_a * 0.5 + 0.25 * (
+        sample_color(in.uv + dir * -0.5) +
+        sample_color(in.uv + dir * 0.5)
+    );
+
+    // 広い範囲の平均が局所の明るさの範囲を外れたら狭い方を使う
+    let luma_b = luma(color_b);
+    if luma_b < luma_min || luma_b > luma_max {
+        return vec4<f32>(color_a, center.a);
+    }
+    return vec4<f32>(color_b, center.a);
+}
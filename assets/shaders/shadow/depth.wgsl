@@ -0,0 +1,21 @@
+// 平行光源から見た深度だけをシャドウマップに書く（フラグメントシェーダーなし）
+struct ShadowUniform {
+    light_view_proj: mat4x4<f32>,
+    bias: f32,
+    enabled: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> shadow: ShadowUniform;
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+}
+
+@group(1) @binding(0)
+var<uniform> model_uniform: ModelUniform;
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return shadow.light_view_proj * model_uniform.model * vec4<f32>(position, 1.0);
+}
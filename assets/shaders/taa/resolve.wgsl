@@ -0,0 +1,80 @@
+struct TaaUniform {
+    // 現フレームのクリップ空間 → 前フレームのクリップ空間
+    reprojection: mat4x4<f32>,
+    jitter: vec2<f32>,
+    // 現フレームの寄与率（履歴が無効なときは 1.0）
+    blend: f32,
+    _padding: f32,
+}
+
+@group(0) @binding(0)
+var current_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var history_texture: texture_2d<f32>;
+// 深度は非フィルタの浮動小数テクスチャとして読む（GLバックエンド互換）
+@group(0) @binding(2)
+var depth_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var history_sampler: sampler;
+@group(0) @binding(4)
+var<uniform> taa: TaaUniform;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+};
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) history: vec4<f32>,
+};
+
+// 画面全体を覆う三角形
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var vout: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    vout.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    return vout;
+}
+
+@fragment
+fn fs_main(fin: VertexOutput) -> FragmentOutput {
+    let size = vec2<i32>(textureDimensions(current_texture));
+    let coord = vec2<i32>(fin.clip_position.xy);
+    let current = textureLoad(current_texture, coord, 0);
+
+    // 3x3 近傍の範囲で履歴をクランプしてゴーストを抑える
+    var neighborhood_min = current.rgb;
+    var neighborhood_max = current.rgb;
+    for (var y = -1; y <= 1; y++) {
+        for (var x = -1; x <= 1; x++) {
+            let sample_coord = clamp(coord + vec2<i32>(x, y), vec2<i32>(0), size - 1);
+            let neighbor = textureLoad(current_texture, sample_coord, 0).rgb;
+            neighborhood_min = min(neighborhood_min, neighbor);
+            neighborhood_max = max(neighborhood_max, neighbor);
+        }
+    }
+
+    // 深度から前フレームの画面位置を求める
+    let uv = fin.clip_position.xy / vec2<f32>(size);
+    let depth = textureLoad(depth_texture, coord, 0).r;
+    let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0) - taa.jitter;
+    let previous_clip = taa.reprojection * vec4<f32>(ndc, depth, 1.0);
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let previous_uv = vec2<f32>(previous_ndc.x * 0.5 + 0.5, 0.5 - previous_ndc.y * 0.5);
+
+    let history = textureSampleLevel(history_texture, history_sampler, previous_uv, 0.0).rgb;
+    let clamped_history = clamp(history, neighborhood_min, neighborhood_max);
+
+    var blend = taa.blend;
+    if (any(previous_uv < vec2<f32>(0.0)) || any(previous_uv > vec2<f32>(1.0))) {
+        blend = 1.0;
+    }
+
+    let resolved = vec4<f32>(mix(clamped_history, current.rgb, blend), current.a);
+
+    var fout: FragmentOutput;
+    fout.color = resolved;
+    fout.history = resolved;
+    return fout;
+}
@@ -0,0 +1,39 @@
+struct ToneMappingUniform {
+    exposure: f32,
+    gamma: f32,
+    _padding: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var scene_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var scene_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> tone_mapping: ToneMappingUniform;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 画面全体を覆う三角形
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var vout: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    vout.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    // クリップ空間はY上向き、テクスチャ座標はY下向き
+    vout.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return vout;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let hdr = textureSample(scene_texture, scene_sampler, in.uv);
+    // 露出を掛けてから Reinhard で 0..1 に圧縮する
+    let exposed = max(hdr.rgb, vec3<f32>(0.0)) * tone_mapping.exposure;
+    let mapped = exposed / (vec3<f32>(1.0) + exposed);
+    // sRGB の出力ではハードウェアが符号化するので gamma は 1
+    let encoded = pow(mapped, vec3<f32>(1.0 / tone_mapping.gamma));
+    return vec4<f32>(encoded, clamp(hdr.a, 0.0, 1.0));
+}
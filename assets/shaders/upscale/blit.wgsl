@@ -0,0 +1,25 @@
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 画面全体を覆う三角形
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var vout: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    vout.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    // クリップ空間はY上向き、テクスチャ座標はY下向き
+    vout.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return vout;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(source_texture, source_sampler, in.uv);
+}
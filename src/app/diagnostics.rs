@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use winit::{application::ApplicationHandler, window::WindowAttributes};
+
+use crate::{
+    core::{config::AppConfig, logging::init_logger},
+    graphics::diagnostics::{DiagnosticsOptions, DiagnosticsReport, run_diagnostics},
+    window::Window,
+};
+
+/// Runs the startup self-test and prints the report to stdout.
+///
+/// A hidden window is created when a display is available so that surface
+/// formats and present modes can be reported; otherwise the test runs headless.
+/// Returns `true` when every check passed.
+pub fn run(options: DiagnosticsOptions) -> bool {
+    let loaded = AppConfig::load_from_file("config.toml");
+    init_logger(
+        &loaded
+            .as_ref()
+            .map(|config| config.logging.clone())
+            .unwrap_or_default(),
+    );
+    let config = AppConfig::loaded_or_default("config.toml", loaded);
+
+    let report = match winit::event_loop::EventLoop::new() {
+        Ok(event_loop) => {
+            let mut app = DiagnosticsApp {
+                config: config.clone(),
+                options,
+                report: None,
+            };
+            if let Err(e) = event_loop.run_app(&mut app) {
+                log::warn!("Diagnostics event loop error: {}", e);
+            }
+            app.report
+        }
+        Err(e) => {
+            log::warn!("No display available ({}), running headless diagnostics", e);
+            None
+        }
+    };
+
+    let report =
+        report.unwrap_or_else(|| pollster::block_on(run_diagnostics(&config, None, options)));
+
+    println!("{}", report);
+    report.passed()
+}
+
+struct DiagnosticsApp {
+    config: AppConfig,
+    options: DiagnosticsOptions,
+    report: Option<DiagnosticsReport>,
+}
+
+impl ApplicationHandler for DiagnosticsApp {
+    fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        if self.report.is_some() {
+            return;
+        }
+
+        let window = event_loop
+            .create_window(
+                WindowAttributes::default()
+                    .with_title(self.config.window.title.clone())
+                    .with_visible(false),
+            )
+            .map(|window| Window::new(Arc::new(window)));
+
+        let window = match window {
+            Ok(window) => Some(window),
+            Err(e) => {
+                log::warn!("Diagnostics window creation error: {}", e);
+                None
+            }
+        };
+
+        self.report = Some(pollster::block_on(run_diagnostics(
+            &self.config,
+            window.as_ref(),
+            self.options,
+        )));
+        event_loop.exit();
+    }
+
+    fn window_event(
+        &mut self,
+        _event_loop: &winit::event_loop::ActiveEventLoop,
+        _window_id: winit::window::WindowId,
+        _event: winit::event::WindowEvent,
+    ) {
+    }
+}
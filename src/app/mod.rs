@@ -1,12 +1,17 @@
+pub mod diagnostics;
+
 use std::sync::Arc;
 
 use winit::{application::ApplicationHandler, window::WindowAttributes};
 
 use crate::{
-    core::{config::AppConfig, logging::init_logger},
-    graphics::engine::GraphicsEngine,
+    core::{
+        config::AppConfig, config_watcher::ConfigWatcher, error::EngineError, logging::init_logger,
+    },
+    graphics::{engine::GraphicsEngine, screenshot::ColorSpace},
     input::InputState,
-    scene::{SceneId, demo_scene::DemoScene, manager::SceneManager},
+    resources::primitives::ObjectType,
+    scene::{Scene, SceneId, demo_scene::DemoScene, manager::SceneManager, transform::Transform},
     window::Window,
 };
 
@@ -15,21 +20,337 @@ pub struct App {
     engine: Option<GraphicsEngine>,
     input_state: InputState,
     last_frame_time: std::time::Instant,
+    /// Set while the window is unfocused or occluded; no frames are rendered.
+    paused: bool,
+    /// Draw the world axes and the last picking ray (F4).
+    show_gizmos: bool,
+    /// Ray `(origin, direction)` of the last click, shown with the gizmos.
+    pick_ray: Option<(glam::Vec3, glam::Vec3)>,
     scene_manager: SceneManager,
+    /// Scene currently owned by the engine.
+    active_scene_id: Option<SceneId>,
     config: Arc<AppConfig>,
+    /// `None` if the file system watcher could not be started.
+    config_watcher: Option<ConfigWatcher>,
+    /// When the fps in the window title was last refreshed.
+    last_title_update: std::time::Instant,
+    /// Whether the window title currently shows the fps, so the base title
+    /// can be restored once `show_fps_in_title` is turned off.
+    fps_in_title: bool,
+    #[cfg(feature = "gamepad")]
+    gamepad: crate::input::gamepad::GamepadPoller,
+}
+
+/// Config file loaded at startup and watched for live edits.
+const CONFIG_PATH: &str = "config.toml";
+/// Scenes selectable with the number keys.
+const DEMO_SCENE: &str = "Demo_Scene";
+const GALLERY_SCENE: &str = "Gallery_Scene";
+/// Length and color of the picking ray drawn with the gizmos.
+const PICK_RAY_LENGTH: f32 = 100.0;
+const PICK_RAY_COLOR: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+/// How often the fps in the window title is refreshed.
+const TITLE_UPDATE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Window title with the fps appended, e.g. `"Demo Engine - 59.9 FPS"`.
+fn title_with_fps(base_title: &str, fps: f32) -> String {
+    format!("{} - {:.1} FPS", base_title, fps)
 }
 
 impl App {
     pub fn new() -> Self {
-        init_logger();
+        // ログの設定を読むため、ロガーより先に設定を読み込み、失敗はロガーの準備後に報告する
+        let loaded = AppConfig::load_from_file(CONFIG_PATH);
+        init_logger(
+            &loaded
+                .as_ref()
+                .map(|config| config.logging.clone())
+                .unwrap_or_default(),
+        );
+        let config = AppConfig::loaded_or_default(CONFIG_PATH, loaded);
 
         App {
             window: None,
             engine: None,
             input_state: InputState::new(),
             last_frame_time: std::time::Instant::now(),
+            paused: false,
+            show_gizmos: false,
+            pick_ray: None,
             scene_manager: SceneManager::new(),
-            config: Arc::new(AppConfig::load_or_default("config.toml")),
+            active_scene_id: None,
+            config: Arc::new(config),
+            config_watcher: ConfigWatcher::new(CONFIG_PATH)
+                .inspect_err(|e| log::warn!("Config hot-reload disabled: {}", e))
+                .ok(),
+            last_title_update: std::time::Instant::now(),
+            fps_in_title: false,
+            #[cfg(feature = "gamepad")]
+            gamepad: crate::input::gamepad::GamepadPoller::new(),
+        }
+    }
+
+    /// config.toml が変更されていれば読み直してエンジンに反映する
+    fn reload_config_if_changed(&mut self) {
+        let Some(config) = self.config_watcher.as_ref().and_then(ConfigWatcher::poll) else {
+            return;
+        };
+
+        self.config = Arc::new(config);
+        if let Some(engine) = &mut self.engine {
+            engine.apply_config(&self.config);
+        }
+        log::info!("Reloaded {}", CONFIG_PATH);
+    }
+
+    /// Refreshes the fps in the window title about once a second, or restores
+    /// the base title after `show_fps_in_title` was turned off.
+    fn update_window_title(&mut self) {
+        let (Some(window), Some(engine)) = (&self.window, &self.engine) else {
+            return;
+        };
+
+        if self.config.window.show_fps_in_title {
+            if self.last_title_update.elapsed() < TITLE_UPDATE_INTERVAL {
+                return;
+            }
+            self.last_title_update = std::time::Instant::now();
+            let fps = engine.metrics_snapshot().fps;
+            window.set_title(&title_with_fps(&self.config.window.title, fps));
+            self.fps_in_title = true;
+        } else if self.fps_in_title {
+            window.set_title(&self.config.window.title);
+            self.fps_in_title = false;
+        }
+    }
+
+    fn create_scene(&self) -> Box<dyn Scene> {
+        Box::new(DemoScene::new(
+            self.config.window.width as f32 / self.config.window.height as f32,
+            self.config.clone(),
+        ))
+    }
+
+    /// 指定したシーンをエンジンに渡し、それまでのシーンを SceneManager に戻す
+    fn switch_scene(&mut self, name: &str) {
+        let scene_id = SceneId::new(name);
+        let (Some(engine), Some(active_scene_id)) = (&mut self.engine, self.active_scene_id) else {
+            return;
+        };
+        if active_scene_id == scene_id {
+            return;
+        }
+        if let Err(e) = self.scene_manager.set_current_scene(scene_id) {
+            log::error!("Failed to switch scene: {}", e);
+            return;
+        }
+        let Some(scene) = self.scene_manager.take_current_scene() else {
+            return;
+        };
+
+        let previous_scene = match engine.set_scene(scene) {
+            Ok(previous_scene) => previous_scene,
+            Err(e) => {
+                log::error!("Failed to switch scene: {}", e);
+                return;
+            }
+        };
+        self.scene_manager
+            .register_scene(active_scene_id, previous_scene);
+        self.active_scene_id = Some(scene_id);
+
+        // 初めて表示するギャラリーにはプリミティブを一列に並べる
+        let scene = engine.scene_mut();
+        if name == GALLERY_SCENE && scene.get_render_objects().is_empty() {
+            let count = ObjectType::ALL.len() as f32;
+            for (i, object_type) in ObjectType::ALL.iter().enumerate() {
+                let x = (i as f32 - (count - 1.0) / 2.0) * 1.5;
+                scene.add_object(*object_type, glam::vec3(x, 0.0, 0.0));
+            }
+        }
+        log::info!("Switched to scene {}", name);
+    }
+
+    /// 押下された瞬間にだけ反応するホットキーを処理する
+    fn handle_hotkey(&mut self, keycode: winit::keyboard::KeyCode) {
+        use winit::keyboard::KeyCode;
+
+        match keycode {
+            // 数字キーでシーンを切り替え
+            KeyCode::Digit1 => self.switch_scene(DEMO_SCENE),
+            KeyCode::Digit2 => self.switch_scene(GALLERY_SCENE),
+            // F2 で垂直同期を切り替え
+            KeyCode::F2 => {
+                if let Some(engine) = &mut self.engine {
+                    let vsync = !engine.vsync();
+                    engine.set_vsync(vsync);
+                }
+            }
+            // F3 でメトリクス表示を切り替え
+            KeyCode::F3 => {
+                if let Some(engine) = &mut self.engine {
+                    let show_metrics = !engine.show_metrics();
+                    engine.set_show_metrics(show_metrics);
+                }
+            }
+            // F4 でワールド軸と選択レイの表示を切り替え
+            KeyCode::F4 => {
+                self.show_gizmos = !self.show_gizmos;
+                log::info!("Gizmos: {}", self.show_gizmos);
+            }
+            // F7 でフレームごとの計測値のCSV記録を開始・停止
+            KeyCode::F7 => self.toggle_metrics_recording(),
+            // F8 でメトリクスをリセット
+            KeyCode::F8 => {
+                if let Some(engine) = &mut self.engine {
+                    engine.reset_metrics();
+                }
+            }
+            // F9 でフレーム時間ヒストグラムをCSV出力
+            KeyCode::F9 => self.export_frame_time_histogram(),
+            // F10 で現在の設定を config.toml に保存
+            KeyCode::F10 => self.save_config(),
+            // F11 でボーダーレスフルスクリーンを切り替え（サーフェスは Resized で再設定される）
+            KeyCode::F11 => {
+                if let Some(window) = &self.window {
+                    let fullscreen = window.toggle_fullscreen();
+                    log::info!("Fullscreen: {}", fullscreen);
+                }
+            }
+            // Tab でマウスルック用のカーソルキャプチャを切り替え
+            KeyCode::Tab => self.toggle_cursor_capture(),
+            // F12 で現在のフレームをPNGに保存
+            KeyCode::F12 => self.capture_screenshot(),
+            _ => {}
+        }
+    }
+
+    fn toggle_cursor_capture(&mut self) {
+        self.set_cursor_captured(!self.input_state.is_cursor_captured());
+    }
+
+    fn set_cursor_captured(&mut self, captured: bool) {
+        if self.input_state.is_cursor_captured() == captured {
+            return;
+        }
+        self.input_state.set_cursor_captured(captured);
+        if let Some(window) = &self.window {
+            window.set_cursor_captured(captured);
+        }
+        log::info!(
+            "Mouse look {}",
+            if captured { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Stops or restarts the redraw loop.
+    fn set_paused(&mut self, paused: bool) {
+        if self.paused == paused {
+            return;
+        }
+        self.paused = paused;
+
+        if !paused {
+            // 停止中の経過時間を dt に含めない
+            self.last_frame_time = std::time::Instant::now();
+            if let Some(window) = &self.window {
+                window.get_window().request_redraw();
+            }
+        }
+        log::info!("Rendering {}", if paused { "paused" } else { "resumed" });
+    }
+
+    fn capture_screenshot(&self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        if let Err(e) =
+            engine.capture_frame(&format!("screenshot_{}.png", timestamp), ColorSpace::Srgb)
+        {
+            log::error!("{}", e);
+        }
+    }
+
+    fn toggle_metrics_recording(&mut self) {
+        let Some(engine) = &mut self.engine else {
+            return;
+        };
+
+        let result = if engine.is_recording_metrics() {
+            engine.stop_metrics_recording()
+        } else {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            engine.start_metrics_recording(&format!("metrics_{}.csv", timestamp))
+        };
+        if let Err(e) = result {
+            log::error!("{}", e);
+        }
+    }
+
+    fn pick_object_under_cursor(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+
+        let mouse_position = self.input_state.mouse_position();
+        self.pick_ray = Some(engine.screen_to_ray(mouse_position));
+        match engine.pick_object(mouse_position) {
+            Some(object_id) => log::info!("Selected object {}", object_id.as_u32()),
+            None => log::info!("No object under cursor"),
+        }
+    }
+
+    /// Submits the world axes at the origin and the last picking ray as
+    /// debug lines for the next frame.
+    fn draw_gizmos(&mut self) {
+        let Some(engine) = &mut self.engine else {
+            return;
+        };
+
+        let lines = engine.debug_lines_mut();
+        lines.draw_axes(&Transform::new());
+        if let Some((origin, direction)) = self.pick_ray {
+            lines.draw_line(origin, origin + direction * PICK_RAY_LENGTH, PICK_RAY_COLOR);
+        }
+    }
+
+    /// Writes the current runtime settings back to config.toml.
+    fn save_config(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+
+        let mut config = engine.export_config();
+        if let Some(window) = &self.window {
+            config.window.fullscreen = window.is_fullscreen();
+        }
+        match config.save_to_file(CONFIG_PATH) {
+            Ok(()) => {
+                log::info!("Saved settings to {}", CONFIG_PATH);
+                self.config = Arc::new(config);
+            }
+            Err(e) => log::error!("Failed to save {}: {}", CONFIG_PATH, e),
+        }
+    }
+
+    fn export_frame_time_histogram(&self) {
+        if !self.config.metrics.histogram_enabled {
+            return;
+        }
+
+        if let Some(engine) = &self.engine
+            && let Err(e) =
+                engine.export_frame_time_histogram(&self.config.metrics.histogram_export_path)
+        {
+            log::error!("{}", e);
         }
     }
 }
@@ -45,7 +366,13 @@ impl ApplicationHandler for App {
                             self.config.window.width,
                             self.config.window.height,
                         ))
-                        .with_resizable(self.config.window.resizable),
+                        .with_resizable(self.config.window.resizable)
+                        .with_fullscreen(
+                            self.config
+                                .window
+                                .fullscreen
+                                .then_some(winit::window::Fullscreen::Borderless(None)),
+                        ),
                 )
                 .map_err(|e| {
                     log::error!("Window creation error: {}", e);
@@ -53,13 +380,11 @@ impl ApplicationHandler for App {
                 .unwrap(),
         );
 
-        let scene_id = SceneId::new("Demo_Scene");
-        let demo_scene = Box::new(DemoScene::new(
-            self.config.window.width as f32 / self.config.window.height as f32,
-            self.config.clone(),
-        ));
-
-        self.scene_manager.register_scene(scene_id, demo_scene);
+        let scene_id = SceneId::new(DEMO_SCENE);
+        self.scene_manager
+            .register_scene(scene_id, self.create_scene());
+        self.scene_manager
+            .register_scene(SceneId::new(GALLERY_SCENE), self.create_scene());
         if let Err(e) = self.scene_manager.set_current_scene(scene_id) {
             log::error!("Failed to set current scene: {}", e);
             return;
@@ -75,7 +400,7 @@ impl ApplicationHandler for App {
         let engine = match pollster::block_on(GraphicsEngine::new(
             window.clone(),
             current_scene,
-            &self.config.rendering,
+            &self.config,
         )) {
             Ok(engine) => engine,
             Err(e) => {
@@ -86,6 +411,7 @@ impl ApplicationHandler for App {
 
         self.window = Some(window.clone());
         self.engine = Some(engine);
+        self.active_scene_id = Some(scene_id);
 
         window.get_window().request_redraw();
     }
@@ -105,19 +431,35 @@ impl ApplicationHandler for App {
                     engine.resize(size.width, size.height);
                 }
             }
+            // 停止中は描画せず、次フレームも要求しない
+            winit::event::WindowEvent::RedrawRequested if self.paused => {}
             winit::event::WindowEvent::RedrawRequested => {
+                self.reload_config_if_changed();
+                #[cfg(feature = "gamepad")]
+                self.gamepad.poll(&mut self.input_state);
+                if self.show_gizmos {
+                    self.draw_gizmos();
+                }
+
                 if let Some(engine) = &mut self.engine {
                     // 実際のdelta timeを計算
                     let now = std::time::Instant::now();
                     let dt = (now - self.last_frame_time).as_secs_f32();
                     self.last_frame_time = now;
 
-                    if let Err(e) = engine.render(dt, &self.input_state) {
-                        log::error!("Rendering error: {}", e);
+                    match engine.render(dt, &self.input_state) {
+                        Ok(()) => {}
+                        // メモリ不足からは回復できないので終了する
+                        Err(e @ EngineError::OutOfMemory(_)) => {
+                            log::error!("Fatal rendering error: {}", e);
+                            event_loop.exit();
+                        }
+                        Err(e) => log::error!("Rendering error: {}", e),
                     }
                 }
+                self.update_window_title();
 
-                self.input_state.reset_mouse_delta();
+                self.input_state.end_frame();
 
                 // 継続的なレンダリングのため次フレームをリクエスト
                 if let Some(window) = &self.window {
@@ -135,6 +477,13 @@ impl ApplicationHandler for App {
                     event_loop.exit();
                 }
 
+                if let winit::keyboard::PhysicalKey::Code(keycode) = event.physical_key
+                    && event.state.is_pressed()
+                    && !event.repeat
+                {
+                    self.handle_hotkey(keycode);
+                }
+
                 // キー入力後に再描画をリクエスト
                 if let Some(window) = &self.window {
                     window.get_window().request_redraw();
@@ -142,12 +491,75 @@ impl ApplicationHandler for App {
             }
             winit::event::WindowEvent::MouseInput { state, button, .. } => {
                 self.input_state.process_mouse_input(button, state);
+
+                // カーソル捕捉中の左クリックは射撃に使うので、解放中だけ選択する
+                if button == winit::event::MouseButton::Left
+                    && state.is_pressed()
+                    && !self.input_state.is_cursor_captured()
+                {
+                    self.pick_object_under_cursor();
+                }
             }
             winit::event::WindowEvent::CursorMoved { position, .. } => {
                 self.input_state
                     .set_mouse_position(position.x as f32, position.y as f32);
             }
+            winit::event::WindowEvent::MouseWheel { delta, .. } => {
+                self.input_state.process_scroll(delta);
+            }
+            // フォーカスを失ったらカーソルを解放して他のウィンドウを操作できるようにする
+            winit::event::WindowEvent::Focused(false) => {
+                self.set_cursor_captured(false);
+                self.set_paused(true);
+            }
+            winit::event::WindowEvent::Focused(true) => {
+                self.set_paused(false);
+            }
+            winit::event::WindowEvent::Occluded(occluded) => {
+                self.set_paused(occluded);
+            }
             _ => {}
         }
     }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &winit::event_loop::ActiveEventLoop,
+        _device_id: winit::event::DeviceId,
+        event: winit::event::DeviceEvent,
+    ) {
+        if let winit::event::DeviceEvent::MouseMotion { delta } = event {
+            self.input_state
+                .process_mouse_motion(delta.0 as f32, delta.1 as f32);
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
+        self.export_frame_time_histogram();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_constructs_without_window() {
+        let app = App::new();
+        // ウィンドウとエンジンは resumed() まで作られない
+        assert!(app.window.is_none());
+        assert!(app.engine.is_none());
+        assert!(!app.paused);
+
+        // 2つ目を作ってもロガーの初期化で落ちない
+        let _second = App::new();
+    }
+
+    #[test]
+    fn test_title_with_fps_appends_rounded_fps() {
+        assert_eq!(
+            title_with_fps("Demo Engine", 59.94),
+            "Demo Engine - 59.9 FPS"
+        );
+    }
 }
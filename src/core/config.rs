@@ -1,90 +1,737 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+use serde::{Deserialize, Serialize, de::IntoDeserializer};
+use winit::keyboard::KeyCode;
+
+use crate::{
+    core::error::{EngineError, EngineResult},
+    input::InputState,
+    resources::pipeline::SUPPORTED_SAMPLE_COUNTS,
+};
+
+/// `config.toml` の設定
+///
+/// セクション・項目はすべて省略可能で、省略した値は `Default` のものになる。
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub window: WindowConfig,
     pub camera: CameraConfig,
     pub movement: MovementConfig,
     pub rendering: RenderingConfig,
+    pub metrics: MetricsConfig,
+    pub debug: DebugConfig,
+    pub lighting: LightConfig,
+    pub projectile: ProjectileConfig,
+    pub physics: PhysicsConfig,
+    pub keybindings: InputMap,
+    pub logging: LoggingConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct WindowConfig {
     pub width: u32,
     pub height: u32,
     pub title: String,
     pub resizable: bool,
+    /// ボーダーレスフルスクリーンで起動する（F11で切り替え）
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// ウィンドウタイトルに現在のFPSを約1秒ごとに表示する
+    #[serde(default)]
+    pub show_fps_in_title: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct CameraConfig {
     pub fov_degrees: f32,
     pub znear: f32,
     pub zfar: f32,
+    /// カメラ操作モード（FPS移動 / ターゲット周りのオービット）
+    #[serde(default)]
+    pub mode: CameraMode,
+    /// 投影方式（省略時は `fov_degrees` による透視投影）
+    #[serde(default)]
+    pub projection: ProjectionConfig,
+    /// ズーム時に視野角が目標へ近づく速さ（1秒あたり、大きいほど速い）
+    #[serde(default = "default_zoom_speed")]
+    pub zoom_speed: f32,
+    /// FPSモードの移動方式（飛行 / 歩行）
+    #[serde(default)]
+    pub movement: CameraMovement,
+}
+
+fn default_zoom_speed() -> f32 {
+    10.0
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum ProjectionConfig {
+    /// `fov_degrees` を使った透視投影
+    #[default]
+    Perspective,
+    /// 平行投影（`height` は画面に映るワールド空間の高さ）
+    Orthographic { height: f32 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CameraMode {
+    /// WASD/QEで移動、矢印キーで視点回転
+    #[default]
+    Fps,
+    /// 右ドラッグでターゲット周りを回転、ホイールで距離を変更
+    Orbit,
+}
+
+/// FPSモードでの移動の仕方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CameraMovement {
+    /// 向いている方向へ自由に移動し、上下移動はカメラの上方向に沿う
+    #[default]
+    Fly,
+    /// 前後左右の移動で高さを変えず、上下移動はワールドのY方向に沿う
+    Walk,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct MovementConfig {
     pub move_speed: f32,
     pub rotation_speed: f32,
     pub mouse_sensitivity: f32,
+    /// カメラ位置を閉じ込める軸平行ボックス `[min, max]`（未設定なら制限なし）
+    #[serde(default)]
+    pub bounds: Option<[[f32; 3]; 2]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PresentModeConfig {
+    /// 垂直同期（`Fifo`、すべての環境で利用可能）
+    Vsync,
+    /// 上限なし（ティアリングあり）
+    Immediate,
+    /// 上限なしでティアリングなし（非対応なら `Vsync`）
+    Mailbox,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct RenderingConfig {
     pub clear_color: [f32; 4],
+    /// `present_mode` 未指定時に使う（true = Vsync、false = Immediate）
     pub vsync: bool,
+    /// 表示モード（指定時は `vsync` より優先）
+    #[serde(default)]
+    pub present_mode: Option<PresentModeConfig>,
     pub msaa_samples: u32,
+    /// シーンパスで保守的ラスタライズを使う（対応アダプタのみ）
+    #[serde(default)]
+    pub conservative_raster: bool,
+    /// sRGBのサーフェスフォーマットを使う（false なら非sRGBフォーマットを選び、色の問題の切り分けに使う）
+    #[serde(default = "default_srgb")]
+    pub srgb: bool,
+    /// sRGBフォーマットがないサーフェスでsRGBビューを使う（対応アダプタのみ）
+    #[serde(default = "default_srgb_view_fallback")]
+    pub srgb_view_fallback: bool,
+    /// テンポラルアンチエイリアス（有効時はMSAAを無効化）
+    #[serde(default)]
+    pub taa: bool,
+    /// メッシュをワイヤーフレームで描画する（`POLYGON_MODE_LINE` 対応アダプタのみ、F1で切り替え）
+    #[serde(default)]
+    pub wireframe: bool,
+    /// Triangle・Quad・Plane を背面カリングせず両面描画する
+    #[serde(default)]
+    pub double_sided_flat: bool,
+    /// 使用するグラフィックスAPI（"vulkan"・"dx12"・"metal"・"gl"、未指定なら自動選択）
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// 内部解像度の倍率（1.0 = サーフェスと同じ、0.25〜2.0）
+    #[serde(default = "default_render_scale")]
+    pub render_scale: f32,
+    /// 左上にfps・フレーム時間・オブジェクト数を表示する（F3で切り替え）
+    #[serde(default)]
+    pub show_metrics: bool,
+    /// HDRで描画し、露出・Reinhardトーンマッピング・ガンマ補正をかけてから表示する
+    #[serde(default)]
+    pub tone_mapping: bool,
+    /// トーンマッピング前に色へ掛ける露出（`tone_mapping` 有効時のみ）
+    #[serde(default = "default_exposure")]
+    pub exposure: f32,
+    /// 完成したフレームにFXAAをかける（MSAAより軽いアンチエイリアス）
+    #[serde(default)]
+    pub fxaa: bool,
+    /// 平行光源からのシャドウマップで影を落とす
+    #[serde(default)]
+    pub shadows: bool,
+    #[serde(default)]
+    pub adaptive: AdaptiveQualityConfig,
+}
+
+impl RenderingConfig {
+    /// Present mode to use: `present_mode` if set, otherwise derived from `vsync`.
+    pub fn present_mode(&self) -> PresentModeConfig {
+        self.present_mode.unwrap_or(if self.vsync {
+            PresentModeConfig::Vsync
+        } else {
+            PresentModeConfig::Immediate
+        })
+    }
+}
+
+fn default_srgb() -> bool {
+    true
 }
 
-impl Default for AppConfig {
+fn default_srgb_view_fallback() -> bool {
+    true
+}
+
+fn default_render_scale() -> f32 {
+    1.0
+}
+
+fn default_exposure() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AdaptiveQualityConfig {
+    /// fpsに応じて `render_scale` を自動調整する（`render_scale` が上限）
+    pub enabled: bool,
+    /// 維持したいフレームレート
+    pub target_fps: f32,
+    /// 自動調整で下げられる最小の倍率
+    pub min_scale: f32,
+}
+
+impl Default for AdaptiveQualityConfig {
     fn default() -> Self {
         Self {
-            window: WindowConfig {
-                width: 800,
-                height: 600,
-                title: "Demo Engine".to_string(),
-                resizable: true,
-            },
-            camera: CameraConfig {
-                fov_degrees: 45.0,
-                znear: 0.1,
-                zfar: 100.0,
-            },
-            movement: MovementConfig {
-                move_speed: 5.0,
-                rotation_speed: 1.0,
-                mouse_sensitivity: 0.001,
-            },
-            rendering: RenderingConfig {
-                clear_color: [0.5, 0.2, 0.2, 1.0],
-                vsync: true,
-                msaa_samples: 1,
-            },
+            enabled: false,
+            target_fps: 60.0,
+            min_scale: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// FPS平均を計算するフレーム数
+    #[serde(default = "default_metrics_window_size")]
+    pub window_size: usize,
+    /// フレーム時間ヒストグラムを集計するか
+    pub histogram_enabled: bool,
+    /// 各バケットの上限値（ミリ秒、昇順）。上限を超えた値は最後のオーバーフローバケットに入る
+    pub histogram_buckets_ms: Vec<f32>,
+    /// ヒストグラムCSVの出力先（キー操作・終了時）
+    pub histogram_export_path: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LightConfig {
+    /// 平行光源の進む向き（光源からシーンへ、正規化は不要）
+    #[serde(default = "default_light_direction")]
+    pub direction: [f32; 3],
+    /// 平行光源の色（リニアRGB）
+    #[serde(default = "default_light_color")]
+    pub color: [f32; 3],
+    /// `sun.animate` が有効な間は太陽が向きと色を上書きする
+    #[serde(default)]
+    pub sun: SunConfig,
+}
+
+fn default_light_direction() -> [f32; 3] {
+    [-0.4, -1.0, -0.6]
+}
+
+fn default_light_color() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+impl Default for LightConfig {
+    fn default() -> Self {
+        Self {
+            direction: default_light_direction(),
+            color: default_light_color(),
+            sun: SunConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SunConfig {
+    /// 太陽を時刻に合わせて動かし、平行光源を駆動する
+    pub animate: bool,
+    /// 日の出から日の入りまでの秒数
+    pub day_length_seconds: f32,
+}
+
+impl Default for SunConfig {
+    fn default() -> Self {
+        Self {
+            animate: false,
+            day_length_seconds: 120.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProjectileConfig {
+    /// 発射速度（ワールド単位/秒）
+    pub speed: f32,
+    /// 発射から消えるまでの秒数
+    pub lifetime_seconds: f32,
+    /// この高さまで落ちたら消える
+    pub ground_height: f32,
+    /// 重力を適用するか
+    pub use_gravity: bool,
+}
+
+impl Default for ProjectileConfig {
+    fn default() -> Self {
+        Self {
+            speed: 15.0,
+            lifetime_seconds: 5.0,
+            ground_height: -3.0,
+            use_gravity: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PhysicsConfig {
+    /// 物理更新の固定ステップ（秒）。描画時は前後のステップ間を補間する
+    pub fixed_timestep: f32,
+    /// 1フレームで実行する最大ステップ数（超過分は切り捨て）
+    pub max_steps_per_frame: u32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            fixed_timestep: 1.0 / 60.0,
+            max_steps_per_frame: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// 出力するログの最低レベル（"off"・"error"・"warn"・"info"・"debug"・"trace"）。
+    /// 環境変数 `RUST_LOG` が設定されていればそちらを優先する
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    /// ログを追記するファイル（未指定なら標準エラー出力）
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_log_level(),
+            file: None,
+        }
+    }
+}
+
+/// キーに割り当てられるカメラ操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    TurnLeft,
+    TurnRight,
+    LookUp,
+    LookDown,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::MoveForward,
+        Action::MoveBackward,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::TurnLeft,
+        Action::TurnRight,
+        Action::LookUp,
+        Action::LookDown,
+    ];
+
+    /// 既定のキー割り当て（WASD/QE で移動、矢印キーで回転）
+    fn default_keys(self) -> Vec<KeyCode> {
+        let key = match self {
+            Action::MoveForward => KeyCode::KeyW,
+            Action::MoveBackward => KeyCode::KeyS,
+            Action::MoveLeft => KeyCode::KeyA,
+            Action::MoveRight => KeyCode::KeyD,
+            Action::MoveUp => KeyCode::KeyE,
+            Action::MoveDown => KeyCode::KeyQ,
+            Action::TurnLeft => KeyCode::ArrowLeft,
+            Action::TurnRight => KeyCode::ArrowRight,
+            Action::LookUp => KeyCode::ArrowUp,
+            Action::LookDown => KeyCode::ArrowDown,
+        };
+        vec![key]
+    }
+}
+
+/// 操作ごとのキー割り当て（`[keybindings]` セクション、キー名は winit の `KeyCode` 名）
+///
+/// 省略した操作は既定のキーのまま。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(
+    try_from = "BTreeMap<Action, Vec<String>>",
+    into = "BTreeMap<Action, Vec<String>>"
+)]
+pub struct InputMap {
+    bindings: BTreeMap<Action, Vec<KeyCode>>,
+}
+
+impl InputMap {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// 操作にキーを割り当て直す（空にすると無効化）
+    #[allow(dead_code)]
+    pub fn bind(&mut self, action: Action, keys: Vec<KeyCode>) {
+        self.bindings.insert(action, keys);
+    }
+
+    /// 割り当てられたキーのいずれかが押されていれば true
+    pub fn is_action_active(&self, action: Action, input: &InputState) -> bool {
+        self.keys(action)
+            .iter()
+            .any(|key| input.is_key_pressed(*key))
+    }
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<BTreeMap<Action, Vec<String>>> for InputMap {
+    type Error = String;
+
+    fn try_from(names: BTreeMap<Action, Vec<String>>) -> Result<Self, Self::Error> {
+        let mut input_map = Self::default();
+        for (action, key_names) in names {
+            let keys = key_names
+                .iter()
+                .map(|name| parse_key_code(name).map_err(|e| format!("{:?}: {}", action, e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            input_map.bindings.insert(action, keys);
+        }
+        Ok(input_map)
+    }
+}
+
+impl From<InputMap> for BTreeMap<Action, Vec<String>> {
+    fn from(input_map: InputMap) -> Self {
+        input_map
+            .bindings
+            .into_iter()
+            .map(|(action, keys)| {
+                (
+                    action,
+                    keys.iter().map(|key| format!("{:?}", key)).collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// `"KeyW"` や `"ArrowUp"` のような名前を `KeyCode` に変換する
+fn parse_key_code(name: &str) -> Result<KeyCode, String> {
+    let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+        name.into_deserializer();
+    KeyCode::deserialize(deserializer).map_err(|_| {
+        format!(
+            "unknown key name `{}` (expected a winit KeyCode name such as \"KeyW\" or \"ArrowUp\")",
+            name
+        )
+    })
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DebugConfig {
+    /// フレーム番号・パス名付きのGPUラベルとデバッググループを出力する（RenderDoc/PIX向け）
+    pub gpu_labels: bool,
+    /// シェーダーをファイルから読み込み、F5で再読み込みする（開発用）
+    #[serde(default)]
+    pub shader_hot_reload: bool,
+    /// ホットリロード時にシェーダーファイルを読み込むディレクトリ
+    #[serde(default = "default_shader_dir")]
+    pub shader_dir: String,
+    /// XZ平面の目盛りグリッドを表示する（F6で切り替え）
+    #[serde(default)]
+    pub show_grid: bool,
+    /// グリッドの一辺の長さ（1単位間隔の線を引く）
+    #[serde(default = "default_grid_size")]
+    pub grid_size: u32,
+}
+
+fn default_shader_dir() -> String {
+    "assets/shaders".to_string()
+}
+
+fn default_grid_size() -> u32 {
+    20
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        Self {
+            gpu_labels: false,
+            shader_hot_reload: false,
+            shader_dir: default_shader_dir(),
+            show_grid: false,
+            grid_size: default_grid_size(),
+        }
+    }
+}
+
+fn default_metrics_window_size() -> usize {
+    60
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            window_size: default_metrics_window_size(),
+            histogram_enabled: false,
+            histogram_buckets_ms: vec![4.0, 8.0, 16.7, 25.0, 33.3, 50.0, 100.0],
+            histogram_export_path: "frame_time_histogram.csv".to_string(),
+        }
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            title: "Demo Engine".to_string(),
+            resizable: true,
+            fullscreen: false,
+            show_fps_in_title: false,
+        }
+    }
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            fov_degrees: 45.0,
+            znear: 0.1,
+            zfar: 100.0,
+            mode: CameraMode::Fps,
+            projection: ProjectionConfig::Perspective,
+            zoom_speed: default_zoom_speed(),
+            movement: CameraMovement::Fly,
+        }
+    }
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            move_speed: 5.0,
+            rotation_speed: 1.0,
+            mouse_sensitivity: 0.001,
+            bounds: None,
+        }
+    }
+}
+
+impl Default for RenderingConfig {
+    fn default() -> Self {
+        Self {
+            clear_color: [0.5, 0.2, 0.2, 1.0],
+            vsync: true,
+            present_mode: None,
+            msaa_samples: 1,
+            conservative_raster: false,
+            srgb: default_srgb(),
+            srgb_view_fallback: true,
+            taa: false,
+            wireframe: false,
+            double_sided_flat: false,
+            backend: None,
+            render_scale: default_render_scale(),
+            show_metrics: false,
+            tone_mapping: false,
+            exposure: default_exposure(),
+            fxaa: false,
+            shadows: false,
+            adaptive: AdaptiveQualityConfig::default(),
         }
     }
 }
 
 impl AppConfig {
-    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Loads a config from a TOML file and checks it with [`AppConfig::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::Io` if the file cannot be read and
+    /// `EngineError::Config` if it is not a valid config, listing every
+    /// out-of-range value.
+    pub fn load_from_file(path: &str) -> EngineResult<Self> {
         let constant = std::fs::read_to_string(path)?;
         let config: AppConfig = toml::from_str(&constant)?;
+        config.validate().map_err(|violations| {
+            EngineError::Config(format!("Invalid config: {}", violations.join("; ")))
+        })?;
         Ok(config)
     }
 
+    /// Loads a config from a TOML file, falling back to the defaults (and
+    /// logging why) if it cannot be read or is invalid.
+    #[allow(dead_code)]
     pub fn load_or_default(path: &str) -> Self {
-        if let Ok(home) = std::env::current_dir() {
-            let config_path = home.join(path);
-            if let Ok(config) = Self::load_from_file(&config_path.to_string_lossy()) {
-                return config;
-            }
+        Self::loaded_or_default(path, Self::load_from_file(path))
+    }
+
+    /// Returns the config loaded from `path`, or logs the error and returns
+    /// the defaults. Lets the caller load before the logger is installed and
+    /// report afterwards.
+    pub fn loaded_or_default(path: &str, loaded: EngineResult<Self>) -> Self {
+        loaded.unwrap_or_else(|e| {
+            log::warn!("Using default config, {} could not be loaded: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Checks values that would make the window, camera or renderer fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns one message per violation, naming the offending setting.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut violations = Vec::new();
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+
+        if self.window.width == 0 || self.window.height == 0 {
+            violations.push(format!(
+                "window size must be non-zero, got {}x{}",
+                self.window.width, self.window.height
+            ));
+        }
+        let camera = &self.camera;
+        if !positive(camera.fov_degrees) || camera.fov_degrees >= 180.0 {
+            violations.push(format!(
+                "camera.fov_degrees must be between 0 and 180, got {}",
+                camera.fov_degrees
+            ));
+        }
+        if !positive(camera.znear) {
+            violations.push(format!(
+                "camera.znear must be positive, got {}",
+                camera.znear
+            ));
+        }
+        if !(camera.zfar.is_finite() && camera.znear < camera.zfar) {
+            violations.push(format!(
+                "camera.znear ({}) must be less than camera.zfar ({})",
+                camera.znear, camera.zfar
+            ));
+        }
+        if let ProjectionConfig::Orthographic { height } = camera.projection
+            && !positive(height)
+        {
+            violations.push(format!(
+                "camera.projection height must be positive, got {}",
+                height
+            ));
+        }
+        if let Some([min, max]) = self.movement.bounds
+            && min.iter().zip(&max).any(|(min, max)| min > max)
+        {
+            violations.push(format!(
+                "movement.bounds min {:?} must not exceed max {:?} on any axis",
+                min, max
+            ));
+        }
+        if !SUPPORTED_SAMPLE_COUNTS.contains(&self.rendering.msaa_samples) {
+            violations.push(format!(
+                "rendering.msaa_samples must be one of {:?}, got {}",
+                SUPPORTED_SAMPLE_COUNTS, self.rendering.msaa_samples
+            ));
+        }
+        if !positive(self.rendering.exposure) {
+            violations.push(format!(
+                "rendering.exposure must be positive, got {}",
+                self.rendering.exposure
+            ));
+        }
+        let rendering = &self.rendering;
+        if !positive(rendering.render_scale) {
+            violations.push(format!(
+                "rendering.render_scale must be positive, got {}",
+                rendering.render_scale
+            ));
+        }
+        if !positive(rendering.adaptive.min_scale) {
+            violations.push(format!(
+                "rendering.adaptive.min_scale must be positive, got {}",
+                rendering.adaptive.min_scale
+            ));
+        } else if positive(rendering.render_scale)
+            && rendering.adaptive.min_scale > rendering.render_scale
+        {
+            violations.push(format!(
+                "rendering.adaptive.min_scale ({}) must not exceed rendering.render_scale ({})",
+                rendering.adaptive.min_scale, rendering.render_scale
+            ));
+        }
+        if self.metrics.window_size == 0 {
+            violations.push("metrics.window_size must be non-zero".to_string());
+        }
+        if !positive(self.physics.fixed_timestep) {
+            violations.push(format!(
+                "physics.fixed_timestep must be positive, got {}",
+                self.physics.fixed_timestep
+            ));
         }
 
-        Self::default()
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
     }
 
-    #[allow(dead_code)]
-    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn save_to_file(&self, path: &str) -> EngineResult<()> {
         let path_buf = std::path::Path::new(path);
         if let Some(parent) = path_buf.parent() {
             std::fs::create_dir_all(parent)?;
@@ -108,21 +755,88 @@ mod tests {
                 height: 1080,
                 title: "Test Demo Engine".to_string(),
                 resizable: false,
+                fullscreen: true,
+                show_fps_in_title: true,
             },
             camera: CameraConfig {
                 fov_degrees: 60.0,
                 znear: 0.05,
                 zfar: 500.0,
+                mode: CameraMode::Orbit,
+                projection: ProjectionConfig::Orthographic { height: 8.0 },
+                zoom_speed: 4.0,
+                movement: CameraMovement::Walk,
             },
             movement: MovementConfig {
                 move_speed: 8.0,
                 rotation_speed: 1.5,
                 mouse_sensitivity: 0.002,
+                bounds: Some([[-10.0, 0.0, -10.0], [10.0, 5.0, 10.0]]),
             },
             rendering: RenderingConfig {
                 clear_color: [0.1, 0.2, 0.3, 1.0],
                 vsync: false,
+                present_mode: Some(PresentModeConfig::Mailbox),
                 msaa_samples: 4,
+                conservative_raster: true,
+                srgb: false,
+                srgb_view_fallback: false,
+                taa: true,
+                wireframe: true,
+                double_sided_flat: true,
+                backend: Some("vulkan".to_string()),
+                render_scale: 0.75,
+                show_metrics: true,
+                tone_mapping: true,
+                exposure: 1.5,
+                fxaa: true,
+                shadows: true,
+                adaptive: AdaptiveQualityConfig {
+                    enabled: true,
+                    target_fps: 30.0,
+                    min_scale: 0.25,
+                },
+            },
+            metrics: MetricsConfig {
+                window_size: 120,
+                histogram_enabled: true,
+                histogram_buckets_ms: vec![10.0, 20.0, 40.0],
+                histogram_export_path: "histogram.csv".to_string(),
+            },
+            debug: DebugConfig {
+                gpu_labels: true,
+                shader_hot_reload: true,
+                shader_dir: "shaders".to_string(),
+                show_grid: true,
+                grid_size: 8,
+            },
+            lighting: LightConfig {
+                direction: [1.0, -1.0, 0.0],
+                color: [1.0, 0.9, 0.8],
+                sun: SunConfig {
+                    animate: true,
+                    day_length_seconds: 30.0,
+                },
+            },
+            projectile: ProjectileConfig {
+                speed: 20.0,
+                lifetime_seconds: 3.0,
+                ground_height: -5.0,
+                use_gravity: false,
+            },
+            physics: PhysicsConfig {
+                fixed_timestep: 0.02,
+                max_steps_per_frame: 3,
+            },
+            keybindings: {
+                let mut keybindings = InputMap::default();
+                keybindings.bind(Action::MoveForward, vec![KeyCode::KeyI, KeyCode::Numpad8]);
+                keybindings.bind(Action::MoveDown, Vec::new());
+                keybindings
+            },
+            logging: LoggingConfig {
+                level: "warn".to_string(),
+                file: Some("demo_engine.log".to_string()),
             },
         }
     }
@@ -136,21 +850,77 @@ mod tests {
         assert_eq!(config.window.height, 600);
         assert_eq!(config.window.title, "Demo Engine");
         assert!(config.window.resizable);
+        assert!(!config.window.fullscreen);
 
         // Camera設定のテスト
         assert_eq!(config.camera.fov_degrees, 45.0);
         assert_eq!(config.camera.znear, 0.1);
         assert_eq!(config.camera.zfar, 100.0);
+        assert_eq!(config.camera.mode, CameraMode::Fps);
+        assert_eq!(config.camera.projection, ProjectionConfig::Perspective);
 
         // Movement設定のテスト
         assert_eq!(config.movement.move_speed, 5.0);
         assert_eq!(config.movement.rotation_speed, 1.0);
         assert_eq!(config.movement.mouse_sensitivity, 0.001);
+        assert!(config.movement.bounds.is_none());
 
         // Rendering設定のテスト
         assert_eq!(config.rendering.clear_color, [0.5, 0.2, 0.2, 1.0]);
         assert!(config.rendering.vsync);
         assert_eq!(config.rendering.msaa_samples, 1);
+        assert!(!config.rendering.conservative_raster);
+        assert!(config.rendering.srgb_view_fallback);
+        assert!(!config.rendering.taa);
+        assert!(!config.rendering.wireframe);
+        assert!(config.rendering.srgb);
+        assert_eq!(config.rendering.backend, None);
+        assert_eq!(config.rendering.render_scale, 1.0);
+        assert!(!config.rendering.show_metrics);
+        assert!(!config.rendering.tone_mapping);
+        assert_eq!(config.rendering.exposure, 1.0);
+        assert!(!config.rendering.fxaa);
+        assert!(!config.rendering.shadows);
+        assert!(!config.rendering.adaptive.enabled);
+        assert_eq!(config.rendering.adaptive.target_fps, 60.0);
+        assert_eq!(config.rendering.adaptive.min_scale, 0.5);
+
+        // Metrics設定のテスト
+        assert_eq!(config.metrics.window_size, 60);
+        assert!(!config.metrics.histogram_enabled);
+        assert!(!config.metrics.histogram_buckets_ms.is_empty());
+
+        // Debug設定のテスト
+        assert!(!config.debug.gpu_labels);
+        assert!(!config.debug.shader_hot_reload);
+
+        // Lighting設定のテスト
+        assert_eq!(config.lighting.direction, [-0.4, -1.0, -0.6]);
+        assert_eq!(config.lighting.color, [1.0, 1.0, 1.0]);
+        assert!(!config.lighting.sun.animate);
+        assert_eq!(config.lighting.sun.day_length_seconds, 120.0);
+        assert_eq!(config.projectile.speed, 15.0);
+        assert_eq!(config.projectile.lifetime_seconds, 5.0);
+        assert_eq!(config.projectile.ground_height, -3.0);
+        assert!(config.projectile.use_gravity);
+        assert_eq!(config.physics.fixed_timestep, 1.0 / 60.0);
+        assert_eq!(config.physics.max_steps_per_frame, 5);
+        assert_eq!(config.logging.level, "info");
+        assert!(config.logging.file.is_none());
+
+        // キー割り当ては従来のWASD/QE/矢印キー
+        assert_eq!(
+            config.keybindings.keys(Action::MoveForward),
+            [KeyCode::KeyW]
+        );
+        assert_eq!(config.keybindings.keys(Action::MoveDown), [KeyCode::KeyQ]);
+        assert_eq!(
+            config.keybindings.keys(Action::LookDown),
+            [KeyCode::ArrowDown]
+        );
+        for action in Action::ALL {
+            assert_eq!(config.keybindings.keys(action).len(), 1);
+        }
     }
 
     #[test]
@@ -176,21 +946,87 @@ mod tests {
         assert_eq!(loaded_config.window.height, 1080);
         assert_eq!(loaded_config.window.title, "Test Demo Engine");
         assert!(!loaded_config.window.resizable);
+        assert!(loaded_config.window.fullscreen);
 
         // Camera設定の比較
         assert_eq!(loaded_config.camera.fov_degrees, 60.0);
+        assert_eq!(loaded_config.camera.zoom_speed, 4.0);
         assert_eq!(loaded_config.camera.znear, 0.05);
         assert_eq!(loaded_config.camera.zfar, 500.0);
+        assert_eq!(loaded_config.camera.mode, CameraMode::Orbit);
+        assert_eq!(
+            loaded_config.camera.projection,
+            ProjectionConfig::Orthographic { height: 8.0 }
+        );
 
         // Movement設定の比較
         assert_eq!(loaded_config.movement.move_speed, 8.0);
         assert_eq!(loaded_config.movement.rotation_speed, 1.5);
         assert_eq!(loaded_config.movement.mouse_sensitivity, 0.002);
+        assert_eq!(
+            loaded_config.movement.bounds,
+            Some([[-10.0, 0.0, -10.0], [10.0, 5.0, 10.0]])
+        );
 
         // Rendering設定の比較
         assert_eq!(loaded_config.rendering.clear_color, [0.1, 0.2, 0.3, 1.0]);
         assert!(!loaded_config.rendering.vsync);
+        assert_eq!(
+            loaded_config.rendering.present_mode(),
+            PresentModeConfig::Mailbox
+        );
         assert_eq!(loaded_config.rendering.msaa_samples, 4);
+        assert!(loaded_config.rendering.conservative_raster);
+        assert!(!loaded_config.rendering.srgb);
+        assert!(!loaded_config.rendering.srgb_view_fallback);
+        assert!(loaded_config.rendering.taa);
+        assert!(loaded_config.rendering.wireframe);
+        assert!(loaded_config.rendering.double_sided_flat);
+        assert_eq!(loaded_config.rendering.backend.as_deref(), Some("vulkan"));
+        assert_eq!(loaded_config.rendering.render_scale, 0.75);
+        assert!(loaded_config.rendering.show_metrics);
+        assert!(loaded_config.rendering.adaptive.enabled);
+        assert_eq!(loaded_config.rendering.adaptive.target_fps, 30.0);
+        assert_eq!(loaded_config.rendering.adaptive.min_scale, 0.25);
+
+        // Metrics設定の比較
+        assert_eq!(loaded_config.metrics.window_size, 120);
+        assert!(loaded_config.metrics.histogram_enabled);
+        assert_eq!(
+            loaded_config.metrics.histogram_buckets_ms,
+            vec![10.0, 20.0, 40.0]
+        );
+        assert_eq!(loaded_config.metrics.histogram_export_path, "histogram.csv");
+
+        // Debug設定の比較
+        assert!(loaded_config.debug.gpu_labels);
+        assert!(loaded_config.debug.shader_hot_reload);
+        assert_eq!(loaded_config.debug.shader_dir, "shaders");
+
+        // Lighting設定の比較
+        assert_eq!(loaded_config.lighting.direction, [1.0, -1.0, 0.0]);
+        assert_eq!(loaded_config.lighting.color, [1.0, 0.9, 0.8]);
+        assert!(loaded_config.lighting.sun.animate);
+        assert_eq!(loaded_config.lighting.sun.day_length_seconds, 30.0);
+        assert_eq!(loaded_config.projectile.speed, 20.0);
+        assert_eq!(loaded_config.projectile.lifetime_seconds, 3.0);
+        assert_eq!(loaded_config.projectile.ground_height, -5.0);
+        assert!(!loaded_config.projectile.use_gravity);
+        assert_eq!(loaded_config.physics.fixed_timestep, 0.02);
+        assert_eq!(loaded_config.physics.max_steps_per_frame, 3);
+        assert_eq!(loaded_config.logging.level, "warn");
+        assert_eq!(
+            loaded_config.logging.file.as_deref(),
+            Some("demo_engine.log")
+        );
+
+        // KeyBindings設定の比較
+        assert_eq!(
+            loaded_config.keybindings.keys(Action::MoveForward),
+            [KeyCode::KeyI, KeyCode::Numpad8]
+        );
+        assert!(loaded_config.keybindings.keys(Action::MoveDown).is_empty());
+        assert_eq!(loaded_config.keybindings, original_config.keybindings);
     }
 
     #[test]
@@ -261,7 +1097,10 @@ mod tests {
         let nonexistent_path = temp_dir.path().join("nonexistent.toml");
 
         // 存在しないファイルの読み込みはエラーになるべき
-        assert!(AppConfig::load_from_file(nonexistent_path.to_str().unwrap()).is_err());
+        assert!(matches!(
+            AppConfig::load_from_file(nonexistent_path.to_str().unwrap()),
+            Err(EngineError::Io(_))
+        ));
     }
 
     #[test]
@@ -280,6 +1119,54 @@ mod tests {
         assert!(config.camera.zfar > config.camera.znear);
     }
 
+    #[test]
+    fn test_missing_metrics_section_uses_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("no_metrics.toml");
+
+        // [metrics] セクションのない既存の設定ファイル
+        let mut config = create_test_config();
+        config.metrics = MetricsConfig::default();
+        let content = toml::to_string_pretty(&config).unwrap();
+        let content = content.split("[metrics]").next().unwrap().to_string();
+        std::fs::write(&config_path, content).unwrap();
+
+        let loaded = AppConfig::load_from_file(config_path.to_str().unwrap()).unwrap();
+        assert!(!loaded.metrics.histogram_enabled);
+        assert_eq!(
+            loaded.metrics.histogram_buckets_ms,
+            MetricsConfig::default().histogram_buckets_ms
+        );
+    }
+
+    #[test]
+    fn test_present_mode_falls_back_to_vsync_flag() {
+        let mut rendering = AppConfig::default().rendering;
+        assert_eq!(rendering.present_mode(), PresentModeConfig::Vsync);
+
+        rendering.vsync = false;
+        assert_eq!(rendering.present_mode(), PresentModeConfig::Immediate);
+
+        // present_mode を指定すると vsync より優先される
+        rendering.present_mode = Some(PresentModeConfig::Mailbox);
+        assert_eq!(rendering.present_mode(), PresentModeConfig::Mailbox);
+    }
+
+    #[test]
+    fn test_old_config_without_present_mode_keeps_vsync() {
+        let mut config = create_test_config();
+        config.rendering.vsync = false;
+        config.rendering.present_mode = None;
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert!(!content.contains("present_mode"));
+
+        let loaded: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(
+            loaded.rendering.present_mode(),
+            PresentModeConfig::Immediate
+        );
+    }
+
     #[test]
     fn test_invalid_toml_content() {
         let temp_dir = TempDir::new().unwrap();
@@ -289,6 +1176,192 @@ mod tests {
         std::fs::write(&config_path, "invalid toml content [[[").unwrap();
 
         // 無効なTOMLファイルの読み込みはエラーになるべき
-        assert!(AppConfig::load_from_file(config_path.to_str().unwrap()).is_err());
+        assert!(matches!(
+            AppConfig::load_from_file(config_path.to_str().unwrap()),
+            Err(EngineError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_and_test_configs_are_valid() {
+        assert_eq!(AppConfig::default().validate(), Ok(()));
+        assert_eq!(create_test_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_each_invalid_value() {
+        // (違反メッセージに含まれる文字列, 不正な値にする変更)
+        type Case = (&'static str, fn(&mut AppConfig));
+        let cases: [Case; 16] = [
+            ("window size", |c| c.window.width = 0),
+            ("window size", |c| c.window.height = 0),
+            ("camera.fov_degrees", |c| c.camera.fov_degrees = -45.0),
+            ("camera.fov_degrees", |c| c.camera.fov_degrees = 180.0),
+            ("camera.znear must be positive", |c| c.camera.znear = 0.0),
+            ("must be less than camera.zfar", |c| c.camera.znear = 200.0),
+            ("camera.projection", |c| {
+                c.camera.projection = ProjectionConfig::Orthographic { height: 0.0 }
+            }),
+            ("rendering.msaa_samples", |c| c.rendering.msaa_samples = 3),
+            ("rendering.exposure", |c| c.rendering.exposure = f32::NAN),
+            ("physics.fixed_timestep", |c| c.physics.fixed_timestep = 0.0),
+            ("movement.bounds", |c| {
+                c.movement.bounds = Some([[0.0, 5.0, 0.0], [1.0, 1.0, 1.0]])
+            }),
+            ("metrics.window_size", |c| c.metrics.window_size = 0),
+            ("rendering.render_scale", |c| c.rendering.render_scale = 0.0),
+            ("rendering.render_scale", |c| {
+                c.rendering.render_scale = f32::INFINITY
+            }),
+            ("rendering.adaptive.min_scale must be positive", |c| {
+                c.rendering.adaptive.min_scale = f32::NAN
+            }),
+            ("must not exceed rendering.render_scale", |c| {
+                c.rendering.render_scale = 0.4
+            }),
+        ];
+
+        for (expected, make_invalid) in cases {
+            let mut config = AppConfig::default();
+            make_invalid(&mut config);
+            let violations = config.validate().unwrap_err();
+            assert_eq!(violations.len(), 1, "{:?}", violations);
+            assert!(
+                violations[0].contains(expected),
+                "{:?} に {:?} が含まれるべき",
+                violations,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_collects_all_violations() {
+        let mut config = AppConfig::default();
+        config.window.width = 0;
+        config.camera.zfar = 0.05;
+        config.rendering.msaa_samples = 16;
+
+        assert_eq!(config.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_values_fail_loading_and_fall_back_to_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("out_of_range.toml");
+        let mut config = create_test_config();
+        config.camera.znear = 10.0;
+        config.camera.zfar = 1.0;
+        config.save_to_file(config_path.to_str().unwrap()).unwrap();
+
+        let error = AppConfig::load_from_file(config_path.to_str().unwrap()).unwrap_err();
+        assert!(
+            matches!(&error, EngineError::Config(msg) if msg.contains("camera.zfar")),
+            "{}",
+            error
+        );
+
+        // 範囲外の値を含むファイルは丸ごと無視してデフォルトを使う
+        let loaded = AppConfig::load_or_default(config_path.to_str().unwrap());
+        assert_eq!(loaded.window.title, AppConfig::default().window.title);
+    }
+
+    #[test]
+    fn test_window_only_config_fills_other_sections_with_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("window_only.toml");
+        std::fs::write(&config_path, "[window]\ntitle = \"X\"\n").unwrap();
+
+        let config = AppConfig::load_from_file(config_path.to_str().unwrap()).unwrap();
+        let defaults = AppConfig::default();
+
+        assert_eq!(config.window.title, "X");
+        // 同じセクション内の省略した項目もデフォルトになる
+        assert_eq!(config.window.width, defaults.window.width);
+        assert_eq!(config.window.height, defaults.window.height);
+        assert!(config.window.resizable);
+        assert_eq!(config.camera.fov_degrees, defaults.camera.fov_degrees);
+        assert_eq!(config.camera.znear, defaults.camera.znear);
+        assert_eq!(config.camera.zfar, defaults.camera.zfar);
+        assert_eq!(config.movement.move_speed, defaults.movement.move_speed);
+        assert_eq!(
+            config.movement.mouse_sensitivity,
+            defaults.movement.mouse_sensitivity
+        );
+        assert_eq!(config.rendering.clear_color, defaults.rendering.clear_color);
+        assert!(config.rendering.vsync);
+        assert_eq!(config.rendering.msaa_samples, 1);
+        assert_eq!(
+            config.physics.fixed_timestep,
+            defaults.physics.fixed_timestep
+        );
+        assert_eq!(config.keybindings, defaults.keybindings);
+    }
+
+    #[test]
+    fn test_empty_config_is_the_default() {
+        let config: AppConfig = toml::from_str("").unwrap();
+        assert_eq!(config.window.title, AppConfig::default().window.title);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_partial_section_keeps_other_fields() {
+        let config: AppConfig =
+            toml::from_str("[rendering]\nmsaa_samples = 4\n[lighting.sun]\nanimate = true\n")
+                .unwrap();
+
+        assert_eq!(config.rendering.msaa_samples, 4);
+        assert!(config.rendering.vsync);
+        assert_eq!(config.rendering.exposure, 1.0);
+        assert!(config.lighting.sun.animate);
+        assert_eq!(config.lighting.sun.day_length_seconds, 120.0);
+        assert_eq!(config.lighting.direction, [-0.4, -1.0, -0.6]);
+    }
+
+    #[test]
+    fn test_partial_keybindings_keep_defaults() {
+        let content = toml::to_string_pretty(&AppConfig::default()).unwrap();
+        let content = content.split("[keybindings]").next().unwrap().to_string()
+            + "[keybindings]\nTurnLeft = [\"KeyJ\"]\n";
+
+        let config: AppConfig = toml::from_str(&content).unwrap();
+
+        assert_eq!(config.keybindings.keys(Action::TurnLeft), [KeyCode::KeyJ]);
+        assert_eq!(
+            config.keybindings.keys(Action::MoveForward),
+            [KeyCode::KeyW]
+        );
+    }
+
+    #[test]
+    fn test_unknown_key_name_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("bad_keys.toml");
+        let content = toml::to_string_pretty(&AppConfig::default()).unwrap();
+        let content = content.split("[keybindings]").next().unwrap().to_string()
+            + "[keybindings]\nMoveForward = [\"KeyWW\"]\n";
+        std::fs::write(&config_path, content).unwrap();
+
+        let error = AppConfig::load_from_file(config_path.to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+
+        // どのキー名が不正かがエラーメッセージに含まれる
+        assert!(error.contains("KeyWW"), "{}", error);
+        assert!(error.contains("MoveForward"), "{}", error);
+    }
+
+    #[test]
+    fn test_is_action_active_checks_all_bound_keys() {
+        let mut input_map = InputMap::default();
+        input_map.bind(Action::MoveForward, vec![KeyCode::KeyW, KeyCode::ArrowUp]);
+        let mut input = InputState::new();
+
+        assert!(!input_map.is_action_active(Action::MoveForward, &input));
+        input.set_key_pressed(KeyCode::ArrowUp, true);
+        assert!(input_map.is_action_active(Action::MoveForward, &input));
+        assert!(input_map.is_action_active(Action::LookUp, &input));
+        assert!(!input_map.is_action_active(Action::MoveBackward, &input));
     }
 }
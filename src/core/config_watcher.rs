@@ -0,0 +1,116 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use notify::Watcher;
+
+use crate::core::{
+    config::AppConfig,
+    error::{EngineError, EngineResult},
+};
+
+/// Watches the config file and reloads it when it changes on disk.
+///
+/// The parent directory is watched rather than the file itself because many
+/// editors save by writing a new file and renaming it over the old one.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    // ドロップすると監視が止まるので保持する
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Starts watching `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ConfigWatch` if the file system watcher cannot be
+    /// created or the directory cannot be watched.
+    pub fn new(path: impl AsRef<Path>) -> EngineResult<Self> {
+        let path = std::path::absolute(path.as_ref()).map_err(|e| {
+            EngineError::ConfigWatch(format!("Invalid path {}: {}", path.as_ref().display(), e))
+        })?;
+        let directory = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| EngineError::ConfigWatch(format!("Failed to create watcher: {}", e)))?;
+        watcher
+            .watch(&directory, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                EngineError::ConfigWatch(format!("Failed to watch {}: {}", directory.display(), e))
+            })?;
+
+        Ok(Self {
+            path,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the reloaded config if the file changed since the last poll.
+    ///
+    /// Edits that fail to parse are logged and ignored, so the caller keeps
+    /// running with the last good config.
+    pub fn poll(&self) -> Option<AppConfig> {
+        let mut changed = false;
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                Ok(event) => {
+                    changed |= !event.kind.is_access()
+                        && event.paths.iter().any(|path| path == &self.path);
+                }
+                Err(e) => log::warn!("Config watcher error: {}", e),
+            }
+        }
+        if !changed {
+            return None;
+        }
+
+        match AppConfig::load_from_file(&self.path.to_string_lossy()) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::error!("Ignoring invalid config {}: {}", self.path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 変更通知が届くまで最大2秒ポーリングする
+    fn poll_until_changed(watcher: &ConfigWatcher) -> Option<AppConfig> {
+        for _ in 0..40 {
+            if let Some(config) = watcher.poll() {
+                return Some(config);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        None
+    }
+
+    #[test]
+    fn test_reloads_changed_config_and_ignores_invalid_edits() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let mut config = AppConfig::default();
+        config.save_to_file(&path.to_string_lossy()).unwrap();
+
+        let watcher = ConfigWatcher::new(&path).unwrap();
+        assert!(watcher.poll().is_none(), "変更前は何も返さない");
+
+        config.movement.move_speed = 12.5;
+        config.save_to_file(&path.to_string_lossy()).unwrap();
+        let reloaded = poll_until_changed(&watcher).expect("変更が検出されるべき");
+        assert_eq!(reloaded.movement.move_speed, 12.5);
+
+        // 壊れたTOMLは無視される
+        std::fs::write(&path, "[movement\nmove_speed = ").unwrap();
+        assert!(poll_until_changed(&watcher).is_none());
+    }
+}
@@ -10,6 +10,16 @@ pub enum EngineError {
     EventLoopCreation(String),
     EventLoopRun(String),
     SceneNotFound(String),
+    TextureLoad(String),
+    MeshLoad(String),
+    MeshUpdate(String),
+    ShaderCompilation(String),
+    ConfigWatch(String),
+    Screenshot(String),
+    BufferRead(String),
+    OutOfMemory(String),
+    Io(String),
+    Config(String),
 }
 
 impl fmt::Display for EngineError {
@@ -23,10 +33,88 @@ impl fmt::Display for EngineError {
             EngineError::EventLoopCreation(msg) => write!(f, "Event loop creation error: {}", msg),
             EngineError::EventLoopRun(msg) => write!(f, "Event loop run error: {}", msg),
             EngineError::SceneNotFound(msg) => write!(f, "Scene not found: {}", msg),
+            EngineError::TextureLoad(msg) => write!(f, "Texture load error: {}", msg),
+            EngineError::MeshLoad(msg) => write!(f, "Mesh load error: {}", msg),
+            EngineError::MeshUpdate(msg) => write!(f, "Mesh update error: {}", msg),
+            EngineError::ShaderCompilation(msg) => write!(f, "Shader compilation error: {}", msg),
+            EngineError::ConfigWatch(msg) => write!(f, "Config watch error: {}", msg),
+            EngineError::Screenshot(msg) => write!(f, "Screenshot error: {}", msg),
+            EngineError::BufferRead(msg) => write!(f, "Buffer read error: {}", msg),
+            EngineError::OutOfMemory(msg) => write!(f, "Out of GPU memory: {}", msg),
+            EngineError::Io(msg) => write!(f, "I/O error: {}", msg),
+            EngineError::Config(msg) => write!(f, "Config error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for EngineError {}
 
+/// Running out of memory is fatal; every other surface error fails the frame.
+impl From<wgpu::SurfaceError> for EngineError {
+    fn from(e: wgpu::SurfaceError) -> Self {
+        let msg = format!("Failed to acquire next surface texture: {}", e);
+        match e {
+            wgpu::SurfaceError::OutOfMemory => EngineError::OutOfMemory(msg),
+            _ => EngineError::RenderError(msg),
+        }
+    }
+}
+
+impl From<std::io::Error> for EngineError {
+    fn from(e: std::io::Error) -> Self {
+        EngineError::Io(e.to_string())
+    }
+}
+
+impl From<toml::de::Error> for EngineError {
+    fn from(e: toml::de::Error) -> Self {
+        EngineError::Config(format!("Failed to parse TOML: {}", e))
+    }
+}
+
+impl From<toml::ser::Error> for EngineError {
+    fn from(e: toml::ser::Error) -> Self {
+        EngineError::Config(format!("Failed to serialize TOML: {}", e))
+    }
+}
+
 pub type EngineResult<T> = Result<T, EngineError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surface_errors_convert_by_severity() {
+        assert!(matches!(
+            EngineError::from(wgpu::SurfaceError::OutOfMemory),
+            EngineError::OutOfMemory(_)
+        ));
+        assert!(matches!(
+            EngineError::from(wgpu::SurfaceError::Timeout),
+            EngineError::RenderError(_)
+        ));
+    }
+
+    #[test]
+    fn test_io_error_keeps_message() {
+        let error = EngineError::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "config.toml missing",
+        ));
+
+        assert_eq!(error.to_string(), "I/O error: config.toml missing");
+    }
+
+    #[test]
+    fn test_toml_error_becomes_config_error() {
+        let error = EngineError::from(toml::from_str::<toml::Table>("key = [").unwrap_err());
+
+        assert!(matches!(error, EngineError::Config(_)));
+        assert!(
+            error
+                .to_string()
+                .starts_with("Config error: Failed to parse TOML")
+        );
+    }
+}
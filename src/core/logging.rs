@@ -1,5 +1,63 @@
-pub fn init_logger() {
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Debug)
-        .init();
+use crate::core::config::LoggingConfig;
+
+/// Parses a log level name (case-insensitive), e.g. `"info"` or `"off"`.
+fn parse_level(name: &str) -> Option<log::LevelFilter> {
+    name.parse().ok()
+}
+
+/// Installs the global logger configured by `config`.
+///
+/// `RUST_LOG`, when set, overrides the configured level. Logs are appended
+/// to the log file; if it cannot be opened, logs go to stderr and a warning
+/// is logged. Later calls (e.g. constructing a second `App` in tests) keep
+/// the logger that is already installed.
+pub fn init_logger(config: &LoggingConfig) {
+    let level = parse_level(&config.level);
+
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level.unwrap_or(log::LevelFilter::Info));
+    builder.parse_default_env();
+
+    let file_error = config.file.as_ref().and_then(|path| {
+        // 既に動いているロガーの出力を切り詰めないよう追記で開く
+        match std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+        {
+            Ok(file) => {
+                builder.target(env_logger::Target::Pipe(Box::new(file)));
+                None
+            }
+            Err(e) => Some(format!("Failed to open log file {}: {}", path, e)),
+        }
+    });
+
+    if builder.try_init().is_err() {
+        return;
+    }
+    // ロガーの初期化前には出力できないので、設定の問題はここで報告する
+    if level.is_none() {
+        log::warn!(
+            "Unknown log level {:?} (expected off, error, warn, info, debug or trace), using info",
+            config.level
+        );
+    }
+    if let Some(error) = file_error {
+        log::warn!("{}, logging to stderr", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_levels_parse_case_insensitively() {
+        assert_eq!(parse_level("info"), Some(log::LevelFilter::Info));
+        assert_eq!(parse_level("WARN"), Some(log::LevelFilter::Warn));
+        assert_eq!(parse_level("Trace"), Some(log::LevelFilter::Trace));
+        assert_eq!(parse_level("off"), Some(log::LevelFilter::Off));
+        assert_eq!(parse_level("verbose"), None);
+    }
 }
@@ -1,35 +1,229 @@
-use std::{collections::VecDeque, time::Instant};
+use std::{collections::VecDeque, io::Write, time::Instant};
+
+/// Default number of frames in the rolling average window.
+pub const DEFAULT_WINDOW_SIZE: usize = 60;
+/// Recorded frames between flushes of the recording file.
+const RECORDING_FLUSH_INTERVAL: u64 = 60;
+
+/// Frame-time distribution over the rolling window, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimeStats {
+    pub min_ms: f32,
+    pub max_ms: f32,
+    pub p95_ms: f32,
+    pub p99_ms: f32,
+    /// Average fps over the slowest 1% of frames (at least one frame).
+    pub low_1_percent_fps: f32,
+}
+
+impl FrameTimeStats {
+    /// Computes the statistics from frame times in seconds.
+    pub fn from_frame_times(frame_times: &VecDeque<f32>) -> Self {
+        if frame_times.is_empty() {
+            return Self::default();
+        }
+
+        // パーセンタイルはソート済みのコピーから求める（元の順序は保持）
+        let mut sorted_ms: Vec<f32> = frame_times.iter().map(|dt| dt * 1000.0).collect();
+        sorted_ms.sort_by(f32::total_cmp);
+
+        let slowest_count = sorted_ms.len().div_ceil(100);
+        let slowest_avg_ms = sorted_ms[sorted_ms.len() - slowest_count..]
+            .iter()
+            .sum::<f32>()
+            / slowest_count as f32;
+
+        Self {
+            min_ms: sorted_ms[0],
+            max_ms: sorted_ms[sorted_ms.len() - 1],
+            p95_ms: percentile(&sorted_ms, 95.0),
+            p99_ms: percentile(&sorted_ms, 99.0),
+            low_1_percent_fps: if slowest_avg_ms > 0.0 {
+                1000.0 / slowest_avg_ms
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Copy of the headline metrics at one point in time, cheap to pass to a
+/// HUD, a window title or a test.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Average over the rolling window.
+    pub fps: f32,
+    /// Duration of the last frame.
+    pub frame_time_ms: f32,
+    pub object_count: usize,
+    pub culled_object_count: usize,
+    pub frame_time_stats: FrameTimeStats,
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[f32], percent: f32) -> f32 {
+    let rank = (percent / 100.0 * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Per-frame CSV log written while recording is active.
+struct FrameRecorder {
+    writer: std::io::BufWriter<std::fs::File>,
+    frame_index: u64,
+}
+
+impl FrameRecorder {
+    fn create(path: &str) -> std::io::Result<Self> {
+        let path_buf = std::path::Path::new(path);
+        if let Some(parent) = path_buf.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path_buf)?);
+        writeln!(writer, "frame_index,dt_ms,fps,object_count")?;
+        Ok(Self {
+            writer,
+            frame_index: 0,
+        })
+    }
+
+    fn record(&mut self, dt: f32, fps: f32, object_count: usize) -> std::io::Result<()> {
+        writeln!(
+            self.writer,
+            "{},{:.3},{:.2},{}",
+            self.frame_index,
+            dt * 1000.0,
+            fps,
+            object_count
+        )?;
+        self.frame_index += 1;
+        if self.frame_index.is_multiple_of(RECORDING_FLUSH_INTERVAL) {
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+}
 
 pub struct EngineMetrics {
     frame_time: VecDeque<f32>,
+    window_size: usize,
     fps: f32,
+    stats: FrameTimeStats,
     render_objects_count: usize,
+    culled_objects_count: usize,
+    #[allow(dead_code)]
     last_update: Instant,
+    histogram: Vec<(f32, u32)>,
+    recorder: Option<FrameRecorder>,
 }
 
 impl EngineMetrics {
     pub fn new() -> Self {
         Self {
-            frame_time: VecDeque::with_capacity(60),
+            frame_time: VecDeque::with_capacity(DEFAULT_WINDOW_SIZE),
+            window_size: DEFAULT_WINDOW_SIZE,
             fps: 0.0,
+            stats: FrameTimeStats::default(),
             render_objects_count: 0,
+            culled_objects_count: 0,
             last_update: Instant::now(),
+            histogram: Vec::new(),
+            recorder: None,
         }
     }
 
+    /// Sets how many recent frames the fps average is computed over (minimum 1).
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self.frame_time = VecDeque::with_capacity(self.window_size);
+        self
+    }
+
+    /// Enables frame-time histogram accumulation with the given bucket upper bounds.
+    ///
+    /// Bounds are in milliseconds and sorted ascending; an overflow bucket with an
+    /// upper bound of `f32::INFINITY` is appended so every frame is counted.
+    pub fn with_histogram(mut self, bucket_bounds_ms: &[f32]) -> Self {
+        let mut bounds: Vec<f32> = bucket_bounds_ms
+            .iter()
+            .copied()
+            .filter(|b| b.is_finite())
+            .collect();
+        bounds.sort_by(f32::total_cmp);
+        bounds.dedup();
+        bounds.push(f32::INFINITY);
+
+        self.histogram = bounds.into_iter().map(|bound| (bound, 0)).collect();
+        self
+    }
+
     pub fn update(&mut self, dt: f32, object_count: usize) {
         self.frame_time.push_back(dt);
-        if self.frame_time.len() > 60 {
+        while self.frame_time.len() > self.window_size {
             self.frame_time.pop_front();
         }
 
         let avg_frame_time: f32 =
             self.frame_time.iter().sum::<f32>() / self.frame_time.len() as f32;
 
-        self.fps = 1.0 / avg_frame_time;
+        self.fps = if avg_frame_time > 0.0 {
+            1.0 / avg_frame_time
+        } else {
+            0.0
+        };
+        self.stats = FrameTimeStats::from_frame_times(&self.frame_time);
         self.render_objects_count = object_count;
+
+        let frame_time_ms = dt * 1000.0;
+        if let Some((_, count)) = self
+            .histogram
+            .iter_mut()
+            .find(|(bound, _)| frame_time_ms <= *bound)
+        {
+            *count += 1;
+        }
+
+        if let Some(recorder) = &mut self.recorder
+            && let Err(e) = recorder.record(dt, self.fps, object_count)
+        {
+            // 書き込みに失敗したら記録を打ち切る
+            log::error!("Stopped metrics recording: {}", e);
+            self.recorder = None;
+        }
+    }
+
+    /// Starts appending `frame_index,dt_ms,fps,object_count` rows to a new CSV
+    /// at `path` on every `update`, replacing any recording in progress.
+    ///
+    /// The file is flushed every 60 frames and when recording stops.
+    pub fn start_recording(&mut self, path: &str) -> std::io::Result<()> {
+        self.stop_recording()?;
+        self.recorder = Some(FrameRecorder::create(path)?);
+        Ok(())
+    }
+
+    /// Stops recording and flushes the file. Does nothing if not recording.
+    pub fn stop_recording(&mut self) -> std::io::Result<()> {
+        match self.recorder.take() {
+            Some(mut recorder) => recorder.writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` while per-frame timings are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
     }
 
+    /// Clears the rolling frame-time window so stale samples (e.g. from a
+    /// previous scene) no longer skew the average. The histogram is kept.
+    pub fn reset(&mut self) {
+        self.frame_time.clear();
+        self.fps = 0.0;
+        self.stats = FrameTimeStats::default();
+    }
+
+    #[allow(dead_code)]
     pub fn get_fps(&self) -> f32 {
         self.fps
     }
@@ -38,10 +232,98 @@ impl EngineMetrics {
         self.frame_time.back().unwrap_or(&0.0) * 1000.0
     }
 
+    /// Shortest frame time in the window, in milliseconds.
+    #[allow(dead_code)]
+    pub fn get_min_frame_time_ms(&self) -> f32 {
+        self.stats.min_ms
+    }
+
+    /// Longest frame time in the window, in milliseconds.
+    #[allow(dead_code)]
+    pub fn get_max_frame_time_ms(&self) -> f32 {
+        self.stats.max_ms
+    }
+
+    /// 95th percentile frame time in the window, in milliseconds.
+    #[allow(dead_code)]
+    pub fn get_p95_frame_time_ms(&self) -> f32 {
+        self.stats.p95_ms
+    }
+
+    /// 99th percentile frame time in the window, in milliseconds.
+    #[allow(dead_code)]
+    pub fn get_p99_frame_time_ms(&self) -> f32 {
+        self.stats.p99_ms
+    }
+
+    /// Average fps of the slowest 1% of frames in the window; shows stutter
+    /// that the mean fps hides.
+    #[allow(dead_code)]
+    pub fn get_1_percent_low_fps(&self) -> f32 {
+        self.stats.low_1_percent_fps
+    }
+
+    /// All frame-time statistics of the current window.
+    #[allow(dead_code)]
+    pub fn frame_time_stats(&self) -> FrameTimeStats {
+        self.stats
+    }
+
     pub fn get_object_count(&self) -> usize {
         self.render_objects_count
     }
 
+    /// Current fps, frame time and object counts by value.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            fps: self.fps,
+            frame_time_ms: self.get_frame_time_ms(),
+            object_count: self.render_objects_count,
+            culled_object_count: self.culled_objects_count,
+            frame_time_stats: self.stats,
+        }
+    }
+
+    /// Records how many objects frustum culling skipped in the last frame.
+    pub fn set_culled_object_count(&mut self, culled_count: usize) {
+        self.culled_objects_count = culled_count;
+    }
+
+    /// Objects skipped by frustum culling in the last frame.
+    pub fn get_culled_object_count(&self) -> usize {
+        self.culled_objects_count
+    }
+
+    /// Returns the frame-time histogram as `(bucket upper bound in ms, count)` pairs.
+    ///
+    /// Empty when the histogram is not enabled.
+    #[allow(dead_code)]
+    pub fn histogram(&self) -> &[(f32, u32)] {
+        &self.histogram
+    }
+
+    /// Writes the frame-time histogram to `path` as CSV.
+    ///
+    /// The file has a `bucket_upper_ms,count` header followed by one row per bucket;
+    /// the overflow bucket's upper bound is written as `inf`.
+    pub fn export_csv(&self, path: &str) -> std::io::Result<()> {
+        let path_buf = std::path::Path::new(path);
+        if let Some(parent) = path_buf.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path_buf)?);
+        writeln!(file, "bucket_upper_ms,count")?;
+        for (bound, count) in &self.histogram {
+            if bound.is_finite() {
+                writeln!(file, "{},{}", bound, count)?;
+            } else {
+                writeln!(file, "inf,{}", count)?;
+            }
+        }
+        file.flush()
+    }
+
     pub fn check_performance(&self) {
         if self.fps < 30.0 {
             log::warn!("Low FPS: {:.1} fps", self.fps);
@@ -51,3 +333,215 @@ impl EngineMetrics {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_window_size_limits_samples() {
+        let mut metrics = EngineMetrics::new().with_window_size(2);
+
+        metrics.update(0.1, 0);
+        metrics.update(0.02, 0);
+        metrics.update(0.02, 0);
+
+        // 古い 0.1 秒のフレームは窓から外れているはず
+        assert!((metrics.get_fps() - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_snapshot_copies_current_values() {
+        let mut metrics = EngineMetrics::new();
+        metrics.update(0.02, 7);
+        metrics.set_culled_object_count(2);
+
+        let snapshot = metrics.snapshot();
+        metrics.update(0.05, 1);
+
+        // スナップショットは取得時点の値のまま
+        assert!((snapshot.fps - 50.0).abs() < 0.01);
+        assert!((snapshot.frame_time_ms - 20.0).abs() < 0.01);
+        assert_eq!(snapshot.object_count, 7);
+        assert_eq!(snapshot.culled_object_count, 2);
+        assert_eq!(metrics.snapshot().object_count, 1);
+    }
+
+    #[test]
+    fn test_reset_empties_buffer() {
+        let mut metrics = EngineMetrics::new();
+        for _ in 0..10 {
+            metrics.update(0.1, 3);
+        }
+
+        metrics.reset();
+
+        assert!(metrics.frame_time.is_empty());
+        assert_eq!(metrics.get_fps(), 0.0);
+        assert_eq!(metrics.get_frame_time_ms(), 0.0);
+    }
+
+    #[test]
+    fn test_fps_after_reset_uses_only_new_samples() {
+        let mut metrics = EngineMetrics::new();
+        for _ in 0..10 {
+            metrics.update(0.1, 0);
+        }
+
+        metrics.reset();
+        metrics.update(0.02, 0);
+
+        assert!((metrics.get_fps() - 50.0).abs() < 0.01);
+        assert!(metrics.get_fps().is_finite());
+    }
+
+    #[test]
+    fn test_zero_dt_does_not_divide_by_zero() {
+        let mut metrics = EngineMetrics::new();
+        metrics.reset();
+        metrics.update(0.0, 0);
+
+        assert_eq!(metrics.get_fps(), 0.0);
+    }
+
+    #[test]
+    fn test_frame_time_percentiles() {
+        let mut metrics = EngineMetrics::new().with_window_size(100);
+        // 1ms〜100ms を逆順に入力しても結果は順序に依存しない
+        for ms in (1..=100).rev() {
+            metrics.update(ms as f32 / 1000.0, 0);
+        }
+
+        assert!((metrics.get_min_frame_time_ms() - 1.0).abs() < 1e-3);
+        assert!((metrics.get_max_frame_time_ms() - 100.0).abs() < 1e-3);
+        assert!((metrics.get_p95_frame_time_ms() - 95.0).abs() < 1e-3);
+        assert!((metrics.get_p99_frame_time_ms() - 99.0).abs() < 1e-3);
+        // 最も遅い1%（1フレーム = 100ms）の平均
+        assert!((metrics.get_1_percent_low_fps() - 10.0).abs() < 1e-3);
+        // 最新フレームは時系列のまま
+        assert!((metrics.get_frame_time_ms() - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_stutter_visible_in_low_stats() {
+        let mut metrics = EngineMetrics::new();
+        for _ in 0..59 {
+            metrics.update(0.016, 0);
+        }
+        metrics.update(0.1, 0);
+
+        // 平均fpsは高いままだが、最大値と1%低fpsにスパイクが現れる
+        assert!(metrics.get_fps() > 50.0);
+        assert!((metrics.get_p95_frame_time_ms() - 16.0).abs() < 1e-3);
+        assert!((metrics.get_max_frame_time_ms() - 100.0).abs() < 1e-3);
+        assert!((metrics.get_1_percent_low_fps() - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_frame_time_stats_cleared_on_reset() {
+        let mut metrics = EngineMetrics::new();
+        metrics.update(0.05, 0);
+        assert!(metrics.get_max_frame_time_ms() > 0.0);
+
+        metrics.reset();
+
+        assert_eq!(metrics.frame_time_stats(), FrameTimeStats::default());
+    }
+
+    #[test]
+    fn test_recording_writes_one_row_per_frame() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("profile/frames.csv");
+
+        let mut metrics = EngineMetrics::new();
+        metrics.update(0.5, 1);
+        metrics.start_recording(csv_path.to_str().unwrap()).unwrap();
+        assert!(metrics.is_recording());
+        metrics.update(0.010, 3);
+        metrics.update(0.030, 4);
+        metrics.stop_recording().unwrap();
+        // 停止後のフレームは記録されない
+        metrics.update(0.010, 5);
+
+        assert!(!metrics.is_recording());
+        let content = std::fs::read_to_string(&csv_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], "frame_index,dt_ms,fps,object_count");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("0,10.000,"));
+        assert!(lines[1].ends_with(",3"));
+        assert!(lines[2].starts_with("1,30.000,"));
+        assert!(lines[2].ends_with(",4"));
+    }
+
+    #[test]
+    fn test_recording_flushes_periodically() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("frames.csv");
+
+        let mut metrics = EngineMetrics::new();
+        metrics.start_recording(csv_path.to_str().unwrap()).unwrap();
+        for _ in 0..RECORDING_FLUSH_INTERVAL {
+            metrics.update(0.016, 0);
+        }
+
+        // 停止前でも一定フレームごとにファイルへ書き出される
+        let content = std::fs::read_to_string(&csv_path).unwrap();
+        assert_eq!(
+            content.lines().count(),
+            RECORDING_FLUSH_INTERVAL as usize + 1
+        );
+    }
+
+    #[test]
+    fn test_histogram_disabled_by_default() {
+        let mut metrics = EngineMetrics::new();
+        metrics.update(0.016, 1);
+
+        assert!(metrics.histogram().is_empty());
+    }
+
+    #[test]
+    fn test_histogram_bucket_counts() {
+        let mut metrics = EngineMetrics::new().with_histogram(&[10.0, 20.0, 40.0]);
+
+        // 5ms, 10ms（境界値は下のバケット）, 15ms, 30ms, 30ms, 100ms
+        for dt in [0.005, 0.010, 0.015, 0.030, 0.030, 0.100] {
+            metrics.update(dt, 0);
+        }
+
+        let histogram = metrics.histogram();
+        assert_eq!(histogram.len(), 4, "オーバーフローバケットが追加されるべき");
+        assert_eq!(histogram[0], (10.0, 2));
+        assert_eq!(histogram[1], (20.0, 1));
+        assert_eq!(histogram[2], (40.0, 2));
+        assert!(histogram[3].0.is_infinite());
+        assert_eq!(histogram[3].1, 1);
+    }
+
+    #[test]
+    fn test_histogram_bounds_are_sorted() {
+        let metrics = EngineMetrics::new().with_histogram(&[40.0, 10.0, 20.0, 10.0]);
+        let bounds: Vec<f32> = metrics.histogram().iter().map(|(b, _)| *b).collect();
+
+        assert_eq!(bounds[..3], [10.0, 20.0, 40.0]);
+        assert!(bounds[3].is_infinite());
+    }
+
+    #[test]
+    fn test_histogram_csv_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("histogram.csv");
+
+        let mut metrics = EngineMetrics::new().with_histogram(&[16.5, 33.0]);
+        for dt in [0.010, 0.020, 0.050] {
+            metrics.update(dt, 0);
+        }
+
+        metrics.export_csv(csv_path.to_str().unwrap()).unwrap();
+
+        let content = std::fs::read_to_string(&csv_path).unwrap();
+        assert_eq!(content, "bucket_upper_ms,count\n16.5,1\n33,1\ninf,1\n");
+    }
+}
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod config_watcher;
 pub mod error;
 pub mod logging;
 pub mod metrics;
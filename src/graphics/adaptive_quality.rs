@@ -0,0 +1,140 @@
+use crate::{core::config::AdaptiveQualityConfig, graphics::render_scale::clamp_render_scale};
+
+/// Render scale change per adjustment.
+pub const SCALE_STEP: f32 = 0.1;
+/// Seconds fps must stay below the target before quality is reduced.
+const DOWNGRADE_DELAY_SECONDS: f32 = 1.0;
+/// Seconds fps must stay above the headroom threshold before quality is restored.
+const UPGRADE_DELAY_SECONDS: f32 = 3.0;
+/// Quality is only restored while fps exceeds `target_fps * UPGRADE_HEADROOM`.
+const UPGRADE_HEADROOM: f32 = 1.2;
+
+/// Adjusts the render scale to hold a target frame rate.
+///
+/// Hysteresis keeps it from oscillating: quality drops only after fps has been
+/// below the target for a sustained period, and is restored only after fps has
+/// stayed well above it (with headroom) for a longer one. Frame rates inside
+/// the band between the two thresholds leave the scale untouched.
+pub struct AdaptiveQuality {
+    target_fps: f32,
+    min_scale: f32,
+    max_scale: f32,
+    scale: f32,
+    below_seconds: f32,
+    above_seconds: f32,
+}
+
+impl AdaptiveQuality {
+    /// Creates a controller starting at (and never exceeding) `max_scale`.
+    pub fn new(config: &AdaptiveQualityConfig, max_scale: f32) -> Self {
+        let max_scale = clamp_render_scale(max_scale);
+        Self {
+            target_fps: config.target_fps,
+            min_scale: clamp_render_scale(config.min_scale).min(max_scale),
+            max_scale,
+            scale: max_scale,
+            below_seconds: 0.0,
+            above_seconds: 0.0,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Feeds the current average fps and frame delta.
+    ///
+    /// Returns the new render scale when it changed this frame.
+    pub fn update(&mut self, fps: f32, dt: f32) -> Option<f32> {
+        if fps < self.target_fps {
+            self.below_seconds += dt;
+            self.above_seconds = 0.0;
+        } else if fps > self.target_fps * UPGRADE_HEADROOM {
+            self.above_seconds += dt;
+            self.below_seconds = 0.0;
+        } else {
+            self.below_seconds = 0.0;
+            self.above_seconds = 0.0;
+        }
+
+        let previous = self.scale;
+        if self.below_seconds >= DOWNGRADE_DELAY_SECONDS {
+            self.scale = (self.scale - SCALE_STEP).max(self.min_scale);
+            self.below_seconds = 0.0;
+        } else if self.above_seconds >= UPGRADE_DELAY_SECONDS {
+            self.scale = (self.scale + SCALE_STEP).min(self.max_scale);
+            self.above_seconds = 0.0;
+        }
+
+        (self.scale != previous).then_some(self.scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn create_controller() -> AdaptiveQuality {
+        AdaptiveQuality::new(
+            &AdaptiveQualityConfig {
+                enabled: true,
+                target_fps: 60.0,
+                min_scale: 0.5,
+            },
+            1.0,
+        )
+    }
+
+    /// `seconds` 秒間同じfpsを与え、スケールが変わった回数を返す
+    fn run(controller: &mut AdaptiveQuality, fps: f32, seconds: f32) -> usize {
+        let frames = (seconds / DT).round() as usize;
+        (0..frames)
+            .filter(|_| controller.update(fps, DT).is_some())
+            .count()
+    }
+
+    #[test]
+    fn test_sustained_low_fps_reduces_scale() {
+        let mut controller = create_controller();
+
+        run(&mut controller, 40.0, 1.5);
+
+        assert!((controller.scale() - 0.9).abs() < 1e-6);
+
+        // 下限より下がらない
+        run(&mut controller, 20.0, 30.0);
+        assert_eq!(controller.scale(), 0.5);
+    }
+
+    #[test]
+    fn test_sustained_high_fps_restores_scale() {
+        let mut controller = create_controller();
+        run(&mut controller, 40.0, 3.5);
+        assert!(controller.scale() < 1.0);
+
+        run(&mut controller, 90.0, 30.0);
+
+        assert_eq!(controller.scale(), 1.0, "上限まで戻るべき");
+    }
+
+    #[test]
+    fn test_hysteresis_prevents_flip_flopping() {
+        let mut controller = create_controller();
+        run(&mut controller, 40.0, 1.5);
+        let reduced = controller.scale();
+
+        // 目標付近（ヘッドルーム未満）では戻さない
+        assert_eq!(run(&mut controller, 65.0, 10.0), 0);
+        assert_eq!(controller.scale(), reduced);
+
+        // 閾値をまたいで揺れても変化しない
+        let changes: usize = (0..20)
+            .map(|_| run(&mut controller, 55.0, 0.5) + run(&mut controller, 90.0, 0.5))
+            .sum();
+        assert_eq!(changes, 0, "短い揺れでスケールが変わるべきではない");
+        assert_eq!(controller.scale(), reduced);
+    }
+}
@@ -0,0 +1,111 @@
+/// Converts an sRGB-encoded channel value in `[0, 1]` to linear light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear-light channel value in `[0, 1]` to sRGB encoding.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Returns `true` for formats holding linear light: sRGB formats (encoded in
+/// hardware) and floating-point HDR formats.
+fn stores_linear(format: wgpu::TextureFormat) -> bool {
+    format.is_srgb()
+        || matches!(
+            format,
+            wgpu::TextureFormat::Rgba16Float
+                | wgpu::TextureFormat::Rgba32Float
+                | wgpu::TextureFormat::Rg11b10Ufloat
+        )
+}
+
+/// Returns the clear color to pass to `LoadOp::Clear` for a target `format`.
+///
+/// Clear values are interpreted as linear light. sRGB targets encode them in
+/// hardware and HDR targets keep them linear; other targets store them
+/// verbatim, so the RGB channels are encoded here to display the same color.
+/// Alpha is never converted.
+pub fn clear_color_for_format(color: [f32; 4], format: wgpu::TextureFormat) -> [f32; 4] {
+    if stores_linear(format) {
+        return color;
+    }
+    [
+        linear_to_srgb(color[0]),
+        linear_to_srgb(color[1]),
+        linear_to_srgb(color[2]),
+        color[3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_to_linear_endpoints() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_srgb_to_linear_known_values() {
+        // sRGB 0.5 は線形で約 0.214
+        assert!((srgb_to_linear(0.5) - 0.214_041).abs() < 1e-5);
+        // 線形区間
+        assert!((srgb_to_linear(0.04) - 0.04 / 12.92).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_linear_to_srgb_round_trip() {
+        for value in [0.0, 0.001, 0.2, 0.5, 0.8, 1.0] {
+            let round_trip = srgb_to_linear(linear_to_srgb(value));
+            assert!(
+                (round_trip - value).abs() < 1e-5,
+                "{} -> {}",
+                value,
+                round_trip
+            );
+        }
+    }
+
+    #[test]
+    fn test_clear_color_on_srgb_target_is_unchanged() {
+        let color = [0.5, 0.2, 0.2, 1.0];
+        assert_eq!(
+            clear_color_for_format(color, wgpu::TextureFormat::Bgra8UnormSrgb),
+            color
+        );
+    }
+
+    #[test]
+    fn test_clear_color_on_non_srgb_target_is_converted() {
+        let color = [0.5, 0.2, 0.0, 0.5];
+        let converted = clear_color_for_format(color, wgpu::TextureFormat::Bgra8Unorm);
+
+        // 非sRGBサーフェスでは表示結果がsRGBサーフェスと一致するよう変換される
+        assert!((converted[0] - 0.735_357).abs() < 1e-4, "{:?}", converted);
+        assert!((srgb_to_linear(converted[1]) - 0.2).abs() < 1e-5);
+        assert_eq!(converted[2], 0.0);
+        // アルファは変換しない
+        assert_eq!(converted[3], 0.5);
+    }
+
+    #[test]
+    fn test_clear_color_on_hdr_target_stays_linear() {
+        // トーンマッピング前の HDR ターゲットは 1.0 を超える値も線形のまま持つ
+        let color = [4.0, 0.5, 0.2, 1.0];
+        assert_eq!(
+            clear_color_for_format(color, wgpu::TextureFormat::Rgba16Float),
+            color
+        );
+    }
+}
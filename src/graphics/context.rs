@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::core::error::{EngineError, EngineResult};
+
+/// Core WGPU objects shared by the windowed and headless rendering paths.
+///
+/// Owns the instance and adapter alongside the device/queue pair so callers can
+/// create surfaces or query adapter capabilities after initialization.
+pub struct GpuContext {
+    pub instance: wgpu::Instance,
+    pub adapter: wgpu::Adapter,
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+}
+
+impl GpuContext {
+    /// Creates the instance, requests an adapter and opens a device on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `force_fallback_adapter` - Request a software (CPU) adapter instead of a GPU
+    /// * `optional_features` - Features enabled only if the adapter supports them
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if no adapter is available or device creation fails.
+    pub async fn new(
+        force_fallback_adapter: bool,
+        optional_features: wgpu::Features,
+    ) -> EngineResult<Self> {
+        Self::with_backends(
+            wgpu::Backends::all(),
+            force_fallback_adapter,
+            optional_features,
+        )
+        .await
+    }
+
+    /// Like [`GpuContext::new`], but only considers adapters of `backends`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if none of `backends` provides an adapter or
+    /// device creation fails.
+    pub async fn with_backends(
+        backends: wgpu::Backends,
+        force_fallback_adapter: bool,
+        optional_features: wgpu::Features,
+    ) -> EngineResult<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends,
+            ..Default::default()
+        });
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: None,
+                force_fallback_adapter,
+            })
+            .await
+            .map_err(|e| {
+                EngineError::AdapterRequest(format!("Failed to request adapter: {}", e))
+            })?;
+
+        let required_features = optional_features & adapter.features();
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: None,
+                required_features,
+                required_limits: wgpu::Limits::default(),
+                memory_hints: wgpu::MemoryHints::default(),
+                trace: wgpu::Trace::default(),
+            })
+            .await
+            .map_err(|e| EngineError::DeviceRequest(format!("Failed to request device: {}", e)))?;
+
+        Ok(Self {
+            instance,
+            adapter,
+            device: Arc::new(device),
+            queue: Arc::new(queue),
+        })
+    }
+}
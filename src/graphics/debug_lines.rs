@@ -0,0 +1,161 @@
+use wgpu::util::DeviceExt;
+
+use crate::scene::debug_lines::DebugLines;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LineVertex {
+    /// Clip-space position.
+    pub position: [f32; 4],
+    pub color: [f32; 4],
+}
+
+impl LineVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x4, 1 => Float32x4];
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Transforms the world-space line end points of `lines` to clip space.
+pub fn line_vertices(lines: &DebugLines, view_proj: glam::Mat4) -> Vec<LineVertex> {
+    lines
+        .vertices()
+        .iter()
+        .map(|vertex| LineVertex {
+            position: (view_proj * vertex.position.extend(1.0)).to_array(),
+            color: vertex.color,
+        })
+        .collect()
+}
+
+/// Draws [`DebugLines`] as a line list over the finished scene, without
+/// depth testing so they stay visible through geometry.
+pub struct DebugLineRenderer {
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl DebugLineRenderer {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Line Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../assets/shaders/debug/lines.wgsl").into(),
+            ),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Line Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[LineVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self { pipeline }
+    }
+
+    /// Draws `lines` over the existing contents of `view` once per camera,
+    /// each given as its view-projection matrix and `(x, y, width, height)`
+    /// pixel viewport. Does nothing if there are no lines.
+    pub fn draw(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        label: &str,
+        lines: &DebugLines,
+        cameras: &[(glam::Mat4, [f32; 4])],
+    ) {
+        if lines.is_empty() || cameras.is_empty() {
+            return;
+        }
+
+        // 1フレームに複数パスを描くことがあるので、書き換えずパスごとに作る
+        let vertices: Vec<_> = cameras
+            .iter()
+            .flat_map(|(view_proj, _)| line_vertices(lines, *view_proj))
+            .collect();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Debug Line Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        // カメラごとに同じ本数の頂点が並ぶ
+        let count = (vertices.len() / cameras.len()) as u32;
+        for (index, (_, [x, y, width, height])) in cameras.iter().enumerate() {
+            render_pass.set_viewport(*x, *y, *width, *height, 0.0, 1.0);
+            let start = index as u32 * count;
+            render_pass.draw(start..start + count, 0..1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_vertices_are_in_clip_space() {
+        let mut lines = DebugLines::new();
+        lines.draw_line(glam::Vec3::ZERO, glam::vec3(1.0, 2.0, 0.0), [1.0; 4]);
+        let view_proj = glam::Mat4::from_scale(glam::vec3(0.5, 0.5, 1.0));
+
+        let vertices = line_vertices(&lines, view_proj);
+
+        assert_eq!(vertices.len(), 2);
+        assert_eq!(vertices[0].position, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(vertices[1].position, [0.5, 1.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,349 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    core::config::AppConfig,
+    graphics::{color, context::GpuContext, renderer::Renderer},
+    resources::{
+        manager::{ResourceId, ResourceManager, ResourceStats},
+        primitives::ObjectType,
+    },
+    scene::{Scene, demo_scene::DemoScene},
+    window::Window,
+};
+
+/// Pipelines created by the built-in scenes, checked by name.
+const BUILTIN_PIPELINES: &[&str] = &[
+    "basic_pipeline",
+    "pulse_pipeline",
+    "grid_pipeline",
+    "textured_pipeline",
+];
+
+/// Color format of the off-screen target used for the test frame.
+const TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const TARGET_SIZE: u32 = 256;
+
+/// Options controlling a diagnostics run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiagnosticsOptions {
+    /// Request a software (CPU) adapter instead of a hardware GPU.
+    pub force_fallback_adapter: bool,
+}
+
+/// Outcome of a single diagnostics step.
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, error: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            error,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Surface capabilities reported when diagnostics run with a window.
+#[derive(Debug, Clone)]
+pub struct SurfaceSupport {
+    pub formats: Vec<wgpu::TextureFormat>,
+    pub present_modes: Vec<wgpu::PresentMode>,
+}
+
+/// Structured result of a startup self-test.
+///
+/// Collects adapter information, capabilities and the pass/fail state of each
+/// pipeline, primitive mesh and the test frame so that GPU problems can be
+/// reported without custom instrumentation.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsReport {
+    /// Fatal initialization error (no adapter, device creation failure).
+    pub init_error: Option<String>,
+    pub adapter_info: Option<wgpu::AdapterInfo>,
+    pub features: wgpu::Features,
+    pub limits: Option<wgpu::Limits>,
+    /// `None` when running headless.
+    pub surface: Option<SurfaceSupport>,
+    pub pipelines: Vec<CheckResult>,
+    pub meshes: Vec<CheckResult>,
+    pub frame: Option<CheckResult>,
+    pub resources: ResourceStats,
+}
+
+impl DiagnosticsReport {
+    /// Returns `true` when initialization succeeded and every check passed.
+    pub fn passed(&self) -> bool {
+        self.init_error.is_none()
+            && self.pipelines.iter().all(CheckResult::passed)
+            && self.meshes.iter().all(CheckResult::passed)
+            && self.frame.as_ref().is_some_and(CheckResult::passed)
+    }
+}
+
+/// Runs the startup self-test.
+///
+/// Initializes the GPU without a surface, creates the built-in pipelines and
+/// every primitive mesh through a [`DemoScene`], then renders one frame into an
+/// off-screen texture. When `window` is given, the surface formats and present
+/// modes supported for it are reported as well.
+pub async fn run_diagnostics(
+    config: &AppConfig,
+    window: Option<&Window>,
+    options: DiagnosticsOptions,
+) -> DiagnosticsReport {
+    let mut report = DiagnosticsReport::default();
+
+    let context =
+        match GpuContext::new(options.force_fallback_adapter, wgpu::Features::empty()).await {
+            Ok(context) => context,
+            Err(e) => {
+                report.init_error = Some(e.to_string());
+                return report;
+            }
+        };
+
+    report.adapter_info = Some(context.adapter.get_info());
+    report.features = context.adapter.features();
+    report.limits = Some(context.adapter.limits());
+
+    if let Some(window) = window {
+        match context.instance.create_surface(window.get_window()) {
+            Ok(surface) => {
+                let caps = surface.get_capabilities(&context.adapter);
+                report.surface = Some(SurfaceSupport {
+                    formats: caps.formats,
+                    present_modes: caps.present_modes,
+                });
+            }
+            Err(e) => log::warn!("Diagnostics: failed to create surface: {}", e),
+        }
+    }
+
+    let device = context.device.clone();
+    let resource_manager =
+        ResourceManager::new(device.clone(), context.queue.clone(), TARGET_FORMAT);
+    let mut scene = DemoScene::new(1.0, Arc::new(config.clone()));
+
+    // パイプライン作成
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let init_result = scene.initialize(resource_manager);
+    let init_error = device
+        .pop_error_scope()
+        .await
+        .map(|e| e.to_string())
+        .or_else(|| init_result.err().map(|e| e.to_string()));
+
+    for name in BUILTIN_PIPELINES {
+        let error = if scene
+            .get_resource_manager()
+            .get_pipeline(&ResourceId::new(name))
+            .is_some()
+        {
+            init_error.clone()
+        } else {
+            Some(
+                init_error
+                    .clone()
+                    .unwrap_or_else(|| "not created".to_string()),
+            )
+        };
+        report.pipelines.push(CheckResult::new(*name, error));
+    }
+
+    // プリミティブメッシュ作成
+    for object_type in ObjectType::ALL {
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let object_id = scene.add_object(object_type, glam::Vec3::ZERO);
+        let mut error = device.pop_error_scope().await.map(|e| e.to_string());
+
+        let registered = scene
+            .get_render_objects()
+            .iter()
+            .find(|object| object.id == object_id)
+            .and_then(|object| scene.get_resource_manager().get_mesh(&object.mesh_id))
+            .is_some();
+        if error.is_none() && !registered {
+            error = Some("mesh not registered".to_string());
+        }
+
+        report
+            .meshes
+            .push(CheckResult::new(format!("{:?}", object_type), error));
+    }
+
+    // オフスクリーンに1フレーム描画
+    report.frame = Some(render_test_frame(&context, &scene, config).await);
+    report.resources = scene.get_resource_manager().stats();
+
+    report
+}
+
+async fn render_test_frame(
+    context: &GpuContext,
+    scene: &DemoScene,
+    config: &AppConfig,
+) -> CheckResult {
+    let name = format!("render {0}x{0} frame", TARGET_SIZE);
+    let device = &context.device;
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Diagnostics Target"),
+        size: wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TARGET_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let renderer = Renderer::new(
+        device.clone(),
+        color::clear_color_for_format(config.rendering.clear_color, TARGET_FORMAT),
+    );
+    let result = renderer.render_scene(&view, scene, scene.get_resource_manager());
+
+    let error = match result {
+        Ok(command_buffer) => {
+            context.queue.submit(std::iter::once(command_buffer));
+            device
+                .poll(wgpu::PollType::Wait)
+                .err()
+                .map(|e| e.to_string())
+        }
+        Err(e) => Some(e.to_string()),
+    };
+    let validation_error = device.pop_error_scope().await.map(|e| e.to_string());
+
+    CheckResult::new(name, error.or(validation_error))
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            None => write!(f, "  [PASS] {}", self.name),
+            Some(error) => write!(f, "  [FAIL] {}: {}", self.name, error),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Demo Engine Diagnostics ===")?;
+
+        if let Some(error) = &self.init_error {
+            writeln!(f, "Initialization: FAIL ({})", error)?;
+            return write!(f, "Result: FAIL");
+        }
+
+        if let Some(info) = &self.adapter_info {
+            writeln!(
+                f,
+                "Adapter: {} ({:?}, {:?})",
+                info.name, info.backend, info.device_type
+            )?;
+            writeln!(f, "Driver: {} {}", info.driver, info.driver_info)?;
+        }
+        writeln!(f, "Features: {:?}", self.features)?;
+        if let Some(limits) = &self.limits {
+            writeln!(
+                f,
+                "Limits: max_texture_dimension_2d={}, max_bind_groups={}, \
+                 max_uniform_buffer_binding_size={}, max_vertex_buffers={}",
+                limits.max_texture_dimension_2d,
+                limits.max_bind_groups,
+                limits.max_uniform_buffer_binding_size,
+                limits.max_vertex_buffers
+            )?;
+        }
+
+        match &self.surface {
+            Some(surface) => {
+                writeln!(f, "Surface formats: {:?}", surface.formats)?;
+                writeln!(f, "Present modes: {:?}", surface.present_modes)?;
+            }
+            None => writeln!(f, "Surface: not available (headless)")?,
+        }
+
+        writeln!(f, "Pipelines:")?;
+        for check in &self.pipelines {
+            writeln!(f, "{}", check)?;
+        }
+        writeln!(f, "Meshes:")?;
+        for check in &self.meshes {
+            writeln!(f, "{}", check)?;
+        }
+        writeln!(f, "Frame:")?;
+        if let Some(frame) = &self.frame {
+            writeln!(f, "{}", frame)?;
+        }
+        writeln!(
+            f,
+            "Resources: {} buffers, {} pipelines, {} shaders, {} meshes, {} bind groups",
+            self.resources.buffers,
+            self.resources.pipelines,
+            self.resources.shaders,
+            self.resources.meshes,
+            self.resources.bind_groups
+        )?;
+
+        write!(f, "Result: {}", if self.passed() { "PASS" } else { "FAIL" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_on_software_adapter() {
+        let config = AppConfig::default();
+        let report = pollster::block_on(run_diagnostics(
+            &config,
+            None,
+            DiagnosticsOptions {
+                force_fallback_adapter: true,
+            },
+        ));
+
+        // ソフトウェアアダプタがない環境ではスキップ
+        if report.init_error.is_some() {
+            eprintln!("Skipping: {}", report);
+            return;
+        }
+
+        assert!(report.passed(), "{}", report);
+        assert!(report.surface.is_none());
+
+        let pipeline_names: Vec<&str> = report.pipelines.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(pipeline_names, BUILTIN_PIPELINES);
+
+        let mesh_names: Vec<String> = report.meshes.iter().map(|m| m.name.clone()).collect();
+        let expected: Vec<String> = ObjectType::ALL.iter().map(|t| format!("{:?}", t)).collect();
+        assert_eq!(mesh_names, expected);
+        // プリミティブに加えてグリッドのメッシュがある
+        assert_eq!(report.resources.meshes, ObjectType::ALL.len() + 1);
+        assert_eq!(report.resources.pipelines, BUILTIN_PIPELINES.len());
+    }
+
+    #[test]
+    fn test_report_fails_without_frame() {
+        let report = DiagnosticsReport::default();
+        assert!(!report.passed());
+        assert!(report.to_string().ends_with("Result: FAIL"));
+    }
+}
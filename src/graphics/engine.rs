@@ -2,13 +2,30 @@ use std::sync::Arc;
 
 use crate::{
     core::{
-        config::RenderingConfig,
+        config::{AppConfig, PresentModeConfig, RenderingConfig},
         error::{EngineError, EngineResult},
-        metrics::EngineMetrics,
+        metrics::{EngineMetrics, MetricsSnapshot},
     },
-    graphics::{renderer::Renderer, surface_manager::SurfaceManager},
-    resources::{manager::ResourceManager, primitives::ObjectType},
-    scene::Scene,
+    graphics::{
+        adaptive_quality::AdaptiveQuality,
+        color,
+        context::GpuContext,
+        render_scale::{clamp_render_scale, scaled_size},
+        render_target::RenderTarget,
+        renderer::Renderer,
+        screenshot::{self, ColorSpace},
+        surface_manager::SurfaceManager,
+        taa::DEPTH_FORMAT,
+        tone_mapping::HDR_FORMAT,
+    },
+    resources::{
+        import::ImportOptions,
+        manager::{ResourceId, ResourceManager},
+        pipeline::msaa_sample_count,
+        primitives::ObjectType,
+        uniforms::GlobalUniform,
+    },
+    scene::{Scene, debug_lines::DebugLines, light::PointLight, render_object::ObjectId},
     window::Window,
 };
 
@@ -29,12 +46,114 @@ use crate::{
 /// ```
 pub struct GraphicsEngine {
     device: Arc<wgpu::Device>,
+    adapter_info: wgpu::AdapterInfo,
     queue: Arc<wgpu::Queue>,
     scene: Box<dyn Scene>,
-    config: RenderingConfig,
+    /// Config the engine runs with; runtime changes are written back into it.
+    config: AppConfig,
     metrics: EngineMetrics,
-    surface_manager: SurfaceManager,
+    target: FrameTarget,
     renderer: Renderer,
+    adaptive_quality: Option<AdaptiveQuality>,
+    /// Seconds accumulated from the `dt` passed to `render`.
+    elapsed_time: f32,
+}
+
+/// Color format of the off-screen target in headless mode.
+pub const HEADLESS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Where frames are presented: a window surface or an off-screen texture.
+enum FrameTarget {
+    Surface(SurfaceManager),
+    #[allow(dead_code)]
+    Offscreen(RenderTarget),
+}
+
+impl FrameTarget {
+    /// Format pipelines must target.
+    fn format(&self) -> wgpu::TextureFormat {
+        match self {
+            FrameTarget::Surface(surface_manager) => surface_manager.format(),
+            FrameTarget::Offscreen(target) => target.format,
+        }
+    }
+
+    fn size(&self) -> (u32, u32) {
+        match self {
+            FrameTarget::Surface(surface_manager) => {
+                let config = surface_manager.config();
+                (config.width, config.height)
+            }
+            FrameTarget::Offscreen(target) => (target.width(), target.height()),
+        }
+    }
+}
+
+/// Backends the adapter may come from: the configured one, or all of them.
+fn backends(config: &RenderingConfig) -> wgpu::Backends {
+    let Some(name) = config.backend.as_deref() else {
+        return wgpu::Backends::all();
+    };
+    parse_backend(name).unwrap_or_else(|| {
+        log::warn!(
+            "Unknown backend {:?} (expected vulkan, dx12, metal or gl), using all backends",
+            name
+        );
+        wgpu::Backends::all()
+    })
+}
+
+/// Parses a backend name (case-insensitive) into its `wgpu::Backends` flag.
+fn parse_backend(name: &str) -> Option<wgpu::Backends> {
+    match name.to_ascii_lowercase().as_str() {
+        "vulkan" => Some(wgpu::Backends::VULKAN),
+        "dx12" => Some(wgpu::Backends::DX12),
+        "metal" => Some(wgpu::Backends::METAL),
+        "gl" => Some(wgpu::Backends::GL),
+        _ => None,
+    }
+}
+
+/// Format the scene pipelines and targets render in: HDR while tone mapping,
+/// otherwise the frame target's own format.
+fn scene_format(
+    config: &RenderingConfig,
+    target_format: wgpu::TextureFormat,
+) -> wgpu::TextureFormat {
+    if config.tone_mapping {
+        HDR_FORMAT
+    } else {
+        target_format
+    }
+}
+
+/// Color space of the values a frame leaves in an output of `format`.
+///
+/// sRGB formats are encoded in hardware and the tone mapping pass encodes
+/// its output itself; on other formats shader output is stored linear.
+/// (The clear color is the exception: `clear_color_for_format` encodes it
+/// for every non-sRGB output.)
+fn output_color_space(config: &RenderingConfig, format: wgpu::TextureFormat) -> ColorSpace {
+    if format.is_srgb() || config.tone_mapping {
+        ColorSpace::Srgb
+    } else {
+        ColorSpace::Linear
+    }
+}
+
+/// Optional device features the rendering config can make use of.
+fn optional_features(config: &RenderingConfig) -> wgpu::Features {
+    let mut optional_features = wgpu::Features::empty();
+    if config.conservative_raster {
+        optional_features |= wgpu::Features::CONSERVATIVE_RASTERIZATION;
+    }
+    // 実行時にワイヤーフレームへ切り替えられるよう常に要求する
+    optional_features |= wgpu::Features::POLYGON_MODE_LINE;
+    if config.msaa_samples > 1 {
+        // 2x/8x はアダプタ固有のフォーマット機能が必要
+        optional_features |= wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+    }
+    optional_features
 }
 
 impl GraphicsEngine {
@@ -47,6 +166,7 @@ impl GraphicsEngine {
     ///
     /// * `window` - The window to render to
     /// * `scene` - The scene to be rendered
+    /// * `app_config` - Application configuration (rendering and metrics settings)
     ///
     /// # Returns
     ///
@@ -54,77 +174,459 @@ impl GraphicsEngine {
     ///
     /// # Errors
     ///
-    /// Returns `EngineError` if WGPU initialization fails.
+    /// Returns `EngineError` if WGPU initialization or scene initialization
+    /// (e.g. an invalid shader) fails.
     pub async fn new(
         window: Window,
+        scene: Box<dyn Scene>,
+        app_config: &AppConfig,
+    ) -> EngineResult<Self> {
+        let config = &app_config.rendering;
+
+        let context =
+            GpuContext::with_backends(backends(config), false, optional_features(config)).await?;
+        if config.wireframe
+            && !context
+                .device
+                .features()
+                .contains(wgpu::Features::POLYGON_MODE_LINE)
+        {
+            log::error!(
+                "Wireframe rendering requires POLYGON_MODE_LINE, which the adapter does not support"
+            );
+        }
+
+        let surface_manager = SurfaceManager::new(
+            &context.instance,
+            &window,
+            &context.adapter,
+            &context.device,
+            config,
+        )?;
+
+        Self::with_target(
+            context,
+            scene,
+            app_config,
+            FrameTarget::Surface(surface_manager),
+            true,
+        )
+    }
+
+    /// Creates an engine that renders into an off-screen texture instead of a
+    /// window surface (CI, automated image tests).
+    ///
+    /// The scene is initialized but left empty; populate it through
+    /// [`GraphicsEngine::scene_mut`] and read frames back with
+    /// [`GraphicsEngine::render_to_image`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if no adapter is available, device creation fails
+    /// or the scene fails to initialize.
+    #[allow(dead_code)]
+    pub async fn new_headless(
+        width: u32,
+        height: u32,
+        scene: Box<dyn Scene>,
+        app_config: &AppConfig,
+    ) -> EngineResult<Self> {
+        let config = &app_config.rendering;
+        let context =
+            GpuContext::with_backends(backends(config), false, optional_features(config)).await?;
+        let target = RenderTarget::new(
+            &context.device,
+            width,
+            height,
+            HEADLESS_FORMAT,
+            "Headless Target",
+        );
+
+        Self::with_target(
+            context,
+            scene,
+            app_config,
+            FrameTarget::Offscreen(target),
+            false,
+        )
+    }
+
+    /// Shared setup of the windowed and headless engines: renderer targets,
+    /// resource manager and scene initialization. `demo_content` adds the
+    /// sample objects shown in the windowed demo.
+    fn with_target(
+        context: GpuContext,
         mut scene: Box<dyn Scene>,
-        config: &RenderingConfig,
+        app_config: &AppConfig,
+        target: FrameTarget,
+        demo_content: bool,
     ) -> EngineResult<Self> {
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            ..Default::default()
-        });
+        let config = &app_config.rendering;
+        let format = scene_format(config, target.format());
+        let format_features = if context
+            .device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            context.adapter.get_texture_format_features(format)
+        } else {
+            format.guaranteed_format_features(context.device.features())
+        };
+        let mut sample_count = msaa_sample_count(config.msaa_samples, format_features.flags);
+        if config.taa && sample_count > 1 {
+            log::warn!("TAA is enabled, disabling MSAA x{}", sample_count);
+            sample_count = 1;
+        }
 
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: None,
-                force_fallback_adapter: false,
-            })
-            .await
-            .map_err(|e| {
-                EngineError::AdapterRequest(format!("Failed to request adapter: {}", e))
-            })?;
-
-        let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                label: None,
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
-                memory_hints: wgpu::MemoryHints::default(),
-                trace: wgpu::Trace::default(),
-            })
-            .await
-            .map_err(|e| EngineError::DeviceRequest(format!("Failed to request device: {}", e)))?;
+        let adapter_info = context.adapter.get_info();
+        log::info!(
+            "Using adapter {:?} ({:?}, {:?})",
+            adapter_info.name,
+            adapter_info.backend,
+            adapter_info.device_type
+        );
 
-        let surface_manager = SurfaceManager::new(&instance, &window, &adapter, &device, config)?;
+        let device = context.device;
+        let queue = context.queue;
 
-        let device = Arc::new(device);
+        let (width, height) = target.size();
+        let render_scale = clamp_render_scale(config.render_scale);
+        let (render_width, render_height) = scaled_size(width, height, render_scale);
+        let mut renderer = Renderer::new(
+            device.clone(),
+            color::clear_color_for_format(config.clear_color, format),
+        )
+        .with_msaa(format, render_width, render_height, sample_count)
+        .with_overlay(queue.clone(), target.format(), width, height)
+        .with_debug_lines(format)
+        .with_gpu_labels(app_config.debug.gpu_labels);
+        if config.taa {
+            renderer = renderer.with_taa(queue.clone(), format, render_width, render_height);
+        }
+        if render_scale != 1.0 || config.adaptive.enabled {
+            renderer = renderer.with_render_scale(format, width, height, render_scale);
+        }
+        if config.tone_mapping {
+            renderer = renderer.with_tone_mapping(
+                queue.clone(),
+                target.format(),
+                width,
+                height,
+                config.exposure,
+            );
+        }
+        if config.fxaa {
+            renderer = renderer.with_fxaa(target.format(), width, height);
+        }
+        let adaptive_quality = config
+            .adaptive
+            .enabled
+            .then(|| AdaptiveQuality::new(&config.adaptive, render_scale));
 
-        let queue: Arc<wgpu::Queue> = Arc::new(queue);
+        let mut resource_manager = ResourceManager::new(device.clone(), queue.clone(), format)
+            .with_sample_count(sample_count)
+            .with_depth_format(config.taa.then_some(DEPTH_FORMAT));
 
-        let renderer = Renderer::new(device.clone(), config.clear_color);
+        let checker_texture_id = ResourceId::new("checker_texture");
+        let pyramid_mesh_id = ResourceId::new("pyramid_mesh");
+        if demo_content {
+            if let Err(e) = resource_manager.create_texture(
+                checker_texture_id,
+                include_bytes!("../../assets/textures/checker.png"),
+                Some("Checker Texture"),
+            ) {
+                log::error!("Failed to load texture: {}", e);
+            }
 
-        let resource_manager =
-            ResourceManager::new(device.clone(), queue.clone(), surface_manager.format());
+            if let Err(e) = resource_manager.load_obj(
+                pyramid_mesh_id,
+                "assets/models/pyramid.obj",
+                &ImportOptions::default(),
+            ) {
+                log::error!("Failed to load mesh: {}", e);
+            }
+        }
 
         // シーンを初期化
-        scene.initialize(resource_manager);
+        scene.initialize(resource_manager)?;
 
-        scene.add_object(
-            ObjectType::Sphere,
-            glam::Vec3 {
-                x: -2.0,
-                y: -2.0,
-                z: 0.0,
-            },
-        );
+        if demo_content {
+            scene.add_object(
+                ObjectType::Sphere,
+                glam::Vec3 {
+                    x: -2.0,
+                    y: -2.0,
+                    z: 0.0,
+                },
+            );
+            scene.add_textured_quad(glam::vec3(2.0, -2.0, 0.0), checker_texture_id);
+            scene.add_mesh_object(pyramid_mesh_id, glam::vec3(0.0, -2.0, -2.0));
+            let pulse_cube = scene.add_object(ObjectType::Cube, glam::vec3(-2.0, 2.0, -2.0));
+            scene.set_object_pulse(pulse_cube, true);
+            scene.add_point_light(PointLight::new(
+                glam::vec3(-1.0, -1.0, 1.5),
+                glam::vec3(1.0, 0.6, 0.2),
+                4.0,
+            ));
+        }
 
-        let metrics = EngineMetrics::new();
+        let mut metrics = EngineMetrics::new().with_window_size(app_config.metrics.window_size);
+        if app_config.metrics.histogram_enabled {
+            metrics = metrics.with_histogram(&app_config.metrics.histogram_buckets_ms);
+        }
 
         Ok(GraphicsEngine {
             device,
+            adapter_info,
             queue,
             scene,
-            config: config.clone(),
+            config: app_config.clone(),
             metrics,
-            surface_manager,
+            target,
             renderer,
+            adaptive_quality,
+            elapsed_time: 0.0,
         })
     }
 
+    /// Makes `scene` the active scene and returns the previous one.
+    ///
+    /// The incoming scene is initialized with the shared resource manager,
+    /// which rebuilds its camera and light bind groups, and is resized to the
+    /// current target. The outgoing scene keeps its objects and can be passed
+    /// back in later. Frame-time metrics restart for the new scene.
+    ///
+    /// # Errors
+    ///
+    /// Returns the initialization error of `scene`, which is dropped; the
+    /// current scene stays active.
+    pub fn set_scene(&mut self, mut scene: Box<dyn Scene>) -> EngineResult<Box<dyn Scene>> {
+        let resource_manager = self
+            .scene
+            .take_resource_manager()
+            .expect("Active scene has no resource manager");
+        if let Err(e) = scene.initialize(resource_manager) {
+            // リソースマネージャーを取り戻して現在のシーンを続ける
+            let resource_manager = scene
+                .take_resource_manager()
+                .expect("Failed scene has no resource manager");
+            self.scene.initialize(resource_manager)?;
+            return Err(e);
+        }
+
+        let (width, height) = self.target.size();
+        scene.on_resize(width, height);
+
+        self.metrics.reset();
+        Ok(std::mem::replace(&mut self.scene, scene))
+    }
+
+    /// Adapter the engine renders with (name, backend, device type, driver),
+    /// useful in bug reports.
+    #[allow(dead_code)]
+    pub fn adapter_info(&self) -> wgpu::AdapterInfo {
+        self.adapter_info.clone()
+    }
+
+    /// Limits of the active device.
+    #[allow(dead_code)]
+    pub fn device_limits(&self) -> wgpu::Limits {
+        self.device.limits()
+    }
+
+    /// Mutable access to the scene, e.g. to populate a headless engine.
+    pub fn scene_mut(&mut self) -> &mut dyn Scene {
+        self.scene.as_mut()
+    }
+
+    /// Returns the world-space ray `(origin, direction)` under a cursor
+    /// position (pixels from the top-left corner of the render target).
+    pub fn screen_to_ray(&self, mouse_pos: glam::Vec2) -> (glam::Vec3, glam::Vec3) {
+        let (width, height) = self.target.size();
+        self.scene
+            .screen_to_ray(mouse_pos, glam::vec2(width as f32, height as f32))
+    }
+
+    /// Returns the nearest object under a cursor position (pixels from the
+    /// top-left corner of the render target).
+    pub fn pick_object(&self, mouse_pos: glam::Vec2) -> Option<ObjectId> {
+        let (origin, direction) = self.screen_to_ray(mouse_pos);
+        self.scene.pick(origin, direction)
+    }
+
+    /// Lines to draw over the next frame; cleared after it is rendered.
+    pub fn debug_lines_mut(&mut self) -> &mut DebugLines {
+        self.scene.debug_lines_mut()
+    }
+
+    /// Frame timing and object counts, updated by every `render` call.
+    #[allow(dead_code)]
+    pub fn metrics(&self) -> &EngineMetrics {
+        &self.metrics
+    }
+
+    /// Current fps, frame time and object counts by value.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Clears the rolling frame-time statistics.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+        log::info!("Engine metrics reset");
+    }
+
+    /// Exports the accumulated frame-time histogram to `path` as CSV.
+    pub fn export_frame_time_histogram(&self, path: &str) -> EngineResult<()> {
+        self.metrics.export_csv(path).map_err(|e| {
+            EngineError::RenderError(format!("Failed to export frame-time histogram: {}", e))
+        })?;
+        log::info!("Frame-time histogram exported to {}", path);
+        Ok(())
+    }
+
+    /// Starts writing per-frame timings to a CSV file at `path`.
+    pub fn start_metrics_recording(&mut self, path: &str) -> EngineResult<()> {
+        self.metrics.start_recording(path).map_err(|e| {
+            EngineError::RenderError(format!("Failed to start metrics recording: {}", e))
+        })?;
+        log::info!("Recording frame metrics to {}", path);
+        Ok(())
+    }
+
+    /// Stops the metrics recording and flushes the file.
+    pub fn stop_metrics_recording(&mut self) -> EngineResult<()> {
+        self.metrics.stop_recording().map_err(|e| {
+            EngineError::RenderError(format!("Failed to finish metrics recording: {}", e))
+        })?;
+        log::info!("Metrics recording stopped");
+        Ok(())
+    }
+
+    /// Returns `true` while frame metrics are being recorded.
+    pub fn is_recording_metrics(&self) -> bool {
+        self.metrics.is_recording()
+    }
+
+    /// Applies the settings that can change without recreating GPU resources:
+    /// clear color, vsync, and the scene's movement and camera projection.
+    ///
+    /// The rest of `config` takes effect on the next start, but is kept so
+    /// that `export_config` does not discard it.
+    pub fn apply_config(&mut self, config: &AppConfig) {
+        // 出力フォーマットは起動時の設定で決まっている
+        let format = scene_format(&self.config.rendering, self.target.format());
+        self.renderer.set_clear_color(color::clear_color_for_format(
+            config.rendering.clear_color,
+            format,
+        ));
+        self.renderer.set_exposure(config.rendering.exposure);
+        self.config = config.clone();
+        self.set_present_mode(config.rendering.present_mode());
+        self.set_show_metrics(config.rendering.show_metrics);
+        self.scene.apply_config(config);
+    }
+
+    /// Returns the current config including settings changed at runtime
+    /// (vsync, metrics overlay, camera zoom, light, wireframe and grid
+    /// toggles), ready for `AppConfig::save_to_file`.
+    pub fn export_config(&self) -> AppConfig {
+        let mut config = self.config.clone();
+        self.scene.export_config(&mut config);
+        config
+    }
+
+    /// Returns whether the metrics overlay is shown.
+    pub fn show_metrics(&self) -> bool {
+        self.config.rendering.show_metrics
+    }
+
+    /// Shows or hides the fps / frame-time / object-count overlay.
+    pub fn set_show_metrics(&mut self, show_metrics: bool) {
+        self.config.rendering.show_metrics = show_metrics;
+        if !show_metrics {
+            self.renderer.set_overlay_text("");
+        }
+    }
+
+    /// Returns whether presentation is synced to the display refresh.
+    pub fn vsync(&self) -> bool {
+        self.present_mode() == PresentModeConfig::Vsync
+    }
+
+    /// Switches between vsync and uncapped (`Immediate`) presentation.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.set_present_mode(if vsync {
+            PresentModeConfig::Vsync
+        } else {
+            PresentModeConfig::Immediate
+        });
+    }
+
+    /// Returns the requested present mode; the surface may fall back to
+    /// `Fifo` if it does not support it.
+    pub fn present_mode(&self) -> PresentModeConfig {
+        self.config.rendering.present_mode()
+    }
+
+    /// Switches the present mode on the live surface without recreating the
+    /// engine. Has no effect on headless engines beyond recording the setting.
+    pub fn set_present_mode(&mut self, mode: PresentModeConfig) {
+        self.config.rendering.present_mode = Some(mode);
+        self.config.rendering.vsync = mode == PresentModeConfig::Vsync;
+        if let FrameTarget::Surface(surface_manager) = &mut self.target {
+            surface_manager.apply_present_mode(&self.device, mode);
+        }
+    }
+
+    /// Renders the current scene into an off-screen texture and writes it to
+    /// `path` as a PNG in `color_space` (8-bit sRGB or 16-bit linear).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if rendering, readback or writing the file fails.
+    pub fn capture_frame(&self, path: &str, color_space: ColorSpace) -> EngineResult<()> {
+        let (width, height) = self.target.size();
+
+        let target = RenderTarget::new(
+            &self.device,
+            width,
+            height,
+            self.target.format(),
+            "Capture Target",
+        );
+
+        let command_buffer = self.renderer.render_scene_to(
+            &target,
+            self.scene.as_ref(),
+            self.scene.get_resource_manager(),
+        )?;
+        self.queue.submit(std::iter::once(command_buffer));
+
+        let texels = screenshot::read_texture_rgba8(&self.device, &self.queue, &target.texture)?;
+        screenshot::save_png(
+            path,
+            width,
+            height,
+            screenshot::convert_texels(
+                &texels,
+                output_color_space(&self.config.rendering, self.target.format()),
+                color_space,
+            ),
+        )?;
+        log::info!("Saved screenshot to {}", path);
+        Ok(())
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
-        self.surface_manager.resize(&self.device, width, height);
+        match &mut self.target {
+            FrameTarget::Surface(surface_manager) => {
+                surface_manager.resize(&self.device, width, height)
+            }
+            FrameTarget::Offscreen(target) => target.resize(&self.device, width, height),
+        }
+        self.renderer.resize(width, height);
+        self.scene.on_resize(width, height);
     }
 
     /// Renders a single frame.
@@ -144,24 +646,864 @@ impl GraphicsEngine {
         self.metrics
             .update(dt, self.scene.get_render_objects().len());
         self.metrics.check_performance();
+        if self.config.rendering.show_metrics {
+            self.renderer.set_overlay_text(&format!(
+                "FPS: {:.1}\nFRAME: {:.2} MS\nOBJECTS: {}\nCULLED: {}",
+                self.metrics.get_fps(),
+                self.metrics.get_frame_time_ms(),
+                self.metrics.get_object_count(),
+                self.metrics.get_culled_object_count()
+            ));
+        }
+
+        if let Some(adaptive_quality) = &mut self.adaptive_quality
+            && let Some(scale) = adaptive_quality.update(self.metrics.get_fps(), dt)
+        {
+            log::info!("Adaptive quality: render scale {:.2}", scale);
+            self.renderer.set_render_scale(scale);
+        }
+
+        self.renderer.begin_frame();
 
         // シーン更新
         log::debug!("GraphicsEngine::render called with dt={}", dt);
         self.scene.update(dt, input);
 
-        // カメラユニフォーム更新（毎フレーム）
+        // 経過時間・カメラ・モデルユニフォーム更新（毎フレーム）
+        self.elapsed_time += dt;
+        let (width, height) = self.target.size();
+        self.scene.update_global_uniform(GlobalUniform::new(
+            self.elapsed_time,
+            [width as f32, height as f32],
+        ));
+        self.scene
+            .set_projection_jitter(self.renderer.projection_jitter());
         self.scene.update_camera_uniform();
+        self.scene.update_light_uniform();
+        self.scene.update_model_uniforms();
+        self.renderer.prepare_taa(self.scene.camera_view_proj());
 
-        let surface_frame = self.surface_manager.acquire_frame()?;
+        match &mut self.target {
+            FrameTarget::Surface(surface_manager) => {
+                let surface_frame = surface_manager.acquire_frame(&self.device)?;
 
-        let command_buffer = self.renderer.render_scene(
-            &surface_frame.view,
-            self.scene.as_ref(),
-            self.scene.get_resource_manager(),
-        )?;
+                let command_buffer = self.renderer.render_scene(
+                    &surface_frame.view,
+                    self.scene.as_ref(),
+                    self.scene.get_resource_manager(),
+                )?;
 
-        self.queue.submit(std::iter::once(command_buffer));
-        surface_frame.present();
+                self.queue.submit(std::iter::once(command_buffer));
+                surface_frame.present();
+            }
+            FrameTarget::Offscreen(target) => {
+                let command_buffer = self.renderer.render_scene_to(
+                    target,
+                    self.scene.as_ref(),
+                    self.scene.get_resource_manager(),
+                )?;
+                self.queue.submit(std::iter::once(command_buffer));
+            }
+        }
+        self.metrics
+            .set_culled_object_count(self.renderer.culled_object_count());
+        self.scene.debug_lines_mut().clear();
         Ok(())
     }
+
+    /// Renders one frame of a headless engine and returns it as tightly packed
+    /// RGBA8 sRGB bytes (`width * height * 4`, rows top to bottom).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::RenderError` if the engine renders to a window
+    /// surface, or any error from rendering and readback.
+    #[allow(dead_code)]
+    pub fn render_to_image(
+        &mut self,
+        dt: f32,
+        input: &crate::input::InputState,
+    ) -> EngineResult<Vec<u8>> {
+        if !matches!(self.target, FrameTarget::Offscreen(_)) {
+            return Err(EngineError::RenderError(
+                "render_to_image requires a headless engine".to_string(),
+            ));
+        }
+
+        self.render(dt, input)?;
+        let FrameTarget::Offscreen(target) = &self.target else {
+            unreachable!();
+        };
+        screenshot::read_texture_rgba8(&self.device, &self.queue, &target.texture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        input::InputState,
+        scene::{camera::Camera, demo_scene::DemoScene, viewport::Viewport},
+    };
+
+    const SIZE: u32 = 32;
+
+    fn create_headless_engine(config: AppConfig) -> Option<GraphicsEngine> {
+        let config = Arc::new(config);
+        let scene = Box::new(DemoScene::new(1.0, config.clone()));
+        match pollster::block_on(GraphicsEngine::new_headless(SIZE, SIZE, scene, &config)) {
+            Ok(engine) => Some(engine),
+            // アダプタがない環境ではスキップするが、シーン初期化の失敗は隠さない
+            Err(EngineError::AdapterRequest(_) | EngineError::DeviceRequest(_)) => None,
+            Err(e) => panic!("エンジンの作成に失敗: {}", e),
+        }
+    }
+
+    /// Config loading the basic shader from a directory holding invalid WGSL.
+    fn config_with_broken_shader(shader_dir: &std::path::Path) -> AppConfig {
+        std::fs::create_dir_all(shader_dir.join("basic")).unwrap();
+        std::fs::write(shader_dir.join("basic/triangle.wgsl"), "fn broken( {").unwrap();
+
+        let mut config = AppConfig::default();
+        config.debug.shader_hot_reload = true;
+        config.debug.shader_dir = shader_dir.to_string_lossy().into_owned();
+        config
+    }
+
+    #[test]
+    fn test_adapter_info_and_limits_are_queryable() {
+        let Some(engine) = create_headless_engine(AppConfig::default()) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+
+        let info = engine.adapter_info();
+        assert!(!info.name.is_empty(), "アダプタ名が取得できる");
+        assert_ne!(info.backend, wgpu::Backend::Noop);
+        assert!(
+            engine.device_limits().max_texture_dimension_2d
+                >= wgpu::Limits::downlevel_defaults().max_texture_dimension_2d
+        );
+    }
+
+    #[test]
+    fn test_vsync_toggle_leaves_mailbox() {
+        let mut config = AppConfig::default();
+        config.rendering.present_mode = Some(PresentModeConfig::Mailbox);
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        assert!(!engine.vsync(), "Mailbox は垂直同期扱いしない");
+
+        engine.set_vsync(true);
+        assert_eq!(engine.present_mode(), PresentModeConfig::Vsync);
+        engine.set_vsync(false);
+        assert_eq!(engine.present_mode(), PresentModeConfig::Immediate);
+    }
+
+    #[test]
+    fn test_export_config_reflects_runtime_changes() {
+        let Some(mut engine) = create_headless_engine(AppConfig::default()) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+
+        let mut changed = AppConfig::default();
+        changed.rendering.clear_color = [0.2, 0.3, 0.4, 1.0];
+        changed.rendering.msaa_samples = 1;
+        engine.apply_config(&changed);
+        engine.set_vsync(false);
+        engine.set_show_metrics(true);
+        engine.scene_mut().set_grid_visible(true);
+        engine.scene_mut().set_light_direction(glam::Vec3::NEG_Y);
+        // ホイールで視野角を狭める
+        let mut input = InputState::new();
+        input.process_scroll(winit::event::MouseScrollDelta::LineDelta(0.0, 1.0));
+        engine.render_to_image(0.0, &input).unwrap();
+
+        let exported = engine.export_config();
+        assert_eq!(exported.rendering.clear_color, [0.2, 0.3, 0.4, 1.0]);
+        assert_eq!(
+            exported.rendering.msaa_samples, 1,
+            "再起動が必要な設定も保持する"
+        );
+        assert_eq!(
+            exported.rendering.present_mode(),
+            PresentModeConfig::Immediate
+        );
+        assert!(exported.rendering.show_metrics);
+        assert!(exported.debug.show_grid);
+        assert_eq!(exported.lighting.direction, [0.0, -1.0, 0.0]);
+        assert!(exported.camera.fov_degrees < AppConfig::default().camera.fov_degrees);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let path = path.to_str().unwrap();
+        exported.save_to_file(path).unwrap();
+        let loaded = AppConfig::load_from_file(path).unwrap();
+        assert_eq!(
+            loaded.rendering.present_mode(),
+            PresentModeConfig::Immediate
+        );
+        assert_eq!(loaded.camera.fov_degrees, exported.camera.fov_degrees);
+    }
+
+    #[test]
+    fn test_backend_names_parse_case_insensitively() {
+        assert_eq!(parse_backend("vulkan"), Some(wgpu::Backends::VULKAN));
+        assert_eq!(parse_backend("DX12"), Some(wgpu::Backends::DX12));
+        assert_eq!(parse_backend("Metal"), Some(wgpu::Backends::METAL));
+        assert_eq!(parse_backend("gl"), Some(wgpu::Backends::GL));
+        assert_eq!(parse_backend("opengl"), None);
+    }
+
+    #[test]
+    fn test_unknown_or_missing_backend_uses_all() {
+        let mut config = AppConfig::default().rendering;
+        assert_eq!(backends(&config), wgpu::Backends::all());
+
+        config.backend = Some("directx9".to_string());
+        assert_eq!(
+            backends(&config),
+            wgpu::Backends::all(),
+            "不明な名前は全バックエンド"
+        );
+
+        config.backend = Some("gl".to_string());
+        assert_eq!(backends(&config), wgpu::Backends::GL);
+    }
+
+    fn pixel(image: &[u8], x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * SIZE + x) * 4) as usize;
+        image[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_headless_renders_clear_color() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [1.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        assert_eq!(image.len(), (SIZE * SIZE * 4) as usize);
+        assert_eq!(pixel(&image, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, SIZE - 1, SIZE - 1), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_output_color_space_follows_target_format() {
+        let mut config = AppConfig::default().rendering;
+        let srgb = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let unorm = wgpu::TextureFormat::Bgra8Unorm;
+        assert_eq!(output_color_space(&config, srgb), ColorSpace::Srgb);
+        assert_eq!(
+            output_color_space(&config, unorm),
+            ColorSpace::Linear,
+            "非sRGBの出力にはシェーダーの線形値がそのまま書かれる"
+        );
+
+        config.tone_mapping = true;
+        assert_eq!(output_color_space(&config, unorm), ColorSpace::Srgb);
+    }
+
+    #[test]
+    fn test_capture_frame_bit_depth_follows_color_space() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [1.0, 0.0, 0.0, 1.0];
+        let Some(engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let srgb_path = temp_dir.path().join("srgb.png");
+        engine
+            .capture_frame(srgb_path.to_str().unwrap(), ColorSpace::Srgb)
+            .unwrap();
+        let srgb = image::open(&srgb_path).unwrap();
+        assert_eq!(srgb.color(), image::ColorType::Rgba8);
+
+        let linear_path = temp_dir.path().join("linear.png");
+        engine
+            .capture_frame(linear_path.to_str().unwrap(), ColorSpace::Linear)
+            .unwrap();
+        let linear = image::open(&linear_path).unwrap();
+        assert_eq!(linear.color(), image::ColorType::Rgba16, "線形出力は16bit");
+        assert_eq!(linear.to_rgba16().get_pixel(0, 0).0, [65535, 0, 0, 65535]);
+    }
+
+    #[test]
+    fn test_headless_renders_scene_objects() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        // カメラ正面の立方体が画面中央を覆う
+        engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        assert_ne!(pixel(&image, SIZE / 2, SIZE / 2), [0, 0, 0, 255]);
+        assert_eq!(
+            pixel(&image, 0, 0),
+            [0, 0, 0, 255],
+            "背景はクリアカラーのまま"
+        );
+    }
+
+    #[test]
+    fn test_headless_renders_object_color() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let scene = engine.scene_mut();
+        let cube = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        scene.set_object_color(cube, [0.0, 1.0, 0.0, 1.0]);
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        // 緑以外の成分は色の乗算で消える
+        let [r, g, b, _] = pixel(&image, SIZE / 2, SIZE / 2);
+        assert_eq!((r, b), (0, 0), "{:?}", (r, g, b));
+        assert!(g > 0);
+    }
+
+    #[test]
+    fn test_instanced_objects_keep_their_own_transform_and_color() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        // 同じメッシュの立方体を左右に置き、色だけ変える
+        let scene = engine.scene_mut();
+        for (x, color) in [(-0.7, [1.0, 0.0, 0.0, 1.0]), (0.7, [0.0, 1.0, 0.0, 1.0])] {
+            let cube = scene.add_object(ObjectType::Cube, glam::vec3(x, 0.0, 0.0));
+            scene.scale_object(cube, glam::Vec3::splat(0.5));
+            scene.set_object_color(cube, color);
+        }
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        let [r, g, b, _] = pixel(&image, SIZE / 2 - 9, SIZE / 2);
+        assert!(r > 0 && g == 0 && b == 0, "左は赤: {:?}", (r, g, b));
+        let [r, g, b, _] = pixel(&image, SIZE / 2 + 9, SIZE / 2);
+        assert!(r == 0 && g > 0 && b == 0, "右は緑: {:?}", (r, g, b));
+        assert_eq!(
+            pixel(&image, SIZE / 2, SIZE / 2),
+            [0, 0, 0, 255],
+            "間は背景"
+        );
+    }
+
+    #[test]
+    fn test_translucent_objects_blend_regardless_of_insertion_order() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let near = (glam::vec3(0.0, 0.0, 0.5), [0.0, 1.0, 0.0, 0.5]);
+        let far = (glam::vec3(0.0, 0.0, -1.0), [1.0, 0.0, 0.0, 0.5]);
+
+        // 追加順を入れ替えても奥から描かれるので同じ色になる
+        let mut centers = Vec::new();
+        for order in [[near, far], [far, near]] {
+            let scene = engine.scene_mut();
+            let ids: Vec<_> = order
+                .iter()
+                .map(|&(position, color)| {
+                    let id = scene.add_object(ObjectType::Cube, position);
+                    scene.set_object_color(id, color);
+                    id
+                })
+                .collect();
+
+            let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+            centers.push(pixel(&image, SIZE / 2, SIZE / 2));
+
+            for id in ids {
+                engine.scene_mut().remove_object(id);
+            }
+        }
+
+        assert_eq!(centers[0], centers[1]);
+        let [r, g, _, _] = centers[0];
+        assert!(r > 0 && g > 0, "両方の色が混ざる: {:?}", centers[0]);
+    }
+
+    #[test]
+    fn test_pulse_shader_color_changes_with_elapsed_time() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let cube = engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        assert!(engine.scene_mut().set_object_pulse(cube, true));
+
+        let first = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        let unchanged = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        let later = engine.render_to_image(1.5, &InputState::new()).unwrap();
+
+        let center = |image: &[u8]| pixel(image, SIZE / 2, SIZE / 2);
+        assert_eq!(
+            center(&first),
+            center(&unchanged),
+            "時間が進まなければ同じ色"
+        );
+        assert_ne!(center(&first), center(&later), "経過時間で色が変わるべき");
+    }
+
+    #[test]
+    fn test_grid_overlay_is_toggleable() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        // 目の高さより下に置いた立方体に合わせるとカメラはグリッドより下から見上げる
+        let cube = engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::vec3(0.0, -1.0, -6.0));
+        engine.scene_mut().frame_object(cube);
+        engine.scene_mut().set_object_visible(cube, false);
+
+        let hidden = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        engine.scene_mut().set_grid_visible(true);
+        let shown = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        assert!(
+            hidden.chunks(4).all(|p| p[..3] == [0, 0, 0]),
+            "グリッドは既定で非表示"
+        );
+        let grid_pixels = shown.chunks(4).filter(|p| p[..3] != [0, 0, 0]).count();
+        assert!(grid_pixels > 0, "グリッドの線が描かれるべき");
+    }
+
+    #[test]
+    fn test_tone_mapping_compresses_bright_colors() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [4.0, 1.0, 0.25, 1.0];
+        config.rendering.tone_mapping = true;
+        let Some(mut engine) = create_headless_engine(config.clone()) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        let expected = |value: f32, exposure: f32| {
+            let mapped = crate::graphics::tone_mapping::reinhard(value, exposure);
+            (color::linear_to_srgb(mapped) * 255.0).round() as i32
+        };
+        let texel = pixel(&image, SIZE / 2, SIZE / 2);
+        // 1.0 を超える値も飽和せず、チャンネルの比が残る
+        for (channel, value) in [4.0, 1.0, 0.25].into_iter().enumerate() {
+            assert!(
+                (texel[channel] as i32 - expected(value, 1.0)).abs() <= 2,
+                "チャンネル {}: {} != {}",
+                channel,
+                texel[channel],
+                expected(value, 1.0)
+            );
+        }
+        assert!(texel[0] < 255);
+
+        // 露出を下げると暗くなる
+        config.rendering.exposure = 0.5;
+        engine.apply_config(&config);
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        let darker = pixel(&image, SIZE / 2, SIZE / 2);
+        assert!((darker[0] as i32 - expected(4.0, 0.5)).abs() <= 2);
+        assert!(darker[..3].iter().zip(&texel[..3]).all(|(d, t)| d < t));
+    }
+
+    /// Sum over neighboring pixel pairs of their largest channel difference.
+    fn edge_contrast(image: &[u8]) -> u32 {
+        let mut contrast = 0;
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let here = pixel(image, x, y);
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    if nx < SIZE && ny < SIZE {
+                        let there = pixel(image, nx, ny);
+                        contrast +=
+                            (0..3).map(|c| here[c].abs_diff(there[c])).max().unwrap() as u32;
+                    }
+                }
+            }
+        }
+        contrast
+    }
+
+    #[test]
+    fn test_fxaa_softens_cube_edges() {
+        let render = |fxaa: bool| {
+            let mut config = AppConfig::default();
+            config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+            config.rendering.fxaa = fxaa;
+            let mut engine = create_headless_engine(config)?;
+            // 斜めのエッジが出るよう傾ける
+            let cube = engine
+                .scene_mut()
+                .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+            engine.scene_mut().rotate_object(
+                cube,
+                glam::Quat::from_euler(glam::EulerRot::XYZ, 0.4, 0.6, 0.5),
+            );
+            Some(engine.render_to_image(0.0, &InputState::new()).unwrap())
+        };
+        let (Some(aliased), Some(smoothed)) = (render(false), render(true)) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+
+        assert!(
+            edge_contrast(&smoothed) < edge_contrast(&aliased),
+            "FXAA でエッジのコントラストが下がるべき"
+        );
+        // 変化するのは元の画像でエッジに接するピクセルだけ
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let changed = (0..3)
+                    .any(|c| pixel(&aliased, x, y)[c].abs_diff(pixel(&smoothed, x, y)[c]) > 2);
+                let near_edge = (x.saturating_sub(1)..(x + 2).min(SIZE)).any(|nx| {
+                    (y.saturating_sub(1)..(y + 2).min(SIZE))
+                        .any(|ny| pixel(&aliased, nx, ny)[..3] != pixel(&aliased, x, y)[..3])
+                });
+                assert!(
+                    !changed || near_edge,
+                    "({}, {}) はエッジから離れている",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_screen_views_use_their_own_cameras() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let camera_config = config.camera.clone();
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        // 立方体をメインカメラの前と後ろに置く
+        for z in [0.0, 6.0] {
+            engine
+                .scene_mut()
+                .add_object(ObjectType::Cube, glam::vec3(0.0, 0.0, z));
+        }
+        engine.scene_mut().set_main_viewport(Viewport::LEFT_HALF);
+
+        // 右半分のカメラは反対を向き、メインカメラの後ろの立方体を映す
+        let mut camera = Camera::new(1.0, &camera_config);
+        camera.look_at(
+            glam::vec3(0.0, 0.0, 3.0),
+            glam::vec3(0.0, 0.0, 6.0),
+            glam::Vec3::Y,
+        );
+        engine
+            .scene_mut()
+            .add_camera_view(camera, Viewport::RIGHT_HALF)
+            .unwrap();
+
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+        for x in [SIZE / 4, SIZE * 3 / 4] {
+            assert_ne!(
+                pixel(&image, x, SIZE / 2)[..3],
+                [0, 0, 0],
+                "x={} のビューポート中央に立方体が映るべき",
+                x
+            );
+        }
+        // ビューポートの外には描かない
+        assert_eq!(pixel(&image, SIZE / 2, 1)[..3], [0, 0, 0]);
+    }
+
+    /// Screen pixel a world-space point is drawn at by the main camera.
+    fn project_to_pixel(engine: &mut GraphicsEngine, point: glam::Vec3) -> (u32, u32) {
+        let ndc = engine.scene_mut().camera_view_proj().project_point3(point);
+        let x = (ndc.x * 0.5 + 0.5) * SIZE as f32;
+        let y = (0.5 - ndc.y * 0.5) * SIZE as f32;
+        (x as u32, y as u32)
+    }
+
+    #[test]
+    fn test_cube_casts_shadow_that_follows_the_light() {
+        let render = |shadows: bool, light_direction: glam::Vec3| {
+            let mut config = AppConfig::default();
+            config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+            config.rendering.shadows = shadows;
+            let mut engine = create_headless_engine(config)?;
+            // 床の上に浮かぶ立方体
+            let scene = engine.scene_mut();
+            let floor = scene.add_object(ObjectType::Plane, glam::vec3(0.0, -1.0, 0.0));
+            scene.scale_object(floor, glam::vec3(6.0, 1.0, 6.0));
+            scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+            scene.set_light_direction(light_direction);
+            let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+            let below = project_to_pixel(&mut engine, glam::vec3(0.0, -1.0, 0.0));
+            let right = project_to_pixel(&mut engine, glam::vec3(1.0, -1.0, 0.0));
+            let left = project_to_pixel(&mut engine, glam::vec3(-1.0, -1.0, 0.0));
+            let brightness = |(x, y): (u32, u32)| -> u32 {
+                pixel(&image, x, y)[..3].iter().map(|&c| c as u32).sum()
+            };
+            Some([brightness(below), brightness(right), brightness(left)])
+        };
+        let Some(unshadowed) = render(false, glam::Vec3::NEG_Y) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let overhead = render(true, glam::Vec3::NEG_Y).unwrap();
+        let slanted = render(true, glam::vec3(1.0, -1.0, 0.0)).unwrap();
+
+        // 真上からの光では立方体の真下だけが暗くなる
+        assert!(
+            overhead[0] * 3 < unshadowed[0] * 2,
+            "立方体の真下に影が落ちるべき: {:?} / {:?}",
+            overhead,
+            unshadowed
+        );
+        for side in [1, 2] {
+            assert!(
+                overhead[side].abs_diff(unshadowed[side]) <= 3,
+                "影の外は影なしと同じ明るさ（アクネなし）: {:?} / {:?}",
+                overhead,
+                unshadowed
+            );
+        }
+        // 光を傾けると影が光の進む向き（+X）へ移る
+        assert!(
+            slanted[1] * 3 < slanted[2] * 2,
+            "影が +X 側へ移るべき: {:?}",
+            slanted
+        );
+    }
+
+    #[test]
+    fn test_metrics_report_scene_object_count() {
+        let Some(mut engine) = create_headless_engine(AppConfig::default()) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        for x in [-1.0, 0.0, 1.0] {
+            engine
+                .scene_mut()
+                .add_object(ObjectType::Cube, glam::vec3(x, 0.0, 0.0));
+        }
+
+        for _ in 0..5 {
+            engine.render_to_image(0.02, &InputState::new()).unwrap();
+        }
+
+        let snapshot = engine.metrics_snapshot();
+        assert_eq!(
+            snapshot.object_count,
+            engine.scene_mut().get_render_objects().len()
+        );
+        assert_eq!(snapshot.object_count, 3);
+        assert!((snapshot.fps - 50.0).abs() < 0.01);
+        assert_eq!(engine.metrics().get_object_count(), 3);
+    }
+
+    #[test]
+    fn test_back_facing_triangle_is_culled_unless_double_sided() {
+        for double_sided_flat in [false, true] {
+            let mut config = AppConfig::default();
+            config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+            config.rendering.double_sided_flat = double_sided_flat;
+            let Some(mut engine) = create_headless_engine(config) else {
+                eprintln!("Skipping: no adapter");
+                return;
+            };
+            // 裏面がカメラを向くように半回転させる
+            let scene = engine.scene_mut();
+            let triangle = scene.add_object(ObjectType::Triangle, glam::Vec3::ZERO);
+            scene.rotate_object(triangle, glam::Quat::from_rotation_y(std::f32::consts::PI));
+
+            let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+
+            let visible = pixel(&image, SIZE / 2, SIZE / 2) != [0, 0, 0, 255];
+            assert_eq!(
+                visible, double_sided_flat,
+                "背面はカリング無効時のみ描かれる"
+            );
+        }
+    }
+
+    #[test]
+    fn test_debug_lines_are_drawn_for_one_frame() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let input = InputState::new();
+        // 画面中央を横切る線（オブジェクトより手前に描かれる）
+        engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        engine.debug_lines_mut().draw_line(
+            glam::vec3(-5.0, 0.0, 0.0),
+            glam::vec3(5.0, 0.0, 0.0),
+            [0.0, 1.0, 0.0, 1.0],
+        );
+
+        let image = engine.render_to_image(0.0, &input).unwrap();
+        let row = SIZE / 2;
+        let green_pixels = (0..SIZE)
+            .filter(|&x| {
+                pixel(&image, x, row) == [0, 255, 0, 255]
+                    || pixel(&image, x, row - 1) == [0, 255, 0, 255]
+            })
+            .count();
+        assert_eq!(
+            green_pixels, SIZE as usize,
+            "線が画面の端から端まで描かれる"
+        );
+
+        // 描画後に消去されるので次のフレームには残らない
+        assert!(engine.scene_mut().debug_lines().is_empty());
+        let image = engine.render_to_image(0.0, &input).unwrap();
+        assert_ne!(pixel(&image, 0, row), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn test_objects_outside_frustum_are_culled() {
+        let Some(mut engine) = create_headless_engine(AppConfig::default()) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        // 正面に1つ、真後ろと遠い横に1つずつ置く
+        let scene = engine.scene_mut();
+        scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        scene.add_object(ObjectType::Cube, glam::vec3(0.0, 0.0, 10.0));
+        let side = scene.add_object(ObjectType::Cube, glam::vec3(50.0, 0.0, 0.0));
+
+        engine.render_to_image(0.0, &InputState::new()).unwrap();
+        assert_eq!(engine.metrics.get_culled_object_count(), 2);
+
+        // 拡大して球が視錐台にかかれば描画対象に戻る
+        engine
+            .scene_mut()
+            .scale_object(side, glam::Vec3::splat(100.0));
+        engine.render_to_image(0.0, &InputState::new()).unwrap();
+        assert_eq!(engine.metrics.get_culled_object_count(), 1);
+    }
+
+    #[test]
+    fn test_invalid_shader_fails_engine_creation() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Arc::new(config_with_broken_shader(dir.path()));
+        let scene = Box::new(DemoScene::new(1.0, config.clone()));
+
+        match pollster::block_on(GraphicsEngine::new_headless(SIZE, SIZE, scene, &config)) {
+            Err(EngineError::ShaderCompilation(_)) => {}
+            Err(EngineError::AdapterRequest(_) | EngineError::DeviceRequest(_)) => {
+                eprintln!("Skipping: no adapter");
+            }
+            Err(e) => panic!("シェーダーのエラーになるべき: {}", e),
+            Ok(_) => panic!("不正なシェーダーでエンジンが作成された"),
+        }
+    }
+
+    #[test]
+    fn test_set_scene_keeps_current_scene_when_initialization_fails() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let dir = tempfile::tempdir().unwrap();
+        let broken_scene = Box::new(DemoScene::new(
+            1.0,
+            Arc::new(config_with_broken_shader(dir.path())),
+        ));
+
+        assert!(matches!(
+            engine.set_scene(broken_scene),
+            Err(EngineError::ShaderCompilation(_))
+        ));
+
+        // 元のシーンが再初期化され、そのまま描画できる
+        let image = engine.render_to_image(0.0, &InputState::new()).unwrap();
+        assert_ne!(pixel(&image, SIZE / 2, SIZE / 2), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_set_scene_swaps_and_restores_scenes() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        engine
+            .scene_mut()
+            .add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let input = InputState::new();
+        let center = |image: &[u8]| pixel(image, SIZE / 2, SIZE / 2);
+
+        let empty_scene = Box::new(DemoScene::new(1.0, Arc::new(AppConfig::default())));
+        let cube_scene = engine.set_scene(empty_scene).unwrap();
+        let image = engine.render_to_image(0.0, &input).unwrap();
+        assert_eq!(
+            center(&image),
+            [0, 0, 0, 255],
+            "空のシーンには何も描かれない"
+        );
+
+        // 元のシーンに戻すとオブジェクトが再び描画される
+        let empty_scene = engine.set_scene(cube_scene).unwrap();
+        assert!(empty_scene.get_render_objects().is_empty());
+        let image = engine.render_to_image(0.0, &input).unwrap();
+        assert_ne!(center(&image), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_metrics_overlay_draws_over_scene() {
+        let mut config = AppConfig::default();
+        config.rendering.clear_color = [0.0, 0.0, 0.0, 1.0];
+        config.rendering.show_metrics = true;
+        let Some(mut engine) = create_headless_engine(config) else {
+            eprintln!("Skipping: no adapter");
+            return;
+        };
+        let input = InputState::new();
+
+        // "FPS" の F の左上ピクセルは余白の直後に描かれる
+        let image = engine.render_to_image(0.016, &input).unwrap();
+        assert_ne!(pixel(&image, 8, 8), [0, 0, 0, 255]);
+
+        engine.set_show_metrics(false);
+        let image = engine.render_to_image(0.016, &input).unwrap();
+        assert_eq!(pixel(&image, 8, 8), [0, 0, 0, 255]);
+    }
 }
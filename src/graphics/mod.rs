@@ -1,3 +1,15 @@
+pub mod adaptive_quality;
+pub mod color;
+pub mod context;
+pub mod debug_lines;
+pub mod diagnostics;
 pub mod engine;
+pub mod fxaa;
+pub mod overlay;
+pub mod render_scale;
+pub mod render_target;
 pub mod renderer;
+pub mod screenshot;
 pub mod surface_manager;
+pub mod taa;
+pub mod tone_mapping;
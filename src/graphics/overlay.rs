@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+/// Width and height of a glyph cell in font pixels.
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+/// Horizontal advance and line height in font pixels.
+const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + 1;
+const LINE_HEIGHT: u32 = GLYPH_HEIGHT + 2;
+/// Screen pixels per font pixel.
+const PIXEL_SCALE: u32 = 2;
+/// Distance of the text from the top-left corner in screen pixels.
+const MARGIN: u32 = 8;
+
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 0.6, 1.0];
+const SHADOW_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// Returns the 5x7 bitmap of `c` (one row per byte, bit 4 is the leftmost
+/// pixel). Lowercase letters use the uppercase glyphs; unsupported
+/// characters render as blanks.
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        _ => [0x00; 7],
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OverlayVertex {
+    /// Normalized device coordinates.
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
+impl OverlayVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4];
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Builds two triangles per lit font pixel of `text`, placed at the top-left
+/// of a `width` x `height` target, each glyph preceded by a drop shadow.
+pub fn text_vertices(text: &str, width: u32, height: u32) -> Vec<OverlayVertex> {
+    let (width, height) = (width.max(1) as f32, height.max(1) as f32);
+    let pixel = PIXEL_SCALE as f32;
+    let mut vertices = Vec::new();
+
+    // 画面ピクセル座標（左上原点、Y下向き）の矩形をNDCの2三角形にする
+    let mut push_rect = |x: f32, y: f32, color: [f32; 4]| {
+        let left = x / width * 2.0 - 1.0;
+        let right = (x + pixel) / width * 2.0 - 1.0;
+        let top = 1.0 - y / height * 2.0;
+        let bottom = 1.0 - (y + pixel) / height * 2.0;
+        for position in [
+            [left, top],
+            [left, bottom],
+            [right, bottom],
+            [left, top],
+            [right, bottom],
+            [right, top],
+        ] {
+            vertices.push(OverlayVertex { position, color });
+        }
+    };
+
+    for (line_index, line) in text.lines().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            let origin_x = (MARGIN + column as u32 * GLYPH_ADVANCE * PIXEL_SCALE) as f32;
+            let origin_y = (MARGIN + line_index as u32 * LINE_HEIGHT * PIXEL_SCALE) as f32;
+            let rows = glyph(c);
+
+            for (offset, color) in [(pixel, SHADOW_COLOR), (0.0, TEXT_COLOR)] {
+                for (row, bits) in rows.iter().enumerate() {
+                    for col in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                            push_rect(
+                                origin_x + (col * PIXEL_SCALE) as f32 + offset,
+                                origin_y + (row as u32 * PIXEL_SCALE) as f32 + offset,
+                                color,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    vertices
+}
+
+/// Text drawn on top of the finished frame with a built-in 5x7 bitmap font
+/// (debug HUD such as the metrics overlay).
+pub struct TextOverlay {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    /// Vertices the buffer can hold.
+    capacity: usize,
+    vertex_count: u32,
+    text: String,
+    size: (u32, u32),
+}
+
+impl TextOverlay {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Overlay Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../assets/shaders/overlay/text.wgsl").into(),
+            ),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Text Overlay Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Overlay Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[OverlayVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let capacity = 1024;
+        let vertex_buffer = Self::create_vertex_buffer(&device, capacity);
+
+        Self {
+            device,
+            queue,
+            pipeline,
+            vertex_buffer,
+            capacity,
+            vertex_count: 0,
+            text: String::new(),
+            size: (width, height),
+        }
+    }
+
+    fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Text Overlay Vertex Buffer"),
+            size: (capacity * std::mem::size_of::<OverlayVertex>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Replaces the displayed text; an empty string hides the overlay.
+    pub fn set_text(&mut self, text: &str) {
+        if text != self.text {
+            self.text = text.to_string();
+            self.upload();
+        }
+    }
+
+    /// Re-lays out the text for a new target size (ignored if either side is 0).
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.size = (width, height);
+        self.upload();
+    }
+
+    fn upload(&mut self) {
+        let vertices = text_vertices(&self.text, self.size.0, self.size.1);
+        if vertices.len() > self.capacity {
+            self.capacity = vertices.len().next_power_of_two();
+            self.vertex_buffer = Self::create_vertex_buffer(&self.device, self.capacity);
+        }
+        if !vertices.is_empty() {
+            self.queue
+                .write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+        }
+        self.vertex_count = vertices.len() as u32;
+    }
+
+    /// Draws the text over the existing contents of `view`.
+    pub fn draw(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, label: &str) {
+        if self.vertex_count == 0 {
+            return;
+        }
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit_pixels(c: char) -> usize {
+        glyph(c).iter().map(|row| row.count_ones() as usize).sum()
+    }
+
+    #[test]
+    fn test_text_vertex_count() {
+        // 1ピクセルにつき2三角形、影と本体で2回描く
+        let vertices = text_vertices("1", 640, 480);
+        assert_eq!(vertices.len(), lit_pixels('1') * 6 * 2);
+
+        let vertices = text_vertices("FPS: 60", 640, 480);
+        let expected: usize = "FPS: 60".chars().map(lit_pixels).sum();
+        assert_eq!(vertices.len(), expected * 6 * 2);
+    }
+
+    #[test]
+    fn test_blank_and_unknown_characters_draw_nothing() {
+        assert!(text_vertices("", 640, 480).is_empty());
+        assert!(text_vertices("  ?\n", 640, 480).is_empty());
+        assert_eq!(
+            text_vertices("fps", 640, 480).len(),
+            text_vertices("FPS", 640, 480).len(),
+            "小文字は大文字のグリフを使う"
+        );
+    }
+
+    #[test]
+    fn test_text_starts_at_top_left() {
+        let vertices = text_vertices("8\n8", 200, 100);
+        let text_vertices: Vec<_> = vertices
+            .iter()
+            .filter(|vertex| vertex.color == TEXT_COLOR)
+            .collect();
+
+        let min_x = text_vertices
+            .iter()
+            .map(|vertex| vertex.position[0])
+            .fold(f32::MAX, f32::min);
+        let max_y = text_vertices
+            .iter()
+            .map(|vertex| vertex.position[1])
+            .fold(f32::MIN, f32::max);
+        let min_y = text_vertices
+            .iter()
+            .map(|vertex| vertex.position[1])
+            .fold(f32::MAX, f32::min);
+
+        // 左上から MARGIN ピクセル内側
+        assert!((min_x - (MARGIN as f32 / 200.0 * 2.0 - 1.0)).abs() < 1e-6);
+        assert!((max_y - (1.0 - MARGIN as f32 / 100.0 * 2.0)).abs() < 1e-6);
+        // 2行目は1行目より下に配置される
+        let second_line_bottom =
+            1.0 - (MARGIN + (LINE_HEIGHT + GLYPH_HEIGHT) * PIXEL_SCALE) as f32 / 100.0 * 2.0;
+        assert!((min_y - second_line_bottom).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,264 @@
+use std::sync::Arc;
+
+/// Smallest accepted internal resolution scale.
+pub const MIN_RENDER_SCALE: f32 = 0.25;
+/// Largest accepted internal resolution scale (2x supersampling).
+pub const MAX_RENDER_SCALE: f32 = 2.0;
+
+/// Clamps a requested render scale to the supported range.
+pub fn clamp_render_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE)
+    } else {
+        1.0
+    }
+}
+
+/// Internal resolution for a surface of `width` x `height` at `scale`
+/// (each side at least 1).
+pub fn scaled_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    let scale = clamp_render_scale(scale);
+    let side = |length: u32| ((length as f32 * scale).round() as u32).max(1);
+    (side(width), side(height))
+}
+
+/// Off-screen color target rendered at a scaled internal resolution and
+/// upscaled (bilinear) into the output view.
+pub struct RenderScaleTarget {
+    device: Arc<wgpu::Device>,
+    format: wgpu::TextureFormat,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    surface_size: (u32, u32),
+    scale: f32,
+}
+
+impl RenderScaleTarget {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        scale: f32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Upscale Blit Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../assets/shaders/upscale/blit.wgsl").into(),
+            ),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Upscale Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Upscale Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Upscale Blit Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Upscale Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let scale = clamp_render_scale(scale);
+        let (view, bind_group) = Self::create_target(
+            &device,
+            &bind_group_layout,
+            &sampler,
+            format,
+            scaled_size(width, height, scale),
+        );
+
+        Self {
+            device,
+            format,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            view,
+            bind_group,
+            surface_size: (width, height),
+            scale,
+        }
+    }
+
+    fn create_target(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        format: wgpu::TextureFormat,
+        (width, height): (u32, u32),
+    ) -> (wgpu::TextureView, wgpu::BindGroup) {
+        let view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Scaled Scene Color"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Upscale Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+
+        (view, bind_group)
+    }
+
+    fn recreate_target(&mut self) {
+        let (view, bind_group) = Self::create_target(
+            &self.device,
+            &self.bind_group_layout,
+            &self.sampler,
+            self.format,
+            self.internal_size(),
+        );
+        self.view = view;
+        self.bind_group = bind_group;
+    }
+
+    /// Resolution the scene is rendered at.
+    pub fn internal_size(&self) -> (u32, u32) {
+        scaled_size(self.surface_size.0, self.surface_size.1, self.scale)
+    }
+
+    /// Recreates the target for a new surface size, keeping the scale.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.surface_size = (width, height);
+        self.recreate_target();
+    }
+
+    /// Changes the internal resolution scale (clamped to the supported range).
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = clamp_render_scale(scale);
+        self.recreate_target();
+    }
+
+    /// View the scene pass renders into.
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Upscales the scaled scene color into `output_view`.
+    pub fn blit(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        output_view: &wgpu::TextureView,
+        label: &str,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_size_rounds_and_keeps_minimum() {
+        assert_eq!(scaled_size(800, 600, 1.0), (800, 600));
+        assert_eq!(scaled_size(800, 600, 0.5), (400, 300));
+        assert_eq!(scaled_size(801, 601, 0.5), (401, 301));
+        // 極端に小さくても1ピクセルは残る
+        assert_eq!(scaled_size(1, 1, 0.25), (1, 1));
+    }
+
+    #[test]
+    fn test_render_scale_is_clamped() {
+        assert_eq!(clamp_render_scale(0.01), MIN_RENDER_SCALE);
+        assert_eq!(clamp_render_scale(4.0), MAX_RENDER_SCALE);
+        assert_eq!(clamp_render_scale(f32::NAN), 1.0);
+        assert_eq!(scaled_size(100, 100, 0.0), (25, 25));
+    }
+}
@@ -0,0 +1,63 @@
+/// Off-screen color texture the renderer can draw into instead of a surface
+/// view (headless frames, captures, post effects, picking).
+///
+/// The texture is created with `RENDER_ATTACHMENT | TEXTURE_BINDING | COPY_SRC`
+/// so it can be sampled by later passes or read back to the CPU.
+pub struct RenderTarget {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub format: wgpu::TextureFormat,
+    label: String,
+}
+
+impl RenderTarget {
+    /// Creates a `width` x `height` target (each side at least 1).
+    pub fn new(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+        label: &str,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            texture,
+            view,
+            format,
+            label: label.to_string(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    /// Recreates the texture at a new size (ignored if either side is 0).
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        if width == 0 || height == 0 || (width, height) == (self.width(), self.height()) {
+            return;
+        }
+        *self = Self::new(device, width, height, self.format, &self.label);
+    }
+}
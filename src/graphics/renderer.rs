@@ -1,10 +1,99 @@
-use std::sync::Arc;
+use std::{cell::Cell, collections::HashMap, ops::Range, sync::Arc};
 
-use crate::{core::error::EngineResult, resources::manager::ResourceManager, scene::Scene};
+use wgpu::util::DeviceExt;
+
+use crate::{
+    core::error::EngineResult,
+    graphics::{
+        debug_lines::DebugLineRenderer, fxaa::Fxaa, overlay::TextOverlay,
+        render_scale::RenderScaleTarget, render_target::RenderTarget, taa::TemporalAntiAliasing,
+        tone_mapping::ToneMapping,
+    },
+    resources::{
+        manager::{ResourceId, ResourceManager},
+        vertex::InstanceData,
+    },
+    scene::{
+        Scene,
+        frustum::Frustum,
+        light::ShadowPass,
+        render_object::{LAYER_ALL, RenderObject},
+        viewport::SceneView,
+    },
+};
 
 pub struct Renderer {
     device: Arc<wgpu::Device>,
     clear_color: [f32; 4],
+    msaa: Option<MsaaTarget>,
+    taa: Option<TemporalAntiAliasing>,
+    /// Scaled internal-resolution target, upscaled into the surface.
+    render_scale: Option<RenderScaleTarget>,
+    /// HDR target tone mapped into the surface after any upscale.
+    tone_mapping: Option<ToneMapping>,
+    /// Anti-aliases the finished frame (after tone mapping) into the surface.
+    fxaa: Option<Fxaa>,
+    /// Debug text drawn over the finished frame.
+    overlay: Option<TextOverlay>,
+    /// Draws the scene's debug lines after the scene pass.
+    debug_lines: Option<DebugLineRenderer>,
+    /// Emit per-frame labels and debug groups for GPU capture tools.
+    gpu_labels: bool,
+    frame_index: u64,
+    /// Objects skipped by frustum culling in the last rendered pass.
+    culled_objects: Cell<usize>,
+}
+
+/// Formats a GPU object label such as `"Frame 1234 / Scene Pass"`.
+pub fn frame_label(frame_index: u64, pass_name: &str) -> String {
+    format!("Frame {} / {}", frame_index, pass_name)
+}
+
+/// Name of the pass drawing the objects selected by `layer_mask`.
+fn pass_name(layer_mask: u32) -> String {
+    if layer_mask == LAYER_ALL {
+        "Scene Pass".to_string()
+    } else {
+        format!("Layer Pass {:#x}", layer_mask)
+    }
+}
+
+/// Multisampled color texture rendered into and resolved to the output view.
+struct MsaaTarget {
+    view: wgpu::TextureView,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+}
+
+impl MsaaTarget {
+    fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("MSAA Color Target"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        Self {
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            format,
+            sample_count,
+        }
+    }
 }
 
 impl Renderer {
@@ -12,69 +101,700 @@ impl Renderer {
         Self {
             device,
             clear_color,
+            msaa: None,
+            taa: None,
+            render_scale: None,
+            tone_mapping: None,
+            fxaa: None,
+            overlay: None,
+            debug_lines: None,
+            gpu_labels: false,
+            frame_index: 0,
+            culled_objects: Cell::new(0),
+        }
+    }
+
+    /// Labels encoders and passes with the frame index and pass name, and
+    /// wraps each draw in a debug group (RenderDoc/PIX captures).
+    pub fn with_gpu_labels(mut self, gpu_labels: bool) -> Self {
+        self.gpu_labels = gpu_labels;
+        self
+    }
+
+    /// Replaces the clear color (already converted for the target format).
+    pub fn set_clear_color(&mut self, clear_color: [f32; 4]) {
+        self.clear_color = clear_color;
+    }
+
+    /// Advances the frame index used in GPU labels; call once per frame.
+    pub fn begin_frame(&mut self) {
+        self.frame_index += 1;
+    }
+
+    /// Number of objects the last rendered pass skipped because their
+    /// bounding sphere was outside the camera frustum.
+    pub fn culled_object_count(&self) -> usize {
+        self.culled_objects.get()
+    }
+
+    /// Renders into a multisampled texture resolved to the output view.
+    ///
+    /// A `sample_count` of 1 disables multisampling. The count must match the
+    /// one the scene pipelines were created with.
+    pub fn with_msaa(
+        mut self,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) -> Self {
+        self.msaa = (sample_count > 1)
+            .then(|| MsaaTarget::new(&self.device, format, width, height, sample_count));
+        self
+    }
+
+    /// Enables temporal anti-aliasing.
+    ///
+    /// The scene is drawn into an off-screen target with a depth buffer
+    /// ([`crate::graphics::taa::DEPTH_FORMAT`]) and resolved into the output view. Scene pipelines
+    /// must be single-sampled and use that depth format.
+    pub fn with_taa(
+        mut self,
+        queue: Arc<wgpu::Queue>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        self.taa = Some(TemporalAntiAliasing::new(
+            self.device.clone(),
+            queue,
+            format,
+            width,
+            height,
+        ));
+        self
+    }
+
+    /// Renders the scene at `scale` times the surface resolution and upscales
+    /// it into the output view.
+    ///
+    /// `width`/`height` are the surface size; MSAA and TAA targets must be
+    /// created at the scaled size (see [`crate::graphics::render_scale::scaled_size`]).
+    pub fn with_render_scale(
+        mut self,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        scale: f32,
+    ) -> Self {
+        self.render_scale = Some(RenderScaleTarget::new(
+            self.device.clone(),
+            format,
+            width,
+            height,
+            scale,
+        ));
+        self
+    }
+
+    /// Renders the scene into an HDR target at surface resolution, then
+    /// applies exposure, Reinhard tone mapping and gamma into the output view.
+    ///
+    /// `output_format` is the surface format; the scene pipelines and the
+    /// other scene targets must use [`crate::graphics::tone_mapping::HDR_FORMAT`].
+    pub fn with_tone_mapping(
+        mut self,
+        queue: Arc<wgpu::Queue>,
+        output_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        exposure: f32,
+    ) -> Self {
+        self.tone_mapping = Some(ToneMapping::new(
+            self.device.clone(),
+            queue,
+            output_format,
+            width,
+            height,
+            exposure,
+        ));
+        self
+    }
+
+    /// Smooths edges with an FXAA pass over the finished frame, before the
+    /// overlay. `format` and `width`/`height` are those of the surface.
+    pub fn with_fxaa(mut self, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        self.fxaa = Some(Fxaa::new(self.device.clone(), format, width, height));
+        self
+    }
+
+    /// Changes the tone-mapping exposure at runtime.
+    ///
+    /// Ignored unless tone mapping was enabled with `with_tone_mapping`.
+    pub fn set_exposure(&mut self, exposure: f32) {
+        if let Some(tone_mapping) = &mut self.tone_mapping {
+            tone_mapping.set_exposure(exposure);
+        }
+    }
+
+    /// Enables a text overlay drawn over the final output at surface
+    /// resolution, after any resolve and upscale passes.
+    pub fn with_overlay(
+        mut self,
+        queue: Arc<wgpu::Queue>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        self.overlay = Some(TextOverlay::new(
+            self.device.clone(),
+            queue,
+            format,
+            width,
+            height,
+        ));
+        self
+    }
+
+    /// Draws the scene's [`DebugLines`](crate::scene::debug_lines::DebugLines)
+    /// over the main pass, before any upscale.
+    pub fn with_debug_lines(mut self, format: wgpu::TextureFormat) -> Self {
+        self.debug_lines = Some(DebugLineRenderer::new(&self.device, format));
+        self
+    }
+
+    /// Sets the overlay text; an empty string hides it.
+    ///
+    /// Ignored unless the overlay was enabled with `with_overlay`.
+    pub fn set_overlay_text(&mut self, text: &str) {
+        if let Some(overlay) = &mut self.overlay {
+            overlay.set_text(text);
+        }
+    }
+
+    /// Changes the internal resolution scale at runtime.
+    ///
+    /// Ignored unless render scaling was enabled with `with_render_scale`.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        if let Some(render_scale) = &mut self.render_scale {
+            render_scale.set_scale(scale);
+            let (width, height) = render_scale.internal_size();
+            self.resize_scene_targets(width, height);
+        }
+    }
+
+    /// Projection jitter the camera should use for the current frame
+    /// (zero when TAA is disabled).
+    pub fn projection_jitter(&self) -> glam::Vec2 {
+        self.taa
+            .as_ref()
+            .map_or(glam::Vec2::ZERO, |taa| taa.jitter(self.frame_index))
+    }
+
+    /// Uploads per-frame TAA data; call after the camera uniform is updated.
+    ///
+    /// `view_proj` is the camera view-projection without jitter.
+    pub fn prepare_taa(&mut self, view_proj: glam::Mat4) {
+        let jitter = self.projection_jitter();
+        if let Some(taa) = &mut self.taa {
+            taa.prepare(view_proj, jitter);
+        }
+    }
+
+    /// Recreates size-dependent targets (ignored if either side is 0).
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        if let Some(overlay) = &mut self.overlay {
+            overlay.resize(width, height);
+        }
+        if let Some(tone_mapping) = &mut self.tone_mapping {
+            tone_mapping.resize(width, height);
         }
+        if let Some(fxaa) = &mut self.fxaa {
+            fxaa.resize(width, height);
+        }
+        let (width, height) = match &mut self.render_scale {
+            Some(render_scale) => {
+                render_scale.resize(width, height);
+                render_scale.internal_size()
+            }
+            None => (width, height),
+        };
+        self.resize_scene_targets(width, height);
     }
 
+    /// Recreates the targets the scene pass renders into at the internal resolution.
+    fn resize_scene_targets(&mut self, width: u32, height: u32) {
+        if let Some(taa) = &mut self.taa {
+            taa.resize(width, height);
+        }
+        if let Some(msaa) = &self.msaa {
+            self.msaa = Some(MsaaTarget::new(
+                &self.device,
+                msaa.format,
+                width,
+                height,
+                msaa.sample_count,
+            ));
+        }
+    }
+
+    /// Renders the whole scene into a surface view.
     pub fn render_scene(
         &self,
         surface_view: &wgpu::TextureView,
         scene: &dyn Scene,
         resource_manager: &ResourceManager,
     ) -> EngineResult<wgpu::CommandBuffer> {
+        self.render_scene_layers(surface_view, scene, resource_manager, LAYER_ALL)
+    }
+
+    /// Renders the whole scene into an off-screen render target.
+    ///
+    /// The target must match the format the pipelines were built for.
+    pub fn render_scene_to(
+        &self,
+        target: &RenderTarget,
+        scene: &dyn Scene,
+        resource_manager: &ResourceManager,
+    ) -> EngineResult<wgpu::CommandBuffer> {
+        self.render_scene_layers(&target.view, scene, resource_manager, LAYER_ALL)
+    }
+
+    /// Renders only the objects whose layer mask intersects `layer_mask`.
+    ///
+    /// Used by effect passes (glow, outlines, reflections) that target a
+    /// subset of the scene.
+    pub fn render_scene_layers(
+        &self,
+        surface_view: &wgpu::TextureView,
+        scene: &dyn Scene,
+        resource_manager: &ResourceManager,
+        layer_mask: u32,
+    ) -> EngineResult<wgpu::CommandBuffer> {
+        let (encoder_label, pass_label) = if self.gpu_labels {
+            (
+                format!("Frame {}", self.frame_index),
+                frame_label(self.frame_index, &pass_name(layer_mask)),
+            )
+        } else {
+            ("Render Encoder".to_string(), "Render Pass".to_string())
+        };
+
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
+                label: Some(&encoder_label),
             });
 
+        // FXAA 時は完成したフレームをオフスクリーンに書き、最後にサーフェスへ適用する
+        let frame_view = self.fxaa.as_ref().map_or(surface_view, |fxaa| fxaa.view());
+        // トーンマッピング時は HDR のオフスクリーンを経由して書く
+        let hdr_view = self
+            .tone_mapping
+            .as_ref()
+            .map_or(frame_view, |tone_mapping| tone_mapping.view());
+        // 内部解像度が違う場合はオフスクリーンに描画し、最後にサーフェスへ拡大する
+        let output_view = self
+            .render_scale
+            .as_ref()
+            .map_or(hdr_view, |render_scale| render_scale.view());
+
+        // ビューポートはオフスクリーンの内部解像度を基準にする
+        let target_size = output_view.texture().size();
+        let views = scene.views();
+        let mut culled_objects = 0;
+        let view_draws: Vec<_> = views
+            .iter()
+            .map(|view| {
+                let (draws, culled) =
+                    self.prepare_view(view, scene, resource_manager, layer_mask, target_size);
+                culled_objects += culled;
+                draws
+            })
+            .collect();
+        self.culled_objects.set(culled_objects);
+
+        // 影を落とすオブジェクトはカメラの視錐台の外にあってもよいので、カリングせずに描く
+        if let Some(shadow_pass) = scene.shadow_pass() {
+            let shadow_label = if self.gpu_labels {
+                frame_label(self.frame_index, "Shadow Pass")
+            } else {
+                "Shadow Pass".to_string()
+            };
+            self.draw_shadow_map(
+                &mut encoder,
+                &shadow_pass,
+                scene,
+                resource_manager,
+                &shadow_label,
+            );
+        }
+
         {
-            let mut render_pass = self.create_render_pass(&mut encoder, surface_view);
+            let mut render_pass = self.create_render_pass(&mut encoder, output_view, &pass_label);
 
-            if let Some(camera_bind_group) = scene.get_camera_bind_group() {
-                render_pass.set_bind_group(0, camera_bind_group.as_ref(), &[]);
+            for (index, draws) in view_draws.iter().enumerate() {
+                if self.gpu_labels && view_draws.len() > 1 {
+                    render_pass.push_debug_group(&format!("View {}", index));
+                }
+                self.draw_view(&mut render_pass, draws, scene, resource_manager, layer_mask);
+                if self.gpu_labels && view_draws.len() > 1 {
+                    render_pass.pop_debug_group();
+                }
             }
+        }
 
-            for object in scene.get_render_objects() {
-                if !object.visible {
+        if let Some(taa) = &self.taa {
+            let resolve_label = if self.gpu_labels {
+                frame_label(self.frame_index, "TAA Resolve Pass")
+            } else {
+                "TAA Resolve Pass".to_string()
+            };
+            taa.resolve(&mut encoder, output_view, &resolve_label);
+        }
+
+        // デバッグ線は TAA の解決後に重ね、ジッターや履歴の影響を受けないようにする
+        if let Some(debug_lines) = &self.debug_lines
+            && layer_mask == LAYER_ALL
+        {
+            let debug_lines_label = if self.gpu_labels {
+                frame_label(self.frame_index, "Debug Line Pass")
+            } else {
+                "Debug Line Pass".to_string()
+            };
+            let cameras: Vec<_> = views
+                .iter()
+                .map(|view| {
+                    (
+                        view.view_proj,
+                        view.viewport
+                            .pixel_rect(target_size.width, target_size.height),
+                    )
+                })
+                .collect();
+            debug_lines.draw(
+                &self.device,
+                &mut encoder,
+                output_view,
+                &debug_lines_label,
+                scene.debug_lines(),
+                &cameras,
+            );
+        }
+
+        if let Some(render_scale) = &self.render_scale {
+            let upscale_label = if self.gpu_labels {
+                frame_label(self.frame_index, "Upscale Pass")
+            } else {
+                "Upscale Pass".to_string()
+            };
+            render_scale.blit(&mut encoder, hdr_view, &upscale_label);
+        }
+
+        if let Some(tone_mapping) = &self.tone_mapping {
+            let tone_mapping_label = if self.gpu_labels {
+                frame_label(self.frame_index, "Tone Mapping Pass")
+            } else {
+                "Tone Mapping Pass".to_string()
+            };
+            tone_mapping.apply(&mut encoder, frame_view, &tone_mapping_label);
+        }
+
+        if let Some(fxaa) = &self.fxaa {
+            let fxaa_label = if self.gpu_labels {
+                frame_label(self.frame_index, "FXAA Pass")
+            } else {
+                "FXAA Pass".to_string()
+            };
+            fxaa.apply(&mut encoder, surface_view, &fxaa_label);
+        }
+
+        // オーバーレイは全レイヤーを描くメインパスの後にだけ重ねる
+        if let Some(overlay) = &self.overlay
+            && layer_mask == LAYER_ALL
+        {
+            let overlay_label = if self.gpu_labels {
+                frame_label(self.frame_index, "Overlay Pass")
+            } else {
+                "Overlay Pass".to_string()
+            };
+            overlay.draw(&mut encoder, surface_view, &overlay_label);
+        }
+
+        Ok(encoder.finish())
+    }
+
+    /// Records a compute pass into `encoder` that runs `pipeline` over
+    /// `workgroups` (x, y, z) workgroups.
+    ///
+    /// `bind_groups` are bound in order starting at group 0.
+    #[allow(dead_code)]
+    pub fn dispatch_compute(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::ComputePipeline,
+        bind_groups: &[&wgpu::BindGroup],
+        workgroups: [u32; 3],
+    ) {
+        let label = if self.gpu_labels {
+            frame_label(self.frame_index, "Compute Pass")
+        } else {
+            "Compute Pass".to_string()
+        };
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some(&label),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(pipeline);
+        for (index, bind_group) in bind_groups.iter().enumerate() {
+            compute_pass.set_bind_group(index as u32, *bind_group, &[]);
+        }
+        let [x, y, z] = workgroups;
+        compute_pass.dispatch_workgroups(x, y, z);
+    }
+
+    /// Renders the depth of every visible object with a shadow pipeline
+    /// variant into the shadow map, as seen from the light.
+    fn draw_shadow_map(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        shadow_pass: &ShadowPass,
+        scene: &dyn Scene,
+        resource_manager: &ResourceManager,
+        label: &str,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: shadow_pass.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_bind_group(0, shadow_pass.bind_group.as_ref(), &[]);
+
+        for object in pass_objects(scene.get_render_objects(), LAYER_ALL) {
+            let (Some(pipeline), Some(mesh), Some(model_bind_group)) = (
+                resource_manager.get_shadow_pipeline(&object.pipeline_id),
+                resource_manager.get_mesh(&object.mesh_id),
+                &object.model_bind_group,
+            ) else {
+                continue;
+            };
+            render_pass.set_pipeline(&pipeline);
+            render_pass.set_bind_group(1, model_bind_group.as_ref(), &[]);
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+
+            if let Some(index_buffer) = &mesh.index_buffer {
+                render_pass.set_index_buffer(index_buffer.slice(..), mesh.index_format);
+                render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+            } else {
+                render_pass.draw(0..mesh.vertex_count, 0..1);
+            }
+        }
+    }
+
+    /// Culls and batches the objects one view draws. Returns the draw list
+    /// and the number of objects outside the view's frustum.
+    fn prepare_view<'a>(
+        &self,
+        view: &SceneView<'a>,
+        scene: &'a dyn Scene,
+        resource_manager: &ResourceManager,
+        layer_mask: u32,
+        target_size: wgpu::Extent3d,
+    ) -> (ViewDraws<'a>, usize) {
+        // 視錐台の外にあるオブジェクトは描かない
+        let frustum = Frustum::from_view_proj(view.view_proj);
+        let mut culled_objects = 0;
+        let visible_objects =
+            pass_objects(scene.get_render_objects(), layer_mask).filter(|object| {
+                let inside = in_frustum(&frustum, object, resource_manager);
+                if !inside {
+                    culled_objects += 1;
+                }
+                inside
+            });
+
+        // 半透明オブジェクトは不透明なものの後に奥から順に描く
+        let (mut translucent_objects, opaque_objects): (Vec<_>, Vec<_>) =
+            visible_objects.partition(|object| is_translucent(object));
+        sort_back_to_front(&mut translucent_objects, view.eye);
+
+        // インスタンス版パイプラインがあり、テクスチャを持たないオブジェクトはまとめて描く
+        let (batches, instances, mut objects) =
+            batch_instances(opaque_objects.into_iter(), |object| {
+                object.material_bind_group.is_none()
+                    && resource_manager
+                        .get_instanced_pipeline(&object.pipeline_id, object.transform.is_mirrored())
+                        .is_some()
+            });
+        objects.extend(translucent_objects);
+
+        // 1フレームに複数パスを描くことがあるので、書き換えずパスごとに作る
+        let instance_buffer = (!instances.is_empty()).then(|| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Instance Buffer"),
+                    contents: bytemuck::cast_slice(&instances),
+                    usage: wgpu::BufferUsages::VERTEX,
+                })
+        });
+
+        let draws = ViewDraws {
+            viewport: view
+                .viewport
+                .pixel_rect(target_size.width, target_size.height),
+            camera_bind_group: view.camera_bind_group,
+            batches,
+            instance_buffer,
+            objects,
+        };
+        (draws, culled_objects)
+    }
+
+    /// Draws one view into its viewport with its camera bind group.
+    fn draw_view(
+        &self,
+        render_pass: &mut wgpu::RenderPass,
+        draws: &ViewDraws,
+        scene: &dyn Scene,
+        resource_manager: &ResourceManager,
+        layer_mask: u32,
+    ) {
+        let [x, y, width, height] = draws.viewport;
+        render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        render_pass.set_bind_group(0, draws.camera_bind_group.as_ref(), &[]);
+        if let Some(light_bind_group) = scene.get_light_bind_group() {
+            render_pass.set_bind_group(2, light_bind_group.as_ref(), &[]);
+        }
+
+        // 目盛りグリッドはメインパスでのみ、オブジェクトより先に描く
+        // （深度バッファがあるのはTAA有効時のみで、それ以外は後から描くオブジェクトが上書きする）
+        if let Some((pipeline_id, mesh_id)) = scene.grid_overlay()
+            && layer_mask == LAYER_ALL
+            && let (Some(pipeline), Some(mesh)) = (
+                resource_manager.get_pipeline(&pipeline_id),
+                resource_manager.get_mesh(&mesh_id),
+            )
+        {
+            render_pass.set_pipeline(&pipeline);
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            render_pass.draw(0..mesh.vertex_count, 0..1);
+        }
+
+        if let Some(instance_buffer) = &draws.instance_buffer {
+            for batch in &draws.batches {
+                let (Some(pipeline), Some(mesh)) = (
+                    resource_manager.get_instanced_pipeline(&batch.pipeline_id, batch.mirrored),
+                    resource_manager.get_mesh(&batch.mesh_id),
+                ) else {
                     continue;
+                };
+                if self.gpu_labels {
+                    render_pass.push_debug_group(&format!("Instances x{}", batch.instances.len()));
                 }
 
-                if let (Some(pipeline), Some(mesh)) = (
-                    resource_manager.get_pipeline(&object.pipeline_id),
-                    resource_manager.get_mesh(&object.mesh_id),
-                ) {
-                    render_pass.set_pipeline(&pipeline);
-
-                    if let Some(model_bind_group) = &object.model_bind_group {
-                        render_pass.set_bind_group(1, model_bind_group.as_ref(), &[]);
-                    }
-
-                    render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
-
-                    if let Some(index_buffer) = &mesh.index_buffer {
-                        render_pass
-                            .set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-                        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
-                    } else {
-                        render_pass.draw(0..mesh.vertex_count, 0..1);
-                    }
+                render_pass.set_pipeline(&pipeline);
+                // インスタンス版のシェーダーではライトが group 1
+                if let Some(light_bind_group) = scene.get_light_bind_group() {
+                    render_pass.set_bind_group(1, light_bind_group.as_ref(), &[]);
+                }
+                render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+
+                if let Some(index_buffer) = &mesh.index_buffer {
+                    render_pass.set_index_buffer(index_buffer.slice(..), mesh.index_format);
+                    render_pass.draw_indexed(0..mesh.index_count, 0, batch.instances.clone());
+                } else {
+                    render_pass.draw(0..mesh.vertex_count, batch.instances.clone());
+                }
+
+                if self.gpu_labels {
+                    render_pass.pop_debug_group();
                 }
             }
         }
 
-        Ok(encoder.finish())
+        for object in &draws.objects {
+            // 鏡映されたオブジェクトは巻き順を反転したパイプラインで描画する
+            let pipeline = if object.transform.is_mirrored() {
+                resource_manager.get_mirrored_pipeline(&object.pipeline_id)
+            } else {
+                resource_manager.get_pipeline(&object.pipeline_id)
+            };
+
+            if let (Some(pipeline), Some(mesh)) =
+                (pipeline, resource_manager.get_mesh(&object.mesh_id))
+            {
+                if self.gpu_labels {
+                    render_pass.push_debug_group(&format!("Object {}", object.id.as_u32()));
+                }
+
+                render_pass.set_pipeline(&pipeline);
+
+                if let Some(model_bind_group) = &object.model_bind_group {
+                    render_pass.set_bind_group(1, model_bind_group.as_ref(), &[]);
+                }
+                if let Some(material_bind_group) = &object.material_bind_group {
+                    render_pass.set_bind_group(3, material_bind_group.as_ref(), &[]);
+                }
+
+                render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+
+                if let Some(index_buffer) = &mesh.index_buffer {
+                    render_pass.set_index_buffer(index_buffer.slice(..), mesh.index_format);
+                    render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+                } else {
+                    render_pass.draw(0..mesh.vertex_count, 0..1);
+                }
+
+                if self.gpu_labels {
+                    render_pass.pop_debug_group();
+                }
+            }
+        }
     }
 
     fn create_render_pass<'a>(
-        &self,
+        &'a self,
         encoder: &'a mut wgpu::CommandEncoder,
         view: &'a wgpu::TextureView,
+        label: &str,
     ) -> wgpu::RenderPass<'a> {
+        // TAA時はオフスクリーンに描画し後段で解決する
+        // MSAA時はマルチサンプルテクスチャに描画し、出力ビューへ解決する
+        let (view, resolve_target, store) = match (&self.taa, &self.msaa) {
+            (Some(taa), _) => (taa.scene_view(), None, wgpu::StoreOp::Store),
+            (None, Some(msaa)) => (&msaa.view, Some(view), wgpu::StoreOp::Discard),
+            (None, None) => (view, None, wgpu::StoreOp::Store),
+        };
+        let depth_stencil_attachment =
+            self.taa
+                .as_ref()
+                .map(|taa| wgpu::RenderPassDepthStencilAttachment {
+                    view: taa.depth_view(),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                });
+
         encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Render Pass"),
+            label: Some(label),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view,
-                resolve_target: None,
+                resolve_target,
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Clear(wgpu::Color {
                         r: self.clear_color[0] as f64,
@@ -82,13 +802,366 @@ impl Renderer {
                         b: self.clear_color[2] as f64,
                         a: self.clear_color[3] as f64,
                     }),
-                    store: wgpu::StoreOp::Store,
+                    store,
                 },
                 depth_slice: None,
             })],
-            depth_stencil_attachment: None,
+            depth_stencil_attachment,
             occlusion_query_set: None,
             timestamp_writes: None,
         })
     }
 }
+
+/// Returns the visible objects drawn by a pass with the given layer mask.
+fn pass_objects(objects: &[RenderObject], layer_mask: u32) -> impl Iterator<Item = &RenderObject> {
+    objects
+        .iter()
+        .filter(move |object| object.visible && object.in_layers(layer_mask))
+}
+
+/// Returns `false` if the object's bounding sphere (mesh radius scaled by the
+/// largest transform scale) lies fully outside `frustum`.
+///
+/// Objects whose mesh is missing are kept; drawing skips them anyway.
+fn in_frustum(
+    frustum: &Frustum,
+    object: &RenderObject,
+    resource_manager: &ResourceManager,
+) -> bool {
+    let Some(mesh) = resource_manager.get_mesh(&object.mesh_id) else {
+        return true;
+    };
+    let radius = mesh.bounding_radius * object.transform.scale.abs().max_element();
+    frustum.intersects_sphere(object.transform.position, radius)
+}
+
+/// Material alpha below which an object is blended instead of drawn as opaque.
+const OPAQUE_ALPHA: f32 = 0.999;
+
+/// Returns `true` if the object's material is see-through and must be drawn
+/// after opaque objects, sorted by distance.
+fn is_translucent(object: &RenderObject) -> bool {
+    object.material.color[3] < OPAQUE_ALPHA
+}
+
+/// Sorts objects farthest-first from `eye` by their world position, so alpha
+/// blending composites them correctly.
+fn sort_back_to_front(objects: &mut [&RenderObject], eye: glam::Vec3) {
+    objects.sort_by(|a, b| {
+        let distance_a = a.transform.position.distance_squared(eye);
+        let distance_b = b.transform.position.distance_squared(eye);
+        distance_b.total_cmp(&distance_a)
+    });
+}
+
+/// Everything one view draws: its pixel viewport, camera and culled objects.
+struct ViewDraws<'a> {
+    viewport: [f32; 4],
+    camera_bind_group: &'a Arc<wgpu::BindGroup>,
+    batches: Vec<InstanceBatch>,
+    instance_buffer: Option<wgpu::Buffer>,
+    /// Objects drawn one by one: opaque ones first, then translucent ones
+    /// back to front.
+    objects: Vec<&'a RenderObject>,
+}
+
+/// Objects sharing a mesh and an instanced pipeline, drawn with one call.
+#[derive(Debug, PartialEq)]
+struct InstanceBatch {
+    pipeline_id: ResourceId,
+    mesh_id: ResourceId,
+    mirrored: bool,
+    /// Entries of the batch in the pass's instance buffer.
+    instances: Range<u32>,
+}
+
+/// Groups the objects accepted by `is_instanced` by pipeline, mesh and
+/// winding, returning the batches, their packed instance data and the
+/// remaining objects to draw one by one.
+///
+/// Batches are ordered by the first object of each group.
+fn batch_instances<'a>(
+    objects: impl Iterator<Item = &'a RenderObject>,
+    is_instanced: impl Fn(&RenderObject) -> bool,
+) -> (Vec<InstanceBatch>, Vec<InstanceData>, Vec<&'a RenderObject>) {
+    let mut groups: Vec<((ResourceId, ResourceId, bool), Vec<InstanceData>)> = Vec::new();
+    let mut group_indices = HashMap::new();
+    let mut single_objects = Vec::new();
+
+    for object in objects {
+        if !is_instanced(object) {
+            single_objects.push(object);
+            continue;
+        }
+        let key = (
+            object.pipeline_id,
+            object.mesh_id,
+            object.transform.is_mirrored(),
+        );
+        let index = *group_indices.entry(key).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[index].1.push(object.instance_data());
+    }
+
+    let mut batches = Vec::with_capacity(groups.len());
+    let mut instances = Vec::new();
+    for ((pipeline_id, mesh_id, mirrored), group) in groups {
+        let start = instances.len() as u32;
+        instances.extend(group);
+        batches.push(InstanceBatch {
+            pipeline_id,
+            mesh_id,
+            mirrored,
+            instances: start..instances.len() as u32,
+        });
+    }
+    (batches, instances, single_objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::config::AppConfig,
+        graphics::{context::GpuContext, screenshot},
+        resources::manager::ResourceId,
+        scene::{demo_scene::DemoScene, render_object::LAYER_DEFAULT},
+    };
+
+    const LAYER_GLOW: u32 = 1 << 1;
+
+    fn create_object(layer_mask: u32) -> RenderObject {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_layer_mask(layer_mask);
+        object
+    }
+
+    #[test]
+    fn test_pass_draws_only_matching_layers() {
+        let objects = vec![
+            create_object(LAYER_DEFAULT),
+            create_object(LAYER_GLOW),
+            create_object(LAYER_DEFAULT | LAYER_GLOW),
+        ];
+
+        let glow_pass: Vec<_> = pass_objects(&objects, LAYER_GLOW).map(|o| o.id).collect();
+        assert_eq!(glow_pass, vec![objects[1].id, objects[2].id]);
+
+        let default_pass: Vec<_> = pass_objects(&objects, LAYER_DEFAULT)
+            .map(|o| o.id)
+            .collect();
+        assert_eq!(default_pass, vec![objects[0].id, objects[2].id]);
+    }
+
+    #[test]
+    fn test_main_pass_draws_all_layers() {
+        let objects = vec![create_object(LAYER_DEFAULT), create_object(LAYER_GLOW)];
+
+        assert_eq!(pass_objects(&objects, LAYER_ALL).count(), 2);
+    }
+
+    #[test]
+    fn test_pass_skips_hidden_and_layerless_objects() {
+        let mut hidden = create_object(LAYER_GLOW);
+        hidden.set_visible(false);
+        let objects = vec![hidden, create_object(0)];
+
+        assert_eq!(pass_objects(&objects, LAYER_ALL).count(), 0);
+    }
+
+    #[test]
+    fn test_batches_group_objects_by_mesh_and_pipeline() {
+        let cube = ResourceId::new("cube_mesh");
+        let quad = ResourceId::new("quad_mesh");
+        let pipeline = ResourceId::new("pipeline");
+        let create = |mesh_id| RenderObject::new(mesh_id, pipeline);
+        let mut mirrored = create(cube).with_transform(
+            crate::scene::transform::Transform::new().with_scale(glam::vec3(-1.0, 1.0, 1.0)),
+        );
+        mirrored.set_color([0.0, 1.0, 0.0, 1.0]);
+        let textured = create(quad);
+        let objects = [create(cube), create(quad), create(cube), mirrored, textured];
+        let textured_id = objects[4].id;
+
+        let (batches, instances, singles) =
+            batch_instances(objects.iter(), |object| object.id != textured_id);
+
+        assert_eq!(
+            batches,
+            vec![
+                InstanceBatch {
+                    pipeline_id: pipeline,
+                    mesh_id: cube,
+                    mirrored: false,
+                    instances: 0..2,
+                },
+                InstanceBatch {
+                    pipeline_id: pipeline,
+                    mesh_id: quad,
+                    mirrored: false,
+                    instances: 2..3,
+                },
+                InstanceBatch {
+                    pipeline_id: pipeline,
+                    mesh_id: cube,
+                    mirrored: true,
+                    instances: 3..4,
+                },
+            ]
+        );
+        assert_eq!(instances.len(), 4);
+        assert_eq!(
+            instances[3].color,
+            [0.0, 1.0, 0.0, 1.0],
+            "色もインスタンスに入る"
+        );
+        // 対象外のオブジェクトは個別に描画する
+        assert_eq!(singles.len(), 1);
+        assert_eq!(singles[0].id, textured_id);
+    }
+
+    #[test]
+    fn test_translucent_objects_sort_back_to_front() {
+        let mut near = create_object(LAYER_DEFAULT);
+        near.set_position(glam::vec3(0.0, 0.0, 1.0));
+        let mut far = create_object(LAYER_DEFAULT);
+        far.set_position(glam::vec3(0.0, 0.0, -5.0));
+        let mut middle = create_object(LAYER_DEFAULT);
+        middle.set_position(glam::vec3(2.0, 0.0, 0.0));
+
+        let eye = glam::vec3(0.0, 0.0, 3.0);
+        let mut objects = vec![&near, &far, &middle];
+        sort_back_to_front(&mut objects, eye);
+        let ids: Vec<_> = objects.iter().map(|object| object.id).collect();
+        assert_eq!(ids, [far.id, middle.id, near.id], "遠いものから描く");
+
+        // 反対側から見ると順序が逆になる
+        sort_back_to_front(&mut objects, -eye * 3.0);
+        let ids: Vec<_> = objects.iter().map(|object| object.id).collect();
+        assert_eq!(ids, [near.id, middle.id, far.id]);
+    }
+
+    #[test]
+    fn test_only_see_through_materials_are_translucent() {
+        let mut object = create_object(LAYER_DEFAULT);
+        assert!(!is_translucent(&object), "既定のマテリアルは不透明");
+
+        object.set_color([1.0, 1.0, 1.0, 0.5]);
+        assert!(is_translucent(&object));
+    }
+
+    #[test]
+    fn test_frame_label_format() {
+        assert_eq!(frame_label(1234, "Opaque Pass"), "Frame 1234 / Opaque Pass");
+        assert_eq!(
+            frame_label(0, &pass_name(LAYER_ALL)),
+            "Frame 0 / Scene Pass"
+        );
+        assert_eq!(
+            frame_label(7, &pass_name(LAYER_GLOW)),
+            "Frame 7 / Layer Pass 0x2"
+        );
+    }
+
+    #[test]
+    fn test_render_scene_to_target_clears_to_color() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let resource_manager =
+            ResourceManager::new(context.device.clone(), context.queue.clone(), format);
+        let mut scene = DemoScene::new(1.0, Arc::new(AppConfig::default()));
+        scene.initialize(resource_manager).unwrap();
+
+        let renderer = Renderer::new(context.device.clone(), [0.0, 0.5, 1.0, 1.0]);
+        let target = RenderTarget::new(&context.device, 64, 64, format, "Test Target");
+        let command_buffer = renderer
+            .render_scene_to(&target, &scene, scene.get_resource_manager())
+            .unwrap();
+        context.queue.submit(std::iter::once(command_buffer));
+
+        let pixels =
+            screenshot::read_texture_rgba8(&context.device, &context.queue, &target.texture)
+                .unwrap();
+        assert_eq!(pixels.len(), 64 * 64 * 4);
+        // 空のシーンなので全画素がクリアカラーになる
+        for texel in pixels.chunks_exact(4) {
+            assert_eq!(texel[0], 0);
+            assert!((127..=128).contains(&texel[1]), "緑成分が約0.5であること");
+            assert_eq!(&texel[2..], &[255, 255]);
+        }
+    }
+
+    #[test]
+    fn test_dispatch_compute_increments_storage_buffer() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let device = &context.device;
+        let mut manager = ResourceManager::new(
+            device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+        let shader_id = ResourceId::new("increment_shader");
+        let pipeline_id = ResourceId::new("increment_pipeline");
+        manager
+            .create_shader(
+                shader_id,
+                include_str!("../../assets/shaders/compute/increment.wgsl"),
+                Some("Increment Shader"),
+            )
+            .unwrap();
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Increment Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline = manager
+            .create_compute_pipeline(pipeline_id, shader_id, "cs_main", &[&layout])
+            .unwrap();
+        assert!(manager.get_compute_pipeline(&pipeline_id).is_some());
+
+        // ワークグループ1つ分より多く、端数が出る要素数
+        let values: Vec<u32> = (0..100).collect();
+        let storage_id = ResourceId::new("increment_storage");
+        let storage = manager
+            .create_storage_buffer(storage_id, &values, true)
+            .unwrap();
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Increment Bind Group"),
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: storage.as_entire_binding(),
+            }],
+        });
+
+        let renderer = Renderer::new(device.clone(), [0.0; 4]);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Compute Test Encoder"),
+        });
+        let workgroups = (values.len() as u32).div_ceil(64);
+        renderer.dispatch_compute(&mut encoder, &pipeline, &[&bind_group], [workgroups, 1, 1]);
+        context.queue.submit(std::iter::once(encoder.finish()));
+
+        let bytes = manager.read_buffer(storage_id).unwrap();
+        let result: &[u32] = bytemuck::cast_slice(&bytes);
+        let expected: Vec<u32> = values.iter().map(|v| v + 1).collect();
+        assert_eq!(result, expected, "全要素が1ずつ増える");
+    }
+}
@@ -0,0 +1,342 @@
+use std::path::Path;
+
+use crate::{
+    core::error::{EngineError, EngineResult},
+    graphics::color::{linear_to_srgb, srgb_to_linear},
+};
+
+/// Color space screenshots are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Surface texels as displayed (8 bits per channel).
+    #[default]
+    Srgb,
+    /// Linear-light values for compositing in linear pipelines (16 bits per
+    /// channel so the dark range keeps its precision).
+    Linear,
+}
+
+impl ColorSpace {
+    /// Bits per channel of the image written for this color space.
+    #[allow(dead_code)]
+    pub fn bit_depth(self) -> u8 {
+        match self {
+            ColorSpace::Srgb => 8,
+            ColorSpace::Linear => 16,
+        }
+    }
+}
+
+/// Pixel data ready to be encoded, in the bit depth chosen by the color space.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenshotPixels {
+    Rgba8(Vec<u8>),
+    Rgba16(Vec<u16>),
+}
+
+impl ScreenshotPixels {
+    #[allow(dead_code)]
+    pub fn bit_depth(&self) -> u8 {
+        match self {
+            ScreenshotPixels::Rgba8(_) => 8,
+            ScreenshotPixels::Rgba16(_) => 16,
+        }
+    }
+}
+
+/// Converts tightly packed RGBA8 texels read back from a target holding
+/// `source` values into the requested color space.
+///
+/// Texels already in the requested space are passed through (sRGB) or only
+/// widened to 16 bits (linear). Otherwise the RGB channels are decoded to or
+/// encoded from linear light; alpha is never gamma-encoded, so it is only
+/// rescaled.
+pub fn convert_texels(
+    rgba8: &[u8],
+    source: ColorSpace,
+    color_space: ColorSpace,
+) -> ScreenshotPixels {
+    match (source, color_space) {
+        (ColorSpace::Srgb, ColorSpace::Srgb) => ScreenshotPixels::Rgba8(rgba8.to_vec()),
+        (ColorSpace::Linear, ColorSpace::Linear) => {
+            ScreenshotPixels::Rgba16(rgba8.iter().map(|&v| v as u16 * 257).collect())
+        }
+        (ColorSpace::Srgb, ColorSpace::Linear) => ScreenshotPixels::Rgba16(
+            rgba8
+                .chunks_exact(4)
+                .flat_map(|texel| {
+                    let to_linear16 =
+                        |v: u8| (srgb_to_linear(v as f32 / 255.0) * 65535.0).round() as u16;
+                    [
+                        to_linear16(texel[0]),
+                        to_linear16(texel[1]),
+                        to_linear16(texel[2]),
+                        texel[3] as u16 * 257,
+                    ]
+                })
+                .collect(),
+        ),
+        (ColorSpace::Linear, ColorSpace::Srgb) => ScreenshotPixels::Rgba8(
+            rgba8
+                .chunks_exact(4)
+                .flat_map(|texel| {
+                    let to_srgb8 = |v: u8| (linear_to_srgb(v as f32 / 255.0) * 255.0).round() as u8;
+                    [
+                        to_srgb8(texel[0]),
+                        to_srgb8(texel[1]),
+                        to_srgb8(texel[2]),
+                        texel[3],
+                    ]
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Row pitch of a texture-to-buffer copy: `width * 4` rounded up to
+/// `COPY_BYTES_PER_ROW_ALIGNMENT` (256 bytes).
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * 4;
+    let alignment = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(alignment) * alignment
+}
+
+/// Strips the per-row padding of a buffer copied with [`padded_bytes_per_row`].
+pub fn unpad_rows(padded: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row_bytes = (width * 4) as usize;
+    let padded_row_bytes = padded_bytes_per_row(width) as usize;
+    padded
+        .chunks(padded_row_bytes)
+        .take(height as usize)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect()
+}
+
+/// Reorders 4-byte texels of `format` into RGBA channel order.
+///
+/// # Errors
+///
+/// Returns `EngineError::Screenshot` for formats other than 8-bit RGBA/BGRA.
+pub fn texels_to_rgba8(mut texels: Vec<u8>, format: wgpu::TextureFormat) -> EngineResult<Vec<u8>> {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Ok(texels),
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => {
+            for texel in texels.chunks_exact_mut(4) {
+                texel.swap(0, 2);
+            }
+            Ok(texels)
+        }
+        _ => Err(EngineError::Screenshot(format!(
+            "Unsupported texture format for capture: {:?}",
+            format
+        ))),
+    }
+}
+
+/// Copies a 2D texture (usage must include `COPY_SRC`) back to the CPU as
+/// tightly packed RGBA8 texels. Blocks until the GPU has finished.
+///
+/// # Errors
+///
+/// Returns `EngineError::Screenshot` if the format is not 8-bit RGBA/BGRA or
+/// the readback buffer cannot be mapped.
+pub fn read_texture_rgba8(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> EngineResult<Vec<u8>> {
+    let (width, height) = (texture.width(), texture.height());
+    let bytes_per_row = padded_bytes_per_row(width);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: (bytes_per_row * height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device
+        .poll(wgpu::PollType::Wait)
+        .map_err(|e| EngineError::Screenshot(format!("Failed to wait for GPU: {}", e)))?;
+    receiver
+        .recv()
+        .map_err(|e| EngineError::Screenshot(e.to_string()))?
+        .map_err(|e| EngineError::Screenshot(format!("Failed to map buffer: {}", e)))?;
+
+    let texels = unpad_rows(&slice.get_mapped_range(), width, height);
+    buffer.unmap();
+    texels_to_rgba8(texels, texture.format())
+}
+
+/// Writes pixels as a PNG (8 or 16 bits per channel, matching `pixels`).
+///
+/// # Errors
+///
+/// Returns `EngineError::Screenshot` if the file cannot be written.
+pub fn save_png(
+    path: impl AsRef<Path>,
+    width: u32,
+    height: u32,
+    pixels: ScreenshotPixels,
+) -> EngineResult<()> {
+    let path = path.as_ref();
+    let result = match pixels {
+        ScreenshotPixels::Rgba8(data) => image::RgbaImage::from_raw(width, height, data)
+            .map(|image| image.save_with_format(path, image::ImageFormat::Png)),
+        ScreenshotPixels::Rgba16(data) => {
+            image::ImageBuffer::<image::Rgba<u16>, _>::from_raw(width, height, data)
+                .map(|image| image.save_with_format(path, image::ImageFormat::Png))
+        }
+    };
+
+    match result {
+        Some(Ok(())) => Ok(()),
+        Some(Err(e)) => Err(EngineError::Screenshot(format!(
+            "Failed to write {}: {}",
+            path.display(),
+            e
+        ))),
+        None => Err(EngineError::Screenshot(
+            "Pixel data does not match image size".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_passthrough() {
+        let texels = [10, 128, 255, 200];
+        let pixels = convert_texels(&texels, ColorSpace::Srgb, ColorSpace::Srgb);
+
+        assert_eq!(pixels, ScreenshotPixels::Rgba8(texels.to_vec()));
+    }
+
+    #[test]
+    fn test_linear_conversion_of_known_texels() {
+        let texels = [0, 255, 128, 128];
+        let ScreenshotPixels::Rgba16(pixels) =
+            convert_texels(&texels, ColorSpace::Srgb, ColorSpace::Linear)
+        else {
+            panic!("線形出力は16bitであるべき");
+        };
+
+        assert_eq!(pixels[0], 0);
+        assert_eq!(pixels[1], 65535);
+        // sRGB 128/255 ≈ 0.502 → 線形 ≈ 0.2158
+        let mid = pixels[2] as f32 / 65535.0;
+        assert!((mid - 0.2158).abs() < 1e-3, "mid = {}", mid);
+        // アルファは線形化しない
+        assert_eq!(pixels[3], 128 * 257);
+    }
+
+    #[test]
+    fn test_linear_texels_are_encoded_or_widened() {
+        // 非sRGBのターゲットにはシェーダーの線形値がそのまま書かれている
+        let texels = [0, 255, 55, 128];
+        let pixels = convert_texels(&texels, ColorSpace::Linear, ColorSpace::Srgb);
+        let ScreenshotPixels::Rgba8(pixels) = pixels else {
+            panic!("sRGB出力は8bitであるべき");
+        };
+        // 線形 55/255 ≈ 0.2157 → sRGB ≈ 0.5
+        assert_eq!(pixels, vec![0, 255, 128, 128]);
+
+        let pixels = convert_texels(&texels, ColorSpace::Linear, ColorSpace::Linear);
+        assert_eq!(
+            pixels,
+            ScreenshotPixels::Rgba16(vec![0, 65535, 55 * 257, 128 * 257])
+        );
+    }
+
+    #[test]
+    fn test_bit_depth_matches_color_space() {
+        assert_eq!(ColorSpace::Srgb.bit_depth(), 8);
+        assert_eq!(ColorSpace::Linear.bit_depth(), 16);
+
+        let texels = [1, 2, 3, 4];
+        for color_space in [ColorSpace::Srgb, ColorSpace::Linear] {
+            assert_eq!(
+                convert_texels(&texels, ColorSpace::Srgb, color_space).bit_depth(),
+                color_space.bit_depth()
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_is_srgb() {
+        assert_eq!(ColorSpace::default(), ColorSpace::Srgb);
+    }
+
+    #[test]
+    fn test_rows_are_padded_to_256_bytes() {
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+        assert_eq!(padded_bytes_per_row(1), 256);
+    }
+
+    #[test]
+    fn test_unpad_rows_drops_padding() {
+        // 幅3ピクセル（12バイト）の行が256バイトにパディングされている
+        let mut padded = vec![0u8; 256 * 2];
+        padded[..12].copy_from_slice(&[1; 12]);
+        padded[256..268].copy_from_slice(&[2; 12]);
+
+        let texels = unpad_rows(&padded, 3, 2);
+
+        assert_eq!(texels.len(), 24);
+        assert!(texels[..12].iter().all(|&v| v == 1));
+        assert!(texels[12..].iter().all(|&v| v == 2));
+    }
+
+    #[test]
+    fn test_bgra_is_swizzled_to_rgba() {
+        let texels = texels_to_rgba8(vec![1, 2, 3, 4], wgpu::TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(texels.unwrap(), vec![3, 2, 1, 4]);
+
+        let unsupported = texels_to_rgba8(vec![0; 8], wgpu::TextureFormat::Rgba16Float);
+        assert!(matches!(unsupported, Err(EngineError::Screenshot(_))));
+    }
+
+    #[test]
+    fn test_save_png_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("frame.png");
+        let texels = vec![255, 0, 0, 255, 0, 255, 0, 255];
+
+        save_png(
+            &path,
+            2,
+            1,
+            convert_texels(&texels, ColorSpace::Srgb, ColorSpace::Srgb),
+        )
+        .unwrap();
+
+        let image = image::open(&path).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.into_raw(), texels);
+    }
+}
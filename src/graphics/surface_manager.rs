@@ -1,6 +1,6 @@
 use crate::{
     core::{
-        config::RenderingConfig,
+        config::{PresentModeConfig, RenderingConfig},
         error::{EngineError, EngineResult},
     },
     window::Window,
@@ -9,6 +9,7 @@ use crate::{
 pub struct SurfaceManager {
     surface: wgpu::Surface<'static>,
     config: wgpu::SurfaceConfiguration,
+    /// Format of the views rendered into (may be an sRGB view of `config.format`).
     format: wgpu::TextureFormat,
     caps: wgpu::SurfaceCapabilities,
 }
@@ -29,25 +30,49 @@ impl SurfaceManager {
 
         let caps = surface.get_capabilities(adapter);
 
-        let format = caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(caps.formats[0]);
+        let allow_srgb_view = render_config.srgb_view_fallback
+            && adapter
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS);
+        let (surface_format, format) = if render_config.srgb {
+            select_formats(&caps.formats, allow_srgb_view)
+        } else {
+            let format = select_linear_format(&caps.formats);
+            (format, format)
+        };
+        log::info!("Surface format: {:?}", format);
+
+        if !render_config.srgb {
+            log::info!("sRGB disabled by config; colors are encoded manually");
+        } else if !format.is_srgb() {
+            log::warn!(
+                "No sRGB surface format available, rendering to {:?}; colors are encoded manually",
+                format
+            );
+        } else if format != surface_format {
+            log::info!(
+                "Surface has no sRGB format, rendering through an sRGB view ({:?} -> {:?})",
+                surface_format,
+                format
+            );
+        }
 
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format,
+            format: surface_format,
             width: window.get_window().inner_size().width,
             height: window.get_window().inner_size().height,
-            present_mode: if render_config.vsync {
-                wgpu::PresentMode::Fifo
+            present_mode: resolve_present_mode(
+                present_mode(render_config.present_mode()),
+                &caps.present_modes,
+            ),
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: if format != surface_format {
+                vec![format]
             } else {
-                wgpu::PresentMode::Immediate
+                vec![]
             },
-            alpha_mode: wgpu::CompositeAlphaMode::Auto,
-            view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
 
@@ -76,27 +101,159 @@ impl SurfaceManager {
         self.surface.configure(device, &self.config);
     }
 
-    pub fn acquire_frame(&self) -> EngineResult<SurfaceFrame> {
-        let texture = self.surface.get_current_texture().map_err(|e| {
-            EngineError::RenderError(format!("Failed to acquire next surface texture: {}", e))
-        })?;
+    /// Switches to the configured present mode, falling back to the closest
+    /// mode the surface supports.
+    pub fn apply_present_mode(&mut self, device: &wgpu::Device, mode: PresentModeConfig) {
+        self.set_present_mode(device, present_mode(mode));
+    }
+
+    /// Reconfigures the surface with `mode`, falling back to the closest mode
+    /// the surface supports. Returns the mode actually in use.
+    pub fn set_present_mode(
+        &mut self,
+        device: &wgpu::Device,
+        mode: wgpu::PresentMode,
+    ) -> wgpu::PresentMode {
+        let resolved = resolve_present_mode(mode, &self.caps.present_modes);
+        if resolved != mode {
+            log::warn!(
+                "Present mode {:?} not supported, falling back to {:?}",
+                mode,
+                resolved
+            );
+        }
+        if self.config.present_mode != resolved {
+            self.config.present_mode = resolved;
+            self.surface.configure(device, &self.config);
+            log::info!("Present mode set to {:?}", resolved);
+        }
+        resolved
+    }
+
+    /// Acquires the next frame to render into.
+    ///
+    /// A lost or outdated surface (GPU reset, monitor change) is reconfigured
+    /// with the stored configuration and acquired once more.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::OutOfMemory`, which is fatal, if the GPU ran out
+    /// of memory, and `EngineError::RenderError` for any other failure,
+    /// including a surface still lost after reconfiguring.
+    pub fn acquire_frame(&mut self, device: &wgpu::Device) -> EngineResult<SurfaceFrame> {
+        let texture = acquire_with_retry(
+            || self.surface.get_current_texture(),
+            || {
+                log::warn!("Surface lost or outdated, reconfiguring");
+                self.surface.configure(device, &self.config);
+            },
+        )?;
 
-        let view = texture
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let view = texture.texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(self.format),
+            ..Default::default()
+        });
 
         Ok(SurfaceFrame { texture, view })
     }
 
+    /// Format of the frame views; pipelines must target this format.
     pub fn format(&self) -> wgpu::TextureFormat {
         self.format
     }
 
+    /// Returns `true` if rendering goes through an sRGB-encoding view.
+    #[allow(dead_code)]
+    pub fn is_srgb(&self) -> bool {
+        self.format.is_srgb()
+    }
+
     pub fn config(&self) -> &wgpu::SurfaceConfiguration {
         &self.config
     }
 }
 
+/// Calls `acquire`, running `reconfigure` and retrying once if the surface
+/// was lost or outdated.
+fn acquire_with_retry<T>(
+    mut acquire: impl FnMut() -> Result<T, wgpu::SurfaceError>,
+    reconfigure: impl FnOnce(),
+) -> EngineResult<T> {
+    let result = match acquire() {
+        Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+            reconfigure();
+            acquire()
+        }
+        result => result,
+    };
+
+    Ok(result?)
+}
+
+fn present_mode(mode: PresentModeConfig) -> wgpu::PresentMode {
+    match mode {
+        PresentModeConfig::Vsync => wgpu::PresentMode::Fifo,
+        PresentModeConfig::Immediate => wgpu::PresentMode::Immediate,
+        PresentModeConfig::Mailbox => wgpu::PresentMode::Mailbox,
+    }
+}
+
+/// Picks `requested` if the surface supports it, otherwise the closest
+/// supported mode: `Immediate` prefers `Mailbox` before `Fifo`, which every
+/// surface supports. `Mailbox` falls back to `Fifo` directly so it never
+/// tears. The `Auto*` modes are resolved by wgpu itself.
+fn resolve_present_mode(
+    requested: wgpu::PresentMode,
+    supported: &[wgpu::PresentMode],
+) -> wgpu::PresentMode {
+    use wgpu::PresentMode;
+
+    let fallbacks: &[PresentMode] = match requested {
+        PresentMode::AutoVsync | PresentMode::AutoNoVsync => return requested,
+        PresentMode::Immediate => &[PresentMode::Immediate, PresentMode::Mailbox],
+        PresentMode::Mailbox => &[PresentMode::Mailbox],
+        PresentMode::FifoRelaxed => &[PresentMode::FifoRelaxed],
+        PresentMode::Fifo => &[],
+    };
+    fallbacks
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(PresentMode::Fifo)
+}
+
+/// Chooses the surface format and the format frames are rendered through.
+///
+/// Prefers a native sRGB format. Otherwise falls back to the first supported
+/// format and, when `allow_srgb_view` is set and an sRGB variant exists,
+/// renders through an sRGB view of it.
+fn select_formats(
+    formats: &[wgpu::TextureFormat],
+    allow_srgb_view: bool,
+) -> (wgpu::TextureFormat, wgpu::TextureFormat) {
+    if let Some(format) = formats.iter().copied().find(|f| f.is_srgb()) {
+        return (format, format);
+    }
+
+    let surface_format = formats[0];
+    let view_format = if allow_srgb_view {
+        surface_format.add_srgb_suffix()
+    } else {
+        surface_format
+    };
+    (surface_format, view_format)
+}
+
+/// Chooses the first non-sRGB format, or the first format if every
+/// supported format is sRGB.
+fn select_linear_format(formats: &[wgpu::TextureFormat]) -> wgpu::TextureFormat {
+    formats
+        .iter()
+        .copied()
+        .find(|format| !format.is_srgb())
+        .unwrap_or(formats[0])
+}
+
 pub struct SurfaceFrame {
     pub texture: wgpu::SurfaceTexture,
     pub view: wgpu::TextureView,
@@ -107,3 +264,150 @@ impl SurfaceFrame {
         self.texture.present();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::{PresentMode, TextureFormat};
+
+    #[test]
+    fn test_lost_surface_is_reconfigured_and_retried() {
+        for error in [wgpu::SurfaceError::Lost, wgpu::SurfaceError::Outdated] {
+            let mut results = vec![Ok(1), Err(error)];
+            let mut reconfigured = false;
+
+            let result = acquire_with_retry(|| results.pop().unwrap(), || reconfigured = true);
+
+            assert_eq!(result.ok(), Some(1), "再設定後の取得結果を返す");
+            assert!(reconfigured);
+        }
+    }
+
+    #[test]
+    fn test_surface_still_lost_after_retry_is_an_error() {
+        let mut attempts = 0;
+        let result: EngineResult<()> = acquire_with_retry(
+            || {
+                attempts += 1;
+                Err(wgpu::SurfaceError::Lost)
+            },
+            || {},
+        );
+
+        assert!(matches!(result, Err(EngineError::RenderError(_))));
+        assert_eq!(attempts, 2, "再試行は1回だけ");
+    }
+
+    #[test]
+    fn test_out_of_memory_is_fatal_without_retry() {
+        let mut reconfigured = false;
+        let result: EngineResult<()> = acquire_with_retry(
+            || Err(wgpu::SurfaceError::OutOfMemory),
+            || reconfigured = true,
+        );
+
+        assert!(matches!(result, Err(EngineError::OutOfMemory(_))));
+        assert!(!reconfigured);
+    }
+
+    #[test]
+    fn test_prefers_native_srgb_format() {
+        let formats = [TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb];
+        assert_eq!(
+            select_formats(&formats, true),
+            (TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8UnormSrgb)
+        );
+    }
+
+    #[test]
+    fn test_srgb_view_over_non_srgb_surface() {
+        let formats = [TextureFormat::Rgba8Unorm];
+        assert_eq!(
+            select_formats(&formats, true),
+            (TextureFormat::Rgba8Unorm, TextureFormat::Rgba8UnormSrgb)
+        );
+    }
+
+    #[test]
+    fn test_non_srgb_fallback_without_view_support() {
+        let formats = [TextureFormat::Rgba8Unorm];
+        let (_, view_format) = select_formats(&formats, false);
+
+        assert!(!view_format.is_srgb());
+        // sRGBサーフェスがない場合はクリアカラーを手動で変換する
+        let color =
+            crate::graphics::color::clear_color_for_format([0.5, 0.5, 0.5, 1.0], view_format);
+        assert!(color[0] > 0.5);
+    }
+
+    #[test]
+    fn test_format_without_srgb_variant_stays_linear() {
+        let formats = [TextureFormat::Rgba16Float];
+        assert_eq!(
+            select_formats(&formats, true),
+            (TextureFormat::Rgba16Float, TextureFormat::Rgba16Float)
+        );
+    }
+
+    #[test]
+    fn test_linear_format_skips_srgb() {
+        let formats = [TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8Unorm];
+        assert_eq!(select_linear_format(&formats), TextureFormat::Bgra8Unorm);
+
+        // sRGB しかなければそれを使う
+        let srgb_only = [TextureFormat::Rgba8UnormSrgb];
+        assert_eq!(
+            select_linear_format(&srgb_only),
+            TextureFormat::Rgba8UnormSrgb
+        );
+    }
+
+    #[test]
+    fn test_supported_present_mode_is_kept() {
+        let supported = [PresentMode::Fifo, PresentMode::Immediate];
+        assert_eq!(
+            resolve_present_mode(PresentMode::Immediate, &supported),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::Fifo, &supported),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn test_unsupported_present_mode_falls_back() {
+        // Immediate 非対応でも Mailbox があればティアリングなしで上限なしにできる
+        let supported = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(
+            resolve_present_mode(PresentMode::Immediate, &supported),
+            PresentMode::Mailbox
+        );
+
+        let fifo_only = [PresentMode::Fifo];
+        assert_eq!(
+            resolve_present_mode(PresentMode::Immediate, &fifo_only),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::FifoRelaxed, &fifo_only),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn test_unsupported_mailbox_falls_back_to_fifo() {
+        // ティアリングを避けるため Immediate には落とさない
+        let supported = [PresentMode::Fifo, PresentMode::Immediate];
+        assert_eq!(
+            resolve_present_mode(present_mode(PresentModeConfig::Mailbox), &supported),
+            PresentMode::Fifo
+        );
+
+        let with_mailbox = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(
+            resolve_present_mode(present_mode(PresentModeConfig::Mailbox), &with_mailbox),
+            PresentMode::Mailbox
+        );
+    }
+}
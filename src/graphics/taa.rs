@@ -0,0 +1,472 @@
+use std::sync::Arc;
+
+use wgpu::util::DeviceExt;
+
+/// Weight of the current frame when blending into the history.
+const CURRENT_FRAME_WEIGHT: f32 = 0.1;
+/// Number of jitter positions before the Halton sequence repeats.
+const JITTER_SEQUENCE_LENGTH: u64 = 8;
+/// Depth format of the scene pass when TAA is enabled.
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Returns element `index` of the Halton low-discrepancy sequence in `base`.
+///
+/// Index 0 maps to 0; the sequence is well distributed in `[0, 1)` from
+/// index 1 onwards.
+pub fn halton(mut index: u32, base: u32) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Subpixel projection offset for `frame_index`, in NDC units.
+///
+/// Uses the Halton (2, 3) sequence so successive frames sample different
+/// positions within a pixel of a `width` x `height` target.
+pub fn jitter_offset(frame_index: u64, width: u32, height: u32) -> glam::Vec2 {
+    let index = (frame_index % JITTER_SEQUENCE_LENGTH) as u32 + 1;
+    let pixel_offset = glam::vec2(halton(index, 2) - 0.5, halton(index, 3) - 0.5);
+    pixel_offset * 2.0 / glam::vec2(width.max(1) as f32, height.max(1) as f32)
+}
+
+/// Applies an NDC-space `jitter` to a projection (or view-projection) matrix.
+pub fn jittered_projection(projection: glam::Mat4, jitter: glam::Vec2) -> glam::Mat4 {
+    glam::Mat4::from_translation(jitter.extend(0.0)) * projection
+}
+
+/// Matrix mapping current-frame clip space to previous-frame clip space.
+///
+/// Both matrices are unjittered view-projections; a point reconstructed from
+/// the current depth buffer is transformed back to world space and then into
+/// the previous frame.
+pub fn reprojection_matrix(
+    previous_view_proj: glam::Mat4,
+    current_view_proj: glam::Mat4,
+) -> glam::Mat4 {
+    previous_view_proj * current_view_proj.inverse()
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct TaaUniform {
+    reprojection: [[f32; 4]; 4],
+    jitter: [f32; 2],
+    blend: f32,
+    _padding: f32,
+}
+
+/// Size-dependent textures of the TAA pass.
+struct TaaTargets {
+    width: u32,
+    height: u32,
+    scene_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    history_views: [wgpu::TextureView; 2],
+    /// `bind_groups[i]` reads `history_views[i]`.
+    bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// Temporal anti-aliasing: the scene is rendered with a jittered projection
+/// into an off-screen target, then resolved against a reprojected,
+/// neighborhood-clamped history into the output view.
+pub struct TemporalAntiAliasing {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    format: wgpu::TextureFormat,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    targets: TaaTargets,
+    /// History slot read this frame; the other one is written.
+    history_index: usize,
+    history_valid: bool,
+    previous_view_proj: Option<glam::Mat4>,
+}
+
+impl TemporalAntiAliasing {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("TAA Resolve Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../assets/shaders/taa/resolve.wgsl").into(),
+            ),
+        });
+
+        let texture_entry =
+            |binding: u32, sample_type: wgpu::TextureSampleType| wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("TAA Bind Group Layout"),
+            entries: &[
+                texture_entry(0, wgpu::TextureSampleType::Float { filterable: true }),
+                texture_entry(1, wgpu::TextureSampleType::Float { filterable: true }),
+                texture_entry(2, wgpu::TextureSampleType::Float { filterable: false }),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("TAA Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let color_target = Some(wgpu::ColorTargetState {
+            format,
+            blend: None,
+            write_mask: wgpu::ColorWrites::ALL,
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("TAA Resolve Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[color_target.clone(), color_target],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("TAA History Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("TAA Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[TaaUniform {
+                reprojection: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                jitter: [0.0; 2],
+                blend: 1.0,
+                _padding: 0.0,
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let targets = Self::create_targets(
+            &device,
+            &bind_group_layout,
+            &sampler,
+            &uniform_buffer,
+            format,
+            width,
+            height,
+        );
+
+        Self {
+            device,
+            queue,
+            format,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            uniform_buffer,
+            targets,
+            history_index: 0,
+            history_valid: false,
+            previous_view_proj: None,
+        }
+    }
+
+    fn create_targets(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        uniform_buffer: &wgpu::Buffer,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> TaaTargets {
+        let width = width.max(1);
+        let height = height.max(1);
+        let create_view = |label: &str, format: wgpu::TextureFormat| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+
+        let scene_view = create_view("TAA Scene Color", format);
+        let depth_view = create_view("TAA Scene Depth", DEPTH_FORMAT);
+        let history_views = [
+            create_view("TAA History 0", format),
+            create_view("TAA History 1", format),
+        ];
+
+        let bind_groups = std::array::from_fn(|i| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("TAA Bind Group"),
+                layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&scene_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&history_views[i]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&depth_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                ],
+            })
+        });
+
+        TaaTargets {
+            width,
+            height,
+            scene_view,
+            depth_view,
+            history_views,
+            bind_groups,
+        }
+    }
+
+    /// Recreates the size-dependent targets and discards the history.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.targets = Self::create_targets(
+            &self.device,
+            &self.bind_group_layout,
+            &self.sampler,
+            &self.uniform_buffer,
+            self.format,
+            width,
+            height,
+        );
+        self.history_valid = false;
+        self.previous_view_proj = None;
+    }
+
+    /// Projection jitter to apply to the camera for `frame_index`.
+    pub fn jitter(&self, frame_index: u64) -> glam::Vec2 {
+        jitter_offset(frame_index, self.targets.width, self.targets.height)
+    }
+
+    /// Uploads the resolve parameters for the frame about to be rendered.
+    ///
+    /// `view_proj` is the unjittered camera view-projection of this frame.
+    pub fn prepare(&mut self, view_proj: glam::Mat4, jitter: glam::Vec2) {
+        let previous = self.previous_view_proj.unwrap_or(view_proj);
+        let uniform = TaaUniform {
+            reprojection: reprojection_matrix(previous, view_proj).to_cols_array_2d(),
+            jitter: jitter.to_array(),
+            // 履歴が無効なフレームは現フレームをそのまま使う
+            blend: if self.history_valid {
+                CURRENT_FRAME_WEIGHT
+            } else {
+                1.0
+            },
+            _padding: 0.0,
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+
+        // 前フレームに書いた履歴を読む
+        self.history_index = 1 - self.history_index;
+        self.history_valid = true;
+        self.previous_view_proj = Some(view_proj);
+    }
+
+    /// Color target the scene pass renders into.
+    pub fn scene_view(&self) -> &wgpu::TextureView {
+        &self.targets.scene_view
+    }
+
+    /// Depth target of the scene pass.
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.targets.depth_view
+    }
+
+    /// Resolves the scene target against the history into `output_view`.
+    pub fn resolve(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        output_view: &wgpu::TextureView,
+        label: &str,
+    ) {
+        let read = self.history_index;
+        let write = 1 - read;
+
+        let color_attachment = |view| {
+            Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })
+        };
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[
+                color_attachment(output_view),
+                color_attachment(&self.targets.history_views[write]),
+            ],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.targets.bind_groups[read], &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halton_sequence_base_2_and_3() {
+        let base2: Vec<f32> = (1..=4).map(|i| halton(i, 2)).collect();
+        assert_eq!(base2, vec![0.5, 0.25, 0.75, 0.125]);
+
+        let base3: Vec<f32> = (1..=4).map(|i| halton(i, 3)).collect();
+        let expected = [1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0];
+        for (actual, expected) in base3.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+        assert_eq!(halton(0, 2), 0.0);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_a_pixel_and_repeats() {
+        let (width, height) = (800, 600);
+        let pixel = glam::vec2(2.0 / width as f32, 2.0 / height as f32);
+
+        for frame in 0..JITTER_SEQUENCE_LENGTH {
+            let jitter = jitter_offset(frame, width, height);
+            // 半ピクセル以内に収まる
+            assert!(jitter.x.abs() <= pixel.x * 0.5 && jitter.y.abs() <= pixel.y * 0.5);
+        }
+        assert_eq!(
+            jitter_offset(3, width, height),
+            jitter_offset(3 + JITTER_SEQUENCE_LENGTH, width, height)
+        );
+        assert_ne!(
+            jitter_offset(0, width, height),
+            jitter_offset(1, width, height)
+        );
+    }
+
+    #[test]
+    fn test_jittered_projection_shifts_ndc() {
+        let projection = glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 1.0, 0.1, 100.0);
+        let jitter = glam::vec2(0.01, -0.02);
+        let point = glam::vec3(0.3, 0.2, -5.0);
+
+        let ndc = projection.project_point3(point);
+        let jittered_ndc = jittered_projection(projection, jitter).project_point3(point);
+
+        assert!((jittered_ndc - ndc - jitter.extend(0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_reprojection_between_frames() {
+        let projection = glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 1.0, 0.1, 100.0);
+        let previous_view =
+            glam::Mat4::look_at_rh(glam::vec3(0.0, 0.0, 3.0), glam::Vec3::ZERO, glam::Vec3::Y);
+        let current_view =
+            glam::Mat4::look_at_rh(glam::vec3(0.5, 0.2, 3.0), glam::Vec3::ZERO, glam::Vec3::Y);
+        let previous = projection * previous_view;
+        let current = projection * current_view;
+
+        let reprojection = reprojection_matrix(previous, current);
+
+        // 現フレームのNDCから前フレームのNDCへ正しく写る
+        let world = glam::vec3(0.4, -0.3, 0.5);
+        let current_ndc = current.project_point3(world);
+        let reprojected = reprojection.project_point3(current_ndc);
+        let expected = previous.project_point3(world);
+        assert!(
+            (reprojected - expected).length() < 1e-4,
+            "{:?} != {:?}",
+            reprojected,
+            expected
+        );
+
+        // カメラが動いていなければ恒等変換
+        assert!(reprojection_matrix(current, current).abs_diff_eq(glam::Mat4::IDENTITY, 1e-5));
+    }
+}
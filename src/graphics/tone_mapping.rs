@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+/// Floating-point format the scene is rendered in while tone mapping is on,
+/// so values above 1.0 survive until the tone-mapping pass.
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Gamma the pass encodes with on non-sRGB outputs; sRGB outputs are encoded
+/// in hardware.
+const DISPLAY_GAMMA: f32 = 2.2;
+
+/// Applies `exposure` and Reinhard tone mapping to a linear channel value,
+/// mapping `0..inf` to `0..1`. Mirrors the shader, without the gamma step.
+#[allow(dead_code)]
+pub fn reinhard(value: f32, exposure: f32) -> f32 {
+    let exposed = value.max(0.0) * exposure;
+    exposed / (1.0 + exposed)
+}
+
+/// Gamma the pass encodes with when writing to `format`.
+fn output_gamma(format: wgpu::TextureFormat) -> f32 {
+    if format.is_srgb() { 1.0 } else { DISPLAY_GAMMA }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct ToneMappingUniform {
+    exposure: f32,
+    gamma: f32,
+    _padding: [f32; 2],
+}
+
+/// Final full-screen pass: the scene is rendered into an HDR target at the
+/// output size, then exposed, tone mapped and gamma encoded into the output
+/// view.
+pub struct ToneMapping {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    uniform: ToneMappingUniform,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+impl ToneMapping {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        output_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        exposure: f32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tone Mapping Shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../assets/shaders/tonemap/reinhard.wgsl").into(),
+            ),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Tone Mapping Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Tone Mapping Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tone Mapping Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Tone Mapping Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform = ToneMappingUniform {
+            exposure,
+            gamma: output_gamma(output_format),
+            _padding: [0.0; 2],
+        };
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Tone Mapping Uniform Buffer"),
+            size: std::mem::size_of::<ToneMappingUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+
+        let (view, bind_group) = Self::create_target(
+            &device,
+            &bind_group_layout,
+            &sampler,
+            &uniform_buffer,
+            (width, height),
+        );
+
+        Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            uniform_buffer,
+            uniform,
+            view,
+            bind_group,
+        }
+    }
+
+    fn create_target(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        uniform_buffer: &wgpu::Buffer,
+        (width, height): (u32, u32),
+    ) -> (wgpu::TextureView, wgpu::BindGroup) {
+        let view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("HDR Scene Color"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: HDR_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tone Mapping Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        (view, bind_group)
+    }
+
+    /// Recreates the HDR target for a new output size.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let (view, bind_group) = Self::create_target(
+            &self.device,
+            &self.bind_group_layout,
+            &self.sampler,
+            &self.uniform_buffer,
+            (width, height),
+        );
+        self.view = view;
+        self.bind_group = bind_group;
+    }
+
+    /// Sets the factor scene colors are multiplied by before tone mapping.
+    pub fn set_exposure(&mut self, exposure: f32) {
+        self.uniform.exposure = exposure;
+        self.queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[self.uniform]),
+        );
+    }
+
+    /// HDR view the scene (or the upscale pass) renders into.
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Tone maps the HDR scene color into `output_view`.
+    pub fn apply(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        output_view: &wgpu::TextureView,
+        label: &str,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reinhard_compresses_bright_values_below_one() {
+        assert_eq!(reinhard(0.0, 1.0), 0.0);
+        assert_eq!(reinhard(1.0, 1.0), 0.5);
+        // 明るい値も1に届かず、順序は保たれる
+        let values = [0.5, 2.0, 8.0, 100.0].map(|v| reinhard(v, 1.0));
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(values.iter().all(|&v| v < 1.0));
+    }
+
+    #[test]
+    fn test_exposure_scales_before_mapping() {
+        assert_eq!(reinhard(2.0, 0.5), reinhard(1.0, 1.0));
+        assert_eq!(reinhard(-1.0, 1.0), 0.0, "負の値は0にする");
+    }
+
+    #[test]
+    fn test_gamma_is_left_to_srgb_outputs() {
+        assert_eq!(output_gamma(wgpu::TextureFormat::Bgra8UnormSrgb), 1.0);
+        assert_eq!(output_gamma(wgpu::TextureFormat::Bgra8Unorm), DISPLAY_GAMMA);
+    }
+}
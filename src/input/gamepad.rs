@@ -0,0 +1,167 @@
+/// Stick deflection below which an axis reads as 0 (filters stick drift).
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+pub const GAMEPAD_DEADZONE: f32 = 0.15;
+
+/// Analog stick axes, in the range -1.0..=1.0 (Y positive is up).
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
+impl GamepadAxis {
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    pub const ALL: [GamepadAxis; 4] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+    ];
+}
+
+/// Digital buttons, named by position (`South` is A on Xbox, Cross on PlayStation).
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftShoulder,
+    RightShoulder,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    pub const ALL: [GamepadButton; 12] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::North,
+        GamepadButton::West,
+        GamepadButton::LeftShoulder,
+        GamepadButton::RightShoulder,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+    ];
+}
+
+/// Zeroes values inside the deadzone and rescales the rest so output still
+/// starts at 0 and reaches ±1 at full deflection.
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+pub fn apply_deadzone(value: f32) -> f32 {
+    let magnitude = value.abs();
+    if !magnitude.is_finite() || magnitude < GAMEPAD_DEADZONE {
+        return 0.0;
+    }
+    let scaled = ((magnitude - GAMEPAD_DEADZONE) / (1.0 - GAMEPAD_DEADZONE)).min(1.0);
+    scaled.copysign(value)
+}
+
+/// Reads the first connected controller into `InputState` once per frame.
+#[cfg(feature = "gamepad")]
+pub struct GamepadPoller {
+    /// `None` if the platform backend could not be initialized.
+    gilrs: Option<gilrs::Gilrs>,
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadPoller {
+    pub fn new() -> Self {
+        let gilrs = gilrs::Gilrs::new()
+            .inspect_err(|e| log::warn!("Gamepad input disabled: {}", e))
+            .ok();
+        Self { gilrs }
+    }
+
+    /// Updates the gamepad state of `input`; it is cleared when no
+    /// controller is connected.
+    pub fn poll(&mut self, input: &mut super::InputState) {
+        let Some(gilrs) = &mut self.gilrs else {
+            return;
+        };
+        // イベントを取り出して内部状態を最新にする
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                gilrs::EventType::Connected => log::info!("Gamepad connected"),
+                gilrs::EventType::Disconnected => log::info!("Gamepad disconnected"),
+                _ => {}
+            }
+        }
+
+        input.clear_gamepad();
+        let Some((_, gamepad)) = gilrs.gamepads().find(|(_, gamepad)| gamepad.is_connected())
+        else {
+            return;
+        };
+
+        for axis in GamepadAxis::ALL {
+            input.set_gamepad_axis(axis, gamepad.value(to_gilrs_axis(axis)));
+        }
+        for button in GamepadButton::ALL {
+            input.set_gamepad_button(button, gamepad.is_pressed(to_gilrs_button(button)));
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn to_gilrs_axis(axis: GamepadAxis) -> gilrs::Axis {
+    match axis {
+        GamepadAxis::LeftStickX => gilrs::Axis::LeftStickX,
+        GamepadAxis::LeftStickY => gilrs::Axis::LeftStickY,
+        GamepadAxis::RightStickX => gilrs::Axis::RightStickX,
+        GamepadAxis::RightStickY => gilrs::Axis::RightStickY,
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn to_gilrs_button(button: GamepadButton) -> gilrs::Button {
+    match button {
+        GamepadButton::South => gilrs::Button::South,
+        GamepadButton::East => gilrs::Button::East,
+        GamepadButton::North => gilrs::Button::North,
+        GamepadButton::West => gilrs::Button::West,
+        GamepadButton::LeftShoulder => gilrs::Button::LeftTrigger,
+        GamepadButton::RightShoulder => gilrs::Button::RightTrigger,
+        GamepadButton::Select => gilrs::Button::Select,
+        GamepadButton::Start => gilrs::Button::Start,
+        GamepadButton::DPadUp => gilrs::Button::DPadUp,
+        GamepadButton::DPadDown => gilrs::Button::DPadDown,
+        GamepadButton::DPadLeft => gilrs::Button::DPadLeft,
+        GamepadButton::DPadRight => gilrs::Button::DPadRight,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadzone_filters_drift() {
+        assert_eq!(apply_deadzone(0.0), 0.0);
+        assert_eq!(apply_deadzone(GAMEPAD_DEADZONE * 0.5), 0.0);
+        assert_eq!(apply_deadzone(-GAMEPAD_DEADZONE * 0.9), 0.0);
+        assert_eq!(apply_deadzone(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_deadzone_rescales_to_full_range() {
+        assert!((apply_deadzone(1.0) - 1.0).abs() < 1e-6);
+        assert!((apply_deadzone(-1.0) + 1.0).abs() < 1e-6);
+        // デッドゾーン直後は0から滑らかに立ち上がる
+        assert!(apply_deadzone(GAMEPAD_DEADZONE + 0.01) < 0.02);
+        assert_eq!(apply_deadzone(1.5), 1.0);
+    }
+}
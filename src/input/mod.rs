@@ -1,40 +1,63 @@
-use std::collections::HashSet;
+pub mod gamepad;
+
+use std::collections::{HashMap, HashSet};
 
 use winit::{
-    event::{ElementState, KeyEvent, MouseButton},
+    event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta},
     keyboard::{KeyCode, PhysicalKey},
 };
 
+use crate::input::gamepad::{GamepadAxis, GamepadButton, apply_deadzone};
+
 pub struct InputState {
     keys_pressed: HashSet<KeyCode>,
+    /// Snapshot of `keys_pressed` taken by `end_frame`, for edge detection.
+    previous_keys_pressed: HashSet<KeyCode>,
     mouse_buttons: HashSet<MouseButton>,
     mouse_posittion: glam::Vec2,
     mouse_delta: glam::Vec2,
+    /// Accumulated wheel movement in lines (positive = away from the user).
+    scroll_delta: f32,
+    /// While captured, `mouse_delta` comes from raw device motion instead of
+    /// cursor positions, so mouse look does not stop at the screen edges.
+    cursor_captured: bool,
+    /// Stick values after the deadzone; empty without a controller.
+    gamepad_axes: HashMap<GamepadAxis, f32>,
+    gamepad_buttons: HashSet<GamepadButton>,
 }
 
+/// Pixels of touchpad scrolling treated as one wheel line.
+const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
 impl InputState {
     pub fn new() -> Self {
         Self {
             keys_pressed: HashSet::new(),
+            previous_keys_pressed: HashSet::new(),
             mouse_buttons: HashSet::new(),
             mouse_posittion: glam::Vec2::ZERO,
             mouse_delta: glam::Vec2::ZERO,
+            scroll_delta: 0.0,
+            cursor_captured: false,
+            gamepad_axes: HashMap::new(),
+            gamepad_buttons: HashSet::new(),
         }
     }
 
     pub fn process_keybord(&mut self, event: &KeyEvent) {
         if let PhysicalKey::Code(keycode) = event.physical_key {
-            match event.state {
-                ElementState::Pressed => {
-                    log::debug!("Key pressed: {:?}", keycode);
-                    self.keys_pressed.insert(keycode);
-                }
-                ElementState::Released => {
-                    log::debug!("Key released: {:?}", keycode);
-                    self.keys_pressed.remove(&keycode);
-                }
-            }
-            log::debug!("Currently pressed keys: {:?}", self.keys_pressed);
+            self.set_key_pressed(keycode, event.state.is_pressed());
+            log::trace!("Currently pressed keys: {:?}", self.keys_pressed);
+        }
+    }
+
+    pub fn set_key_pressed(&mut self, key: KeyCode, pressed: bool) {
+        if pressed {
+            log::trace!("Key pressed: {:?}", key);
+            self.keys_pressed.insert(key);
+        } else {
+            log::trace!("Key released: {:?}", key);
+            self.keys_pressed.remove(&key);
         }
     }
 
@@ -42,6 +65,17 @@ impl InputState {
         self.keys_pressed.contains(&key)
     }
 
+    /// True only in the first frame the key is held.
+    pub fn is_key_just_pressed(&self, key: KeyCode) -> bool {
+        self.keys_pressed.contains(&key) && !self.previous_keys_pressed.contains(&key)
+    }
+
+    /// True only in the first frame after the key is let go.
+    #[allow(dead_code)]
+    pub fn is_key_just_released(&self, key: KeyCode) -> bool {
+        !self.keys_pressed.contains(&key) && self.previous_keys_pressed.contains(&key)
+    }
+
     pub fn process_mouse_input(&mut self, button: MouseButton, state: ElementState) {
         match state {
             ElementState::Pressed => {
@@ -53,18 +87,214 @@ impl InputState {
         }
     }
 
-    #[allow(dead_code)]
     pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
         self.mouse_buttons.contains(&button)
     }
 
     pub fn set_mouse_position(&mut self, x: f32, y: f32) {
         let new_position = glam::Vec2::new(x, y);
-        self.mouse_delta = new_position - self.mouse_posittion;
+        // 1フレーム内の複数イベント分を累積する（キャプチャ中は生の移動量を使う）
+        if !self.cursor_captured {
+            self.mouse_delta += new_position - self.mouse_posittion;
+        }
         self.mouse_posittion = new_position;
     }
 
+    /// Accumulates relative device motion (`DeviceEvent::MouseMotion`).
+    ///
+    /// Ignored unless the cursor is captured.
+    pub fn process_mouse_motion(&mut self, dx: f32, dy: f32) {
+        if self.cursor_captured {
+            self.mouse_delta += glam::Vec2::new(dx, dy);
+        }
+    }
+
+    pub fn set_cursor_captured(&mut self, captured: bool) {
+        self.cursor_captured = captured;
+        self.mouse_delta = glam::Vec2::ZERO;
+    }
+
+    pub fn is_cursor_captured(&self) -> bool {
+        self.cursor_captured
+    }
+
+    /// Cursor position in physical pixels from the window's top-left corner.
+    pub fn mouse_position(&self) -> glam::Vec2 {
+        self.mouse_posittion
+    }
+
+    pub fn mouse_delta(&self) -> glam::Vec2 {
+        self.mouse_delta
+    }
+
+    pub fn process_scroll(&mut self, delta: MouseScrollDelta) {
+        self.scroll_delta += match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_SCROLL_LINE,
+        };
+    }
+
+    pub fn scroll_delta(&self) -> f32 {
+        self.scroll_delta
+    }
+
+    /// Stores a raw stick value; values inside `GAMEPAD_DEADZONE` read as 0.
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    pub fn set_gamepad_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.gamepad_axes.insert(axis, apply_deadzone(value));
+    }
+
+    /// Returns the stick value (-1.0..=1.0), or 0 when no controller is connected.
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepad_axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    pub fn set_gamepad_button(&mut self, button: GamepadButton, pressed: bool) {
+        if pressed {
+            self.gamepad_buttons.insert(button);
+        } else {
+            self.gamepad_buttons.remove(&button);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn gamepad_button_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepad_buttons.contains(&button)
+    }
+
+    /// Releases all gamepad axes and buttons (controller disconnected).
+    #[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+    pub fn clear_gamepad(&mut self) {
+        self.gamepad_axes.clear();
+        self.gamepad_buttons.clear();
+    }
+
+    /// Clears the per-frame mouse motion and scroll accumulated since the last frame.
     pub fn reset_mouse_delta(&mut self) {
         self.mouse_delta = glam::Vec2::ZERO;
+        self.scroll_delta = 0.0;
+    }
+
+    /// Finishes the frame: snapshots the held keys for the next frame's
+    /// just-pressed/just-released checks and clears the mouse deltas.
+    pub fn end_frame(&mut self) {
+        self.previous_keys_pressed.clone_from(&self.keys_pressed);
+        self.reset_mouse_delta();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_delta_accumulates_when_free() {
+        let mut input = InputState::new();
+        input.set_mouse_position(10.0, 10.0);
+        input.reset_mouse_delta();
+
+        input.set_mouse_position(15.0, 12.0);
+        input.set_mouse_position(20.0, 8.0);
+        // デバイス移動量はキャプチャ中でなければ無視される
+        input.process_mouse_motion(100.0, 100.0);
+
+        assert_eq!(input.mouse_delta(), glam::vec2(10.0, -2.0));
+    }
+
+    #[test]
+    fn test_captured_delta_uses_device_motion() {
+        let mut input = InputState::new();
+        input.set_cursor_captured(true);
+
+        input.process_mouse_motion(3.0, -1.0);
+        input.process_mouse_motion(2.0, 4.0);
+        input.set_mouse_position(400.0, 300.0);
+
+        assert_eq!(input.mouse_delta(), glam::vec2(5.0, 3.0));
+
+        input.reset_mouse_delta();
+        assert_eq!(input.mouse_delta(), glam::Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_gamepad_defaults_to_neutral() {
+        let input = InputState::new();
+
+        // コントローラー未接続でもパニックせず中立値を返す
+        for axis in GamepadAxis::ALL {
+            assert_eq!(input.gamepad_axis(axis), 0.0);
+        }
+        assert!(!input.gamepad_button_pressed(GamepadButton::South));
+    }
+
+    #[test]
+    fn test_gamepad_state_and_clear() {
+        let mut input = InputState::new();
+        input.set_gamepad_axis(GamepadAxis::LeftStickY, 1.0);
+        input.set_gamepad_axis(GamepadAxis::RightStickX, 0.05);
+        input.set_gamepad_button(GamepadButton::South, true);
+
+        assert!((input.gamepad_axis(GamepadAxis::LeftStickY) - 1.0).abs() < 1e-6);
+        assert_eq!(input.gamepad_axis(GamepadAxis::RightStickX), 0.0);
+        assert!(input.gamepad_button_pressed(GamepadButton::South));
+
+        input.clear_gamepad();
+
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickY), 0.0);
+        assert!(!input.gamepad_button_pressed(GamepadButton::South));
+    }
+
+    #[test]
+    fn test_key_press_hold_release_edges() {
+        let mut input = InputState::new();
+
+        // 押した最初のフレーム
+        input.set_key_pressed(KeyCode::F1, true);
+        assert!(input.is_key_pressed(KeyCode::F1));
+        assert!(input.is_key_just_pressed(KeyCode::F1));
+        assert!(!input.is_key_just_released(KeyCode::F1));
+        input.end_frame();
+
+        // 押しっぱなしのフレームではエッジは立たない
+        assert!(input.is_key_pressed(KeyCode::F1));
+        assert!(!input.is_key_just_pressed(KeyCode::F1));
+        assert!(!input.is_key_just_released(KeyCode::F1));
+        input.end_frame();
+
+        // 離した最初のフレーム
+        input.set_key_pressed(KeyCode::F1, false);
+        assert!(!input.is_key_pressed(KeyCode::F1));
+        assert!(!input.is_key_just_pressed(KeyCode::F1));
+        assert!(input.is_key_just_released(KeyCode::F1));
+        input.end_frame();
+
+        assert!(!input.is_key_just_released(KeyCode::F1));
+    }
+
+    #[test]
+    fn test_key_edges_are_per_key() {
+        let mut input = InputState::new();
+        input.set_key_pressed(KeyCode::KeyW, true);
+        input.end_frame();
+
+        input.set_key_pressed(KeyCode::KeyA, true);
+
+        assert!(!input.is_key_just_pressed(KeyCode::KeyW));
+        assert!(input.is_key_just_pressed(KeyCode::KeyA));
+        assert!(!input.is_key_just_pressed(KeyCode::KeyD));
+    }
+
+    #[test]
+    fn test_end_frame_clears_mouse_deltas() {
+        let mut input = InputState::new();
+        input.set_cursor_captured(true);
+        input.process_mouse_motion(3.0, 4.0);
+        input.process_scroll(MouseScrollDelta::LineDelta(0.0, 1.0));
+
+        input.end_frame();
+
+        assert_eq!(input.mouse_delta(), glam::Vec2::ZERO);
+        assert_eq!(input.scroll_delta(), 0.0);
     }
 }
@@ -10,7 +10,20 @@ mod window;
 
 use core::error::EngineError;
 
+use graphics::diagnostics::DiagnosticsOptions;
+
 fn main() -> Result<(), EngineError> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--diagnostics") {
+        let options = DiagnosticsOptions {
+            force_fallback_adapter: args.iter().any(|arg| arg == "--force-fallback-adapter"),
+        };
+        if !app::diagnostics::run(options) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let event_loop = event_loop::EventLoop::new()
         .map_err(|e| EngineError::EventLoopCreation(format!("Event loop creation error: {}", e)))?;
     let mut app = app::App::new();
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::vertex::VertexTrait;
+
+/// Up axis of the coordinate system an asset was authored in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum UpAxis {
+    /// Y-up (glTF, the engine's own convention).
+    #[default]
+    Y,
+    /// Z-up (Blender/3ds Max style exports).
+    Z,
+}
+
+/// Corrections applied to imported geometry so it matches the engine's
+/// right-handed, Y-up coordinate system.
+///
+/// The correction is applied to vertex positions and normals at load time:
+/// first the up-axis rotation, then the optional Z flip, then uniform scale.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ImportOptions {
+    pub up_axis: UpAxis,
+    pub scale: f32,
+    /// Mirror the Z axis (converts left-handed assets to right-handed).
+    pub flip_z: bool,
+    /// Merge vertices closer than this distance after loading (`None` disables
+    /// welding); see [`ImportOptions::weld`].
+    pub weld_epsilon: Option<f32>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            up_axis: UpAxis::Y,
+            scale: 1.0,
+            flip_z: false,
+            weld_epsilon: None,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Returns the matrix mapping asset coordinates to engine coordinates.
+    pub fn correction_matrix(&self) -> glam::Mat4 {
+        let rotation = match self.up_axis {
+            UpAxis::Y => glam::Mat4::IDENTITY,
+            // Z-up -> Y-up: (x, y, z) -> (x, z, -y)
+            UpAxis::Z => glam::Mat4::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+        };
+        let flip = if self.flip_z {
+            glam::Mat4::from_scale(glam::vec3(1.0, 1.0, -1.0))
+        } else {
+            glam::Mat4::IDENTITY
+        };
+
+        glam::Mat4::from_scale(glam::Vec3::splat(self.scale)) * flip * rotation
+    }
+
+    /// Returns `true` if the correction mirrors geometry, which reverses
+    /// triangle winding.
+    #[allow(dead_code)]
+    pub fn flips_winding(&self) -> bool {
+        self.correction_matrix().determinant() < 0.0
+    }
+
+    #[allow(dead_code)]
+    pub fn apply_to_position(&self, position: [f32; 3]) -> [f32; 3] {
+        self.correction_matrix()
+            .transform_point3(glam::Vec3::from(position))
+            .to_array()
+    }
+
+    #[allow(dead_code)]
+    pub fn apply_to_normal(&self, normal: [f32; 3]) -> [f32; 3] {
+        let normal_matrix = glam::Mat3::from_mat4(self.correction_matrix())
+            .inverse()
+            .transpose();
+        (normal_matrix * glam::Vec3::from(normal))
+            .normalize_or_zero()
+            .to_array()
+    }
+
+    /// Runs the optional welding step: merges vertices within `weld_epsilon`
+    /// and rewrites `indices`, or passes the mesh through unchanged when
+    /// welding is disabled.
+    pub fn weld<V: VertexTrait>(&self, vertices: Vec<V>, indices: Vec<u32>) -> WeldedMesh<V> {
+        match self.weld_epsilon {
+            Some(epsilon) => weld_vertices(&vertices, Some(&indices), epsilon),
+            None => WeldedMesh { vertices, indices },
+        }
+    }
+
+    /// Applies the correction in place to positions, normals and (when the
+    /// correction mirrors geometry) the triangle winding of `indices`.
+    pub fn apply(&self, positions: &mut [[f32; 3]], normals: &mut [[f32; 3]], indices: &mut [u32]) {
+        let matrix = self.correction_matrix();
+        let normal_matrix = glam::Mat3::from_mat4(matrix).inverse().transpose();
+
+        for position in positions.iter_mut() {
+            *position = matrix
+                .transform_point3(glam::Vec3::from(*position))
+                .to_array();
+        }
+        for normal in normals.iter_mut() {
+            *normal = (normal_matrix * glam::Vec3::from(*normal))
+                .normalize_or_zero()
+                .to_array();
+        }
+        if matrix.determinant() < 0.0 {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+}
+
+/// Result of [`weld_vertices`]: the deduplicated vertices and the rebuilt index list.
+#[derive(Debug, Clone)]
+pub struct WeldedMesh<V> {
+    pub vertices: Vec<V>,
+    /// Always `u32`; use [`WeldedMesh::indices_u16`] when the mesh is small enough.
+    pub indices: Vec<u32>,
+}
+
+impl<V> WeldedMesh<V> {
+    /// Returns the indices as `u16` if every vertex is addressable with 16 bits.
+    pub fn indices_u16(&self) -> Option<Vec<u16>> {
+        if self.vertices.len() > u16::MAX as usize + 1 {
+            return None;
+        }
+        Some(self.indices.iter().map(|&i| i as u16).collect())
+    }
+}
+
+/// Merges vertices whose positions lie within `epsilon` of each other.
+///
+/// The first vertex of each cluster is kept (with its attributes) and
+/// `indices` is rewritten to reference the welded set. Passing `None` for
+/// `indices` treats the input as a non-indexed triangle list.
+pub fn weld_vertices<V: VertexTrait>(
+    vertices: &[V],
+    indices: Option<&[u32]>,
+    epsilon: f32,
+) -> WeldedMesh<V> {
+    // epsilon 幅のグリッドでハッシュし、近傍セルだけを比較する
+    let cell_size = epsilon.max(f32::EPSILON);
+    let cell_of = |p: glam::Vec3| (p / cell_size).floor().as_ivec3();
+
+    let mut grid: HashMap<glam::IVec3, Vec<u32>> = HashMap::new();
+    let mut welded: Vec<V> = Vec::new();
+    let mut remap: Vec<u32> = Vec::with_capacity(vertices.len());
+
+    for vertex in vertices {
+        let position = glam::Vec3::from(vertex.position());
+        let cell = cell_of(position);
+
+        let mut found = None;
+        'search: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbor = cell + glam::ivec3(dx, dy, dz);
+                    let Some(candidates) = grid.get(&neighbor) else {
+                        continue;
+                    };
+                    for &candidate in candidates {
+                        let other = glam::Vec3::from(welded[candidate as usize].position());
+                        if position.distance(other) <= epsilon {
+                            found = Some(candidate);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+
+        let index = found.unwrap_or_else(|| {
+            let index = welded.len() as u32;
+            welded.push(*vertex);
+            grid.entry(cell).or_default().push(index);
+            index
+        });
+        remap.push(index);
+    }
+
+    let indices = match indices {
+        Some(indices) => indices.iter().map(|&i| remap[i as usize]).collect(),
+        None => remap,
+    };
+
+    WeldedMesh {
+        vertices: welded,
+        indices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::vertex::ColorVertex;
+
+    fn color_vertex(position: [f32; 3]) -> ColorVertex {
+        ColorVertex {
+            position,
+            color: [1.0, 1.0, 1.0],
+        }
+    }
+
+    fn assert_vec_eq(actual: [f32; 3], expected: [f32; 3]) {
+        let diff = glam::Vec3::from(actual) - glam::Vec3::from(expected);
+        assert!(diff.length() < 1e-5, "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn test_default_options_are_identity() {
+        let options = ImportOptions::default();
+
+        assert_vec_eq(options.apply_to_position([1.0, 2.0, 3.0]), [1.0, 2.0, 3.0]);
+        assert!(!options.flips_winding());
+    }
+
+    #[test]
+    fn test_z_up_to_y_up() {
+        let options = ImportOptions {
+            up_axis: UpAxis::Z,
+            ..Default::default()
+        };
+
+        // Z-upの「上」はエンジンのY+になるべき
+        assert_vec_eq(options.apply_to_position([0.0, 0.0, 1.0]), [0.0, 1.0, 0.0]);
+        // Z-upの「前」(Y+) は -Z になるべき
+        assert_vec_eq(options.apply_to_position([0.0, 1.0, 0.0]), [0.0, 0.0, -1.0]);
+        assert_vec_eq(options.apply_to_position([1.0, 2.0, 3.0]), [1.0, 3.0, -2.0]);
+        assert_vec_eq(options.apply_to_normal([0.0, 0.0, 1.0]), [0.0, 1.0, 0.0]);
+        assert!(!options.flips_winding(), "回転のみでは巻き順は変わらない");
+    }
+
+    #[test]
+    fn test_scale_and_flip_z() {
+        let options = ImportOptions {
+            up_axis: UpAxis::Y,
+            scale: 2.0,
+            flip_z: true,
+            weld_epsilon: None,
+        };
+
+        assert_vec_eq(options.apply_to_position([1.0, 1.0, 1.0]), [2.0, 2.0, -2.0]);
+        // 法線はスケールされず正規化されたまま
+        assert_vec_eq(options.apply_to_normal([0.0, 0.0, 1.0]), [0.0, 0.0, -1.0]);
+        assert!(options.flips_winding());
+    }
+
+    #[test]
+    fn test_apply_reverses_winding_when_mirrored() {
+        let options = ImportOptions {
+            flip_z: true,
+            ..Default::default()
+        };
+        let mut positions = [[0.0, 0.0, 1.0]];
+        let mut normals = [[0.0, 0.0, 1.0]];
+        let mut indices = [0, 1, 2, 3, 4, 5];
+
+        options.apply(&mut positions, &mut normals, &mut indices);
+
+        assert_vec_eq(positions[0], [0.0, 0.0, -1.0]);
+        assert_vec_eq(normals[0], [0.0, 0.0, -1.0]);
+        assert_eq!(indices, [0, 2, 1, 3, 5, 4]);
+    }
+
+    #[test]
+    fn test_weld_shares_duplicated_corners() {
+        // 2つの三角形で構成された四角形（対角の頂点が重複している）
+        let vertices = vec![
+            color_vertex([-0.5, -0.5, 0.0]),
+            color_vertex([0.5, -0.5, 0.0]),
+            color_vertex([0.5, 0.5, 0.0]),
+            color_vertex([-0.5, -0.5, 0.0]),
+            color_vertex([0.5, 0.5, 0.0]),
+            color_vertex([-0.5, 0.5, 0.0]),
+        ];
+
+        let welded = weld_vertices(&vertices, None, 1e-5);
+
+        assert_eq!(welded.vertices.len(), 4, "重複頂点はマージされるべき");
+        assert_eq!(welded.indices.len(), 6);
+        for (original, &index) in vertices.iter().zip(&welded.indices) {
+            assert!((index as usize) < welded.vertices.len());
+            assert_eq!(welded.vertices[index as usize].position, original.position);
+        }
+    }
+
+    #[test]
+    fn test_weld_runs_only_with_an_epsilon() {
+        let vertices = vec![
+            color_vertex([0.0, 0.0, 0.0]),
+            color_vertex([1.0, 0.0, 0.0]),
+            color_vertex([0.0, 0.0, 0.0]),
+        ];
+        let indices = vec![0, 1, 2];
+
+        let unwelded = ImportOptions::default().weld(vertices.clone(), indices.clone());
+        assert_eq!(
+            unwelded.vertices.len(),
+            3,
+            "weld_epsilon が None なら溶接しない"
+        );
+        assert_eq!(unwelded.indices, indices);
+
+        let options = ImportOptions {
+            weld_epsilon: Some(1e-5),
+            ..Default::default()
+        };
+        let welded = options.weld(vertices, indices);
+        assert_eq!(welded.vertices.len(), 2);
+        assert_eq!(welded.indices, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_weld_respects_epsilon() {
+        let vertices = vec![
+            color_vertex([0.0, 0.0, 0.0]),
+            color_vertex([0.0005, 0.0, 0.0]),
+            color_vertex([0.1, 0.0, 0.0]),
+        ];
+        let indices = [0, 1, 2];
+
+        let welded = weld_vertices(&vertices, Some(&indices), 0.001);
+
+        assert_eq!(welded.vertices.len(), 2);
+        assert_eq!(welded.indices, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_weld_rewrites_existing_indices() {
+        let vertices = vec![
+            color_vertex([0.0, 0.0, 0.0]),
+            color_vertex([1.0, 0.0, 0.0]),
+            color_vertex([0.0, 0.0, 0.0]),
+        ];
+        let indices = [2, 1, 0];
+
+        let welded = weld_vertices(&vertices, Some(&indices), 1e-5);
+
+        assert_eq!(welded.vertices.len(), 2);
+        assert_eq!(welded.indices, vec![0, 1, 0]);
+        assert_eq!(welded.indices_u16(), Some(vec![0, 1, 0]));
+    }
+}
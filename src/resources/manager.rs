@@ -1,10 +1,22 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use wgpu::util::DeviceExt;
 
 use crate::{
     core::error::{EngineError, EngineResult},
-    resources::mesh::Mesh,
+    resources::{
+        import::ImportOptions,
+        mesh::Mesh,
+        mesh::MeshIndex,
+        obj,
+        pipeline::{PipelineOptions, conservative_rasterization, flipped_front_face, polygon_mode},
+        texture::{SHADOW_FORMAT, Texture},
+        vertex::{InstanceData, VertexTrait},
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -22,22 +34,79 @@ impl ResourceId {
     }
 }
 
+/// Number of resources of each kind currently registered in a [`ResourceManager`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceStats {
+    pub buffers: usize,
+    pub pipelines: usize,
+    pub shaders: usize,
+    pub meshes: usize,
+    pub bind_groups: usize,
+    pub textures: usize,
+}
+
+/// Inputs of `ResourceManager::create_pipeline`, kept so the pipeline can be
+/// rebuilt when its shader is reloaded.
+struct PipelineSource {
+    shader_id: ResourceId,
+    vertex_stride: wgpu::BufferAddress,
+    vertex_step_mode: wgpu::VertexStepMode,
+    vertex_attributes: Vec<wgpu::VertexAttribute>,
+    surface_format: wgpu::TextureFormat,
+    bind_group_layouts: Vec<wgpu::BindGroupLayout>,
+    options: PipelineOptions,
+    /// Reads [`InstanceData`] from vertex slot 1.
+    instanced: bool,
+}
+
+impl PipelineSource {
+    fn new(
+        shader_id: ResourceId,
+        vertex_layout: &wgpu::VertexBufferLayout,
+        surface_format: wgpu::TextureFormat,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+        options: &PipelineOptions,
+        instanced: bool,
+    ) -> Self {
+        Self {
+            shader_id,
+            vertex_stride: vertex_layout.array_stride,
+            vertex_step_mode: vertex_layout.step_mode,
+            vertex_attributes: vertex_layout.attributes.to_vec(),
+            surface_format,
+            bind_group_layouts: bind_group_layouts
+                .iter()
+                .map(|layout| (*layout).clone())
+                .collect(),
+            options: *options,
+            instanced,
+        }
+    }
+}
+
+/// File a shader was loaded from, for `ResourceManager::reload_shader`.
+struct ShaderFile {
+    path: PathBuf,
+    label: Option<String>,
+}
+
 /// Central manager for GPU resources with shared ownership and caching.
-/// 
+///
 /// Manages creation, storage, and retrieval of WGPU resources including
 /// buffers, pipelines, shaders, meshes, and bind groups. Uses Arc for
 /// safe sharing between components and HashMap for efficient lookups.
-/// 
+///
 /// # Resource Types
-/// 
+///
 /// - **Buffers**: Uniform buffers, vertex buffers, index buffers  
 /// - **Pipelines**: Render pipelines with shader stages
 /// - **Shaders**: Compiled WGSL shader modules
 /// - **Meshes**: Vertex and index data for geometry
 /// - **Bind Groups**: Resource binding sets for shaders
-/// 
+/// - **Textures**: Decoded images with their view and sampler
+///
 /// # Examples
-/// 
+///
 /// ```rust
 /// let manager = ResourceManager::new(device, queue, surface_format);
 /// let shader_id = ResourceId::new("basic_shader");
@@ -47,11 +116,27 @@ pub struct ResourceManager {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
     surface_format: wgpu::TextureFormat,
+    sample_count: u32,
+    depth_format: Option<wgpu::TextureFormat>,
     buffers: HashMap<ResourceId, Arc<wgpu::Buffer>>,
     pipelines: HashMap<ResourceId, Arc<wgpu::RenderPipeline>>,
+    /// Clockwise-front variants of `pipelines`, used for mirrored transforms.
+    mirrored_pipelines: HashMap<ResourceId, Arc<wgpu::RenderPipeline>>,
+    /// Instanced variants, keyed by the pipeline they replace.
+    instanced_pipelines: HashMap<ResourceId, Arc<wgpu::RenderPipeline>>,
+    mirrored_instanced_pipelines: HashMap<ResourceId, Arc<wgpu::RenderPipeline>>,
+    compute_pipelines: HashMap<ResourceId, Arc<wgpu::ComputePipeline>>,
+    /// Depth-only variants of pipelines, keyed by the base pipeline id.
+    shadow_pipelines: HashMap<ResourceId, Arc<wgpu::RenderPipeline>>,
     shaders: HashMap<ResourceId, Arc<wgpu::ShaderModule>>,
     meshes: HashMap<ResourceId, Arc<Mesh>>,
+    /// Number of scenes holding each shared mesh (see `retain_mesh`).
+    mesh_refs: HashMap<ResourceId, usize>,
     bind_groups: HashMap<ResourceId, Arc<wgpu::BindGroup>>,
+    textures: HashMap<ResourceId, Arc<Texture>>,
+    pipeline_sources: HashMap<ResourceId, PipelineSource>,
+    instanced_pipeline_sources: HashMap<ResourceId, PipelineSource>,
+    shader_files: HashMap<ResourceId, ShaderFile>,
 }
 
 impl ResourceManager {
@@ -64,14 +149,41 @@ impl ResourceManager {
             device,
             queue,
             surface_format,
+            sample_count: 1,
+            depth_format: None,
             buffers: HashMap::new(),
             pipelines: HashMap::new(),
+            mirrored_pipelines: HashMap::new(),
+            instanced_pipelines: HashMap::new(),
+            mirrored_instanced_pipelines: HashMap::new(),
+            compute_pipelines: HashMap::new(),
+            shadow_pipelines: HashMap::new(),
             shaders: HashMap::new(),
             meshes: HashMap::new(),
+            mesh_refs: HashMap::new(),
             bind_groups: HashMap::new(),
+            textures: HashMap::new(),
+            pipeline_sources: HashMap::new(),
+            instanced_pipeline_sources: HashMap::new(),
+            shader_files: HashMap::new(),
         }
     }
 
+    /// Sets the MSAA sample count of the main color target.
+    ///
+    /// Scenes read it back with [`ResourceManager::get_sample_count`] when
+    /// creating pipelines that render into that target.
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+
+    /// Sets the depth format of the main render pass (`None` = no depth buffer).
+    pub fn with_depth_format(mut self, depth_format: Option<wgpu::TextureFormat>) -> Self {
+        self.depth_format = depth_format;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn create_buffer_with_data(
         &mut self,
@@ -112,6 +224,86 @@ impl ResourceManager {
         Ok(arc_buffer)
     }
 
+    /// Creates a storage buffer initialized with `data`, registered as `id`.
+    ///
+    /// The buffer can be rewritten from the CPU (`COPY_DST`); with `read_back`
+    /// it can also be copied out (`COPY_SRC`), which [`ResourceManager::read_buffer`]
+    /// requires.
+    #[allow(dead_code)]
+    pub fn create_storage_buffer<T: bytemuck::Pod>(
+        &mut self,
+        id: ResourceId,
+        data: &[T],
+        read_back: bool,
+    ) -> EngineResult<Arc<wgpu::Buffer>> {
+        let mut usage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST;
+        if read_back {
+            usage |= wgpu::BufferUsages::COPY_SRC;
+        }
+        self.create_buffer_with_data(
+            id,
+            bytemuck::cast_slice(data),
+            usage,
+            Some("Storage Buffer"),
+        )
+    }
+
+    /// Copies buffer `id` into a staging buffer and returns its contents.
+    ///
+    /// This submits its own copy and blocks until the GPU has finished all
+    /// previously submitted work, so it stalls the pipeline; use it for
+    /// compute results and debugging, not every frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the buffer is not registered
+    /// and `EngineError::BufferRead` if it lacks `COPY_SRC` or mapping fails.
+    #[allow(dead_code)]
+    pub fn read_buffer(&self, id: ResourceId) -> EngineResult<Vec<u8>> {
+        let buffer = self
+            .buffers
+            .get(&id)
+            .ok_or_else(|| EngineError::ResourceNotFound(format!("Buffer not found: {:?}", id)))?;
+        if !buffer.usage().contains(wgpu::BufferUsages::COPY_SRC) {
+            return Err(EngineError::BufferRead(format!(
+                "Buffer {:?} was not created with COPY_SRC",
+                id
+            )));
+        }
+
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Buffer Readback"),
+            size: buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Buffer Readback Encoder"),
+            });
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        // map_async はポーリングされるまで完了しないので、待ってから結果を受け取る
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let slice = staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(|e| EngineError::BufferRead(format!("Failed to wait for GPU: {}", e)))?;
+        receiver
+            .recv()
+            .map_err(|e| EngineError::BufferRead(e.to_string()))?
+            .map_err(|e| EngineError::BufferRead(format!("Failed to map buffer: {}", e)))?;
+
+        let contents = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(contents)
+    }
+
     pub fn update_uniform_buffer<T: bytemuck::Pod>(&mut self, buffer: &wgpu::Buffer, data: &T) {
         self.queue
             .write_buffer(buffer, 0, bytemuck::cast_slice(&[*data]));
@@ -136,6 +328,103 @@ impl ResourceManager {
         Ok(shader)
     }
 
+    /// Creates a shader from a WGSL file and remembers the path so it can be
+    /// reloaded with [`ResourceManager::reload_shader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the file cannot be read and
+    /// `EngineError::ShaderCompilation` if the WGSL is invalid.
+    pub fn create_shader_from_path(
+        &mut self,
+        id: ResourceId,
+        path: impl AsRef<Path>,
+        label: Option<&str>,
+    ) -> EngineResult<Arc<wgpu::ShaderModule>> {
+        let path = path.as_ref();
+        let source = read_shader_source(path)?;
+        let shader = Arc::new(self.compile_shader(&source, label)?);
+
+        self.shaders.insert(id, shader.clone());
+        self.shader_files.insert(
+            id,
+            ShaderFile {
+                path: path.to_path_buf(),
+                label: label.map(str::to_string),
+            },
+        );
+        Ok(shader)
+    }
+
+    /// Re-reads a shader created with [`ResourceManager::create_shader_from_path`],
+    /// recompiles it and rebuilds every pipeline that uses it.
+    ///
+    /// Nothing is replaced unless the shader and all dependent pipelines build
+    /// successfully, so a broken edit keeps the previous version running.
+    /// Returns the number of pipelines rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the shader was not loaded
+    /// from a file or the file cannot be read, and
+    /// `EngineError::ShaderCompilation` if the shader or a pipeline fails to build.
+    pub fn reload_shader(&mut self, id: ResourceId) -> EngineResult<usize> {
+        let file = self.shader_files.get(&id).ok_or_else(|| {
+            EngineError::ResourceNotFound(format!("Shader {:?} was not loaded from a file", id))
+        })?;
+        let source = read_shader_source(&file.path)?;
+        let shader = self.compile_shader(&source, file.label.as_deref())?;
+
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let rebuild = |sources: &HashMap<ResourceId, PipelineSource>| -> Vec<_> {
+            sources
+                .iter()
+                .filter(|(_, source)| source.shader_id == id)
+                .map(|(pipeline_id, source)| (*pipeline_id, self.build_pipelines(&shader, source)))
+                .collect()
+        };
+        let rebuilt = rebuild(&self.pipeline_sources);
+        let rebuilt_instanced = rebuild(&self.instanced_pipeline_sources);
+        if let Some(error) = pollster::block_on(self.device.pop_error_scope()) {
+            return Err(EngineError::ShaderCompilation(error.to_string()));
+        }
+
+        let count = rebuilt.len() + rebuilt_instanced.len();
+        for (pipeline_id, (pipeline, mirrored_pipeline)) in rebuilt {
+            self.pipelines.insert(pipeline_id, Arc::new(pipeline));
+            self.mirrored_pipelines
+                .insert(pipeline_id, Arc::new(mirrored_pipeline));
+        }
+        for (pipeline_id, (pipeline, mirrored_pipeline)) in rebuilt_instanced {
+            self.instanced_pipelines
+                .insert(pipeline_id, Arc::new(pipeline));
+            self.mirrored_instanced_pipelines
+                .insert(pipeline_id, Arc::new(mirrored_pipeline));
+        }
+        self.shaders.insert(id, Arc::new(shader));
+        Ok(count)
+    }
+
+    /// Compiles WGSL, turning validation errors into `EngineError::ShaderCompilation`
+    /// instead of the device's uncaptured-error panic.
+    fn compile_shader(
+        &self,
+        source: &str,
+        label: Option<&str>,
+    ) -> EngineResult<wgpu::ShaderModule> {
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label,
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+        match pollster::block_on(self.device.pop_error_scope()) {
+            Some(error) => Err(EngineError::ShaderCompilation(error.to_string())),
+            None => Ok(shader),
+        }
+    }
+
     pub fn create_pipeline(
         &mut self,
         id: ResourceId,
@@ -143,64 +432,310 @@ impl ResourceManager {
         vertex_layout: wgpu::VertexBufferLayout,
         surface_format: wgpu::TextureFormat,
         bind_group_layouts: &[&wgpu::BindGroupLayout],
+        options: &PipelineOptions,
     ) -> EngineResult<Arc<wgpu::RenderPipeline>> {
         let shader = self.shaders.get(&shader_id).ok_or_else(|| {
             EngineError::ResourceNotFound(format!("Shader not found: {:?}", shader_id))
         })?;
 
+        let source = PipelineSource::new(
+            shader_id,
+            &vertex_layout,
+            surface_format,
+            bind_group_layouts,
+            options,
+            false,
+        );
+        let (pipeline, mirrored_pipeline) = self.build_pipelines(shader, &source);
+
+        let pipeline = Arc::new(pipeline);
+        self.pipelines.insert(id, pipeline.clone());
+        self.mirrored_pipelines
+            .insert(id, Arc::new(mirrored_pipeline));
+        self.pipeline_sources.insert(id, source);
+        Ok(pipeline)
+    }
+
+    /// Creates the instanced variant of pipeline `base_id`.
+    ///
+    /// The variant reads `vertex_layout` at slot 0 plus one [`InstanceData`]
+    /// (model matrix and color) per instance at slot 1, so objects sharing a
+    /// mesh are drawn with a single call. The instance data replaces the
+    /// per-object model uniform, so `bind_group_layouts` should omit it.
+    pub fn create_instanced_pipeline(
+        &mut self,
+        base_id: ResourceId,
+        shader_id: ResourceId,
+        vertex_layout: wgpu::VertexBufferLayout,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+        options: &PipelineOptions,
+    ) -> EngineResult<Arc<wgpu::RenderPipeline>> {
+        let shader = self.shaders.get(&shader_id).ok_or_else(|| {
+            EngineError::ResourceNotFound(format!("Shader not found: {:?}", shader_id))
+        })?;
+
+        let source = PipelineSource::new(
+            shader_id,
+            &vertex_layout,
+            self.surface_format,
+            bind_group_layouts,
+            options,
+            true,
+        );
+        let (pipeline, mirrored_pipeline) = self.build_pipelines(shader, &source);
+
+        let pipeline = Arc::new(pipeline);
+        self.instanced_pipelines.insert(base_id, pipeline.clone());
+        self.mirrored_instanced_pipelines
+            .insert(base_id, Arc::new(mirrored_pipeline));
+        self.instanced_pipeline_sources.insert(base_id, source);
+        Ok(pipeline)
+    }
+
+    /// Creates a compute pipeline running `entry_point` of shader `shader_id`.
+    ///
+    /// Unlike render pipelines, compute pipelines are not rebuilt by
+    /// [`ResourceManager::reload_shader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the shader is not registered.
+    #[allow(dead_code)]
+    pub fn create_compute_pipeline(
+        &mut self,
+        id: ResourceId,
+        shader_id: ResourceId,
+        entry_point: &str,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+    ) -> EngineResult<Arc<wgpu::ComputePipeline>> {
+        let shader = self.shaders.get(&shader_id).ok_or_else(|| {
+            EngineError::ResourceNotFound(format!("Shader not found: {:?}", shader_id))
+        })?;
+
         let pipeline_layout = self
             .device
             .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Render Pipeline Layout"),
+                label: Some("Compute Pipeline Layout"),
                 bind_group_layouts,
                 push_constant_ranges: &[],
             });
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Compute Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: shader,
+                entry_point: Some(entry_point),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                cache: None,
+            });
+
+        let pipeline = Arc::new(pipeline);
+        self.compute_pipelines.insert(id, pipeline.clone());
+        Ok(pipeline)
+    }
+
+    /// Creates the depth-only variant of pipeline `base_id` that renders its
+    /// objects into a shadow map.
+    ///
+    /// The variant reads the base pipeline's vertex layout, runs only the
+    /// `vs_main` entry point of `shader_id`, draws both faces (so mirrored
+    /// objects need no separate variant) and applies a slope-scaled depth
+    /// bias. It is not rebuilt by [`ResourceManager::reload_shader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the shader or base pipeline
+    /// is not registered.
+    pub fn create_shadow_pipeline(
+        &mut self,
+        base_id: ResourceId,
+        shader_id: ResourceId,
+        bind_group_layouts: &[&wgpu::BindGroupLayout],
+    ) -> EngineResult<Arc<wgpu::RenderPipeline>> {
+        let shader = self.shaders.get(&shader_id).ok_or_else(|| {
+            EngineError::ResourceNotFound(format!("Shader not found: {:?}", shader_id))
+        })?;
+        let source = self.pipeline_sources.get(&base_id).ok_or_else(|| {
+            EngineError::ResourceNotFound(format!("Pipeline not found: {:?}", base_id))
+        })?;
 
+        let pipeline_layout = self
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Shadow Pipeline Layout"),
+                bind_group_layouts,
+                push_constant_ranges: &[],
+            });
         let pipeline = self
             .device
             .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("Render Pipeline"),
+                label: Some("Shadow Pipeline"),
                 layout: Some(&pipeline_layout),
                 vertex: wgpu::VertexState {
                     module: shader,
                     entry_point: Some("vs_main"),
                     compilation_options: wgpu::PipelineCompilationOptions::default(),
-                    buffers: &[vertex_layout],
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: source.vertex_stride,
+                        step_mode: source.vertex_step_mode,
+                        attributes: &source.vertex_attributes,
+                    }],
                 },
-                fragment: Some(wgpu::FragmentState {
-                    module: shader,
-                    entry_point: Some("fs_main"),
-                    compilation_options: wgpu::PipelineCompilationOptions::default(),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: surface_format,
-                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                }),
+                fragment: None,
                 primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: Some(wgpu::Face::Back),
-                    unclipped_depth: false,
-                    polygon_mode: wgpu::PolygonMode::Fill,
-                    conservative: false,
-                },
-                depth_stencil: None,
-                multisample: wgpu::MultisampleState {
-                    count: 1,
-                    mask: !0,
-                    alpha_to_coverage_enabled: false,
+                    topology: source.options.topology,
+                    cull_mode: None,
+                    ..Default::default()
                 },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: SHADOW_FORMAT,
+                    depth_write_enabled: true,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    // 斜めの面ほど深度を奥へずらしてシャドウアクネを抑える
+                    bias: wgpu::DepthBiasState {
+                        constant: 2,
+                        slope_scale: 2.0,
+                        clamp: 0.0,
+                    },
+                }),
+                multisample: wgpu::MultisampleState::default(),
                 multiview: None,
                 cache: None,
             });
 
         let pipeline = Arc::new(pipeline);
-        self.pipelines.insert(id, pipeline.clone());
+        self.shadow_pipelines.insert(base_id, pipeline.clone());
         Ok(pipeline)
     }
 
+    /// Builds the regular and mirrored (clockwise-front) variants of a pipeline.
+    fn build_pipelines(
+        &self,
+        shader: &wgpu::ShaderModule,
+        source: &PipelineSource,
+    ) -> (wgpu::RenderPipeline, wgpu::RenderPipeline) {
+        let options = &source.options;
+        let conservative = conservative_rasterization(options.conservative, self.device.features());
+        let polygon_mode = polygon_mode(options.polygon_mode, self.device.features());
+
+        let bind_group_layouts: Vec<&wgpu::BindGroupLayout> =
+            source.bind_group_layouts.iter().collect();
+        let pipeline_layout = self
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &bind_group_layouts,
+                push_constant_ranges: &[],
+            });
+        let vertex_layouts = [
+            wgpu::VertexBufferLayout {
+                array_stride: source.vertex_stride,
+                step_mode: source.vertex_step_mode,
+                attributes: &source.vertex_attributes,
+            },
+            InstanceData::desc(),
+        ];
+        let vertex_layouts = if source.instanced {
+            &vertex_layouts[..]
+        } else {
+            &vertex_layouts[..1]
+        };
+
+        let build = |label: &str, front_face: wgpu::FrontFace| {
+            self.device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(&pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: shader,
+                        entry_point: Some("vs_main"),
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                        buffers: vertex_layouts,
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: shader,
+                        entry_point: Some("fs_main"),
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: source.surface_format,
+                            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        topology: options.topology,
+                        strip_index_format: None,
+                        front_face,
+                        cull_mode: options.cull_mode,
+                        unclipped_depth: false,
+                        polygon_mode,
+                        conservative,
+                    },
+                    depth_stencil: options.depth_format.map(|format| wgpu::DepthStencilState {
+                        format,
+                        depth_write_enabled: true,
+                        depth_compare: wgpu::CompareFunction::Less,
+                        stencil: wgpu::StencilState::default(),
+                        bias: wgpu::DepthBiasState::default(),
+                    }),
+                    multisample: wgpu::MultisampleState {
+                        count: options.sample_count,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    multiview: None,
+                    cache: None,
+                })
+        };
+
+        let pipeline = build("Render Pipeline", options.front_face);
+        // 負の行列式（鏡映）のオブジェクト用に巻き順を反転したバリアント
+        let mirrored_pipeline = build(
+            "Mirrored Render Pipeline",
+            flipped_front_face(options.front_face),
+        );
+        (pipeline, mirrored_pipeline)
+    }
+
+    /// Creates a bind group layout with a single uniform buffer at binding 0.
+    pub fn create_uniform_bind_group_layout(
+        &self,
+        label: &str,
+        visibility: wgpu::ShaderStages,
+    ) -> wgpu::BindGroupLayout {
+        self.create_uniforms_bind_group_layout(label, &[visibility])
+    }
+
+    /// Creates a layout with one uniform buffer per entry of `visibilities`,
+    /// at bindings 0, 1, ... in order.
+    pub fn create_uniforms_bind_group_layout(
+        &self,
+        label: &str,
+        visibilities: &[wgpu::ShaderStages],
+    ) -> wgpu::BindGroupLayout {
+        let entries: Vec<_> = visibilities
+            .iter()
+            .enumerate()
+            .map(|(binding, &visibility)| wgpu::BindGroupLayoutEntry {
+                binding: binding as u32,
+                visibility,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            })
+            .collect();
+        self.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &entries,
+            })
+    }
+
     pub fn create_bind_group(
         &mut self,
         id: ResourceId,
@@ -218,6 +753,99 @@ impl ResourceManager {
         Ok(arc_bind_group)
     }
 
+    /// Decodes a PNG/JPEG image and uploads it as a sampled texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::TextureLoad` if the image cannot be decoded.
+    pub fn create_texture(
+        &mut self,
+        id: ResourceId,
+        bytes: &[u8],
+        label: Option<&str>,
+    ) -> EngineResult<Arc<Texture>> {
+        let texture = Arc::new(Texture::from_bytes(
+            &self.device,
+            &self.queue,
+            bytes,
+            label,
+        )?);
+        self.textures.insert(id, texture.clone());
+        Ok(texture)
+    }
+
+    /// Creates the light bind group layout of lit pipelines: the light
+    /// uniform at binding 0, the [`ShadowUniform`](crate::resources::uniforms::ShadowUniform)
+    /// at binding 1, and the shadow map depth texture and its comparison
+    /// sampler at bindings 2 and 3, all visible to the fragment stage.
+    pub fn create_light_bind_group_layout(&self, label: &str) -> wgpu::BindGroupLayout {
+        let uniform = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        self.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    uniform(0),
+                    uniform(1),
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                        count: None,
+                    },
+                ],
+            })
+    }
+
+    /// Creates a bind group layout with a filterable 2D texture at binding 0
+    /// and its sampler at binding 1, visible to the fragment stage.
+    pub fn create_texture_bind_group_layout(&self, label: &str) -> wgpu::BindGroupLayout {
+        self.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some(label),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            })
+    }
+
+    pub fn register_texture(&mut self, id: ResourceId, texture: Arc<Texture>) {
+        self.textures.insert(id, texture);
+    }
+
     pub fn register_mesh(&mut self, id: ResourceId, mesh: Arc<Mesh>) {
         self.buffers.insert(
             ResourceId::new(&format!("{}_vertex", id.0)),
@@ -232,26 +860,216 @@ impl ResourceManager {
         self.meshes.insert(id, mesh);
     }
 
+    /// Creates a mesh whose vertices can later be rewritten with
+    /// [`ResourceManager::update_mesh_vertices`] and registers it as `id`.
+    #[allow(dead_code)]
+    pub fn create_dynamic_mesh<V: VertexTrait, I: MeshIndex>(
+        &mut self,
+        id: ResourceId,
+        vertices: &[V],
+        indices: Option<&[I]>,
+    ) -> Arc<Mesh> {
+        let mesh = Arc::new(Mesh::new_dynamic(self.device.clone(), vertices, indices));
+        self.register_mesh(id, mesh.clone());
+        mesh
+    }
+
+    /// Uploads new vertices to the dynamic mesh `id`.
+    ///
+    /// The GPU buffer is shared, but bounds and `vertex_count` live in the
+    /// registered `Mesh`; `Arc`s obtained earlier through `get_mesh` keep the
+    /// old values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if no mesh `id` is registered
+    /// and `EngineError::MeshUpdate` if it is not dynamic or too small.
+    #[allow(dead_code)]
+    pub fn update_mesh_vertices<V: VertexTrait>(
+        &mut self,
+        id: ResourceId,
+        vertices: &[V],
+    ) -> EngineResult<()> {
+        let mesh = self
+            .meshes
+            .get_mut(&id)
+            .ok_or_else(|| EngineError::ResourceNotFound(format!("Mesh not found: {:?}", id)))?;
+        Arc::make_mut(mesh).update_vertices(&self.queue, vertices)
+    }
+
+    /// Unregisters mesh `id` together with its vertex and index buffers.
+    ///
+    /// Returns `true` if a mesh was removed. GPU memory is freed once the
+    /// last `Arc` (e.g. held by an in-flight frame) is dropped.
+    pub fn remove_mesh(&mut self, id: &ResourceId) -> bool {
+        self.buffers
+            .remove(&ResourceId::new(&format!("{}_vertex", id.0)));
+        self.buffers
+            .remove(&ResourceId::new(&format!("{}_index", id.0)));
+        self.mesh_refs.remove(id);
+        self.meshes.remove(id).is_some()
+    }
+
+    /// Records one more holder of mesh `id`; pair with `release_mesh`.
+    ///
+    /// Scenes sharing this manager register primitive meshes under the same
+    /// ids, so a mesh is only unregistered once every holder released it.
+    pub fn retain_mesh(&mut self, id: ResourceId) {
+        *self.mesh_refs.entry(id).or_default() += 1;
+    }
+
+    /// Drops one holder of mesh `id` taken with `retain_mesh` and unregisters
+    /// the mesh when none are left.
+    ///
+    /// Returns `true` if the mesh was removed. Meshes that were never
+    /// retained (e.g. loaded with `load_obj`) are left alone.
+    pub fn release_mesh(&mut self, id: &ResourceId) -> bool {
+        let Some(count) = self.mesh_refs.get_mut(id) else {
+            return false;
+        };
+        *count -= 1;
+        if *count > 0 {
+            return false;
+        }
+        self.remove_mesh(id)
+    }
+
+    pub fn remove_buffer(&mut self, id: &ResourceId) -> bool {
+        self.buffers.remove(id).is_some()
+    }
+
+    /// Unregisters pipeline `id` and its mirrored, instanced and shadow
+    /// variants, if any.
+    #[allow(dead_code)]
+    pub fn remove_pipeline(&mut self, id: &ResourceId) -> bool {
+        self.shadow_pipelines.remove(id);
+        self.mirrored_pipelines.remove(id);
+        self.instanced_pipelines.remove(id);
+        self.mirrored_instanced_pipelines.remove(id);
+        self.instanced_pipeline_sources.remove(id);
+        self.pipelines.remove(id).is_some()
+    }
+
+    pub fn remove_bind_group(&mut self, id: &ResourceId) -> bool {
+        self.bind_groups.remove(id).is_some()
+    }
+
+    /// Loads a Wavefront OBJ file, corrects and optionally welds it with
+    /// `options`, uploads it and registers it as mesh `id`.
+    ///
+    /// Indices are stored as `u16` when every vertex fits, `u32` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ResourceNotFound` if the file cannot be read and
+    /// `EngineError::MeshLoad` if it is malformed.
+    pub fn load_obj(
+        &mut self,
+        id: ResourceId,
+        path: impl AsRef<std::path::Path>,
+        options: &ImportOptions,
+    ) -> EngineResult<Arc<Mesh>> {
+        let obj_mesh = obj::load_obj(path, options)?;
+
+        let mesh = match obj_mesh.indices_u16() {
+            Some(indices) => Mesh::new(self.device.clone(), &obj_mesh.vertices, Some(&indices)),
+            None => Mesh::new(
+                self.device.clone(),
+                &obj_mesh.vertices,
+                Some(&obj_mesh.indices),
+            ),
+        };
+        let mesh = Arc::new(mesh);
+        self.register_mesh(id, mesh.clone());
+        Ok(mesh)
+    }
+
     pub fn get_device(&self) -> Arc<wgpu::Device> {
         self.device.clone()
     }
 
+    pub fn get_queue(&self) -> Arc<wgpu::Queue> {
+        self.queue.clone()
+    }
+
     pub fn get_surface_format(&self) -> wgpu::TextureFormat {
         self.surface_format
     }
 
+    pub fn get_sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    pub fn get_depth_format(&self) -> Option<wgpu::TextureFormat> {
+        self.depth_format
+    }
+
     pub fn get_pipeline(&self, id: &ResourceId) -> Option<Arc<wgpu::RenderPipeline>> {
         self.pipelines.get(id).cloned()
     }
 
+    /// Returns the winding-reversed variant of a pipeline, for objects whose
+    /// transform has a negative determinant.
+    pub fn get_mirrored_pipeline(&self, id: &ResourceId) -> Option<Arc<wgpu::RenderPipeline>> {
+        self.mirrored_pipelines.get(id).cloned()
+    }
+
+    /// Returns the instanced variant of pipeline `id`, if one was created;
+    /// `mirrored` selects the winding-reversed version.
+    pub fn get_instanced_pipeline(
+        &self,
+        id: &ResourceId,
+        mirrored: bool,
+    ) -> Option<Arc<wgpu::RenderPipeline>> {
+        let pipelines = if mirrored {
+            &self.mirrored_instanced_pipelines
+        } else {
+            &self.instanced_pipelines
+        };
+        pipelines.get(id).cloned()
+    }
+
+    /// Returns the depth-only shadow variant of pipeline `id`, if one was created.
+    pub fn get_shadow_pipeline(&self, id: &ResourceId) -> Option<Arc<wgpu::RenderPipeline>> {
+        self.shadow_pipelines.get(id).cloned()
+    }
+
+    #[allow(dead_code)]
+    pub fn get_compute_pipeline(&self, id: &ResourceId) -> Option<Arc<wgpu::ComputePipeline>> {
+        self.compute_pipelines.get(id).cloned()
+    }
+
     pub fn get_mesh(&self, id: &ResourceId) -> Option<Arc<Mesh>> {
         self.meshes.get(id).cloned()
     }
+
+    pub fn get_texture(&self, id: &ResourceId) -> Option<Arc<Texture>> {
+        self.textures.get(id).cloned()
+    }
+
+    /// Returns how many resources of each kind are registered.
+    pub fn stats(&self) -> ResourceStats {
+        ResourceStats {
+            buffers: self.buffers.len(),
+            pipelines: self.pipelines.len(),
+            shaders: self.shaders.len(),
+            meshes: self.meshes.len(),
+            bind_groups: self.bind_groups.len(),
+            textures: self.textures.len(),
+        }
+    }
+}
+
+fn read_shader_source(path: &Path) -> EngineResult<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        EngineError::ResourceNotFound(format!("Failed to read {}: {}", path.display(), e))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resources::primitives::{Primitive, quad::Quad};
 
     #[test]
     fn test_resource_id_consistency() {
@@ -279,8 +1097,236 @@ mod tests {
         let id1 = ResourceId::new("日本語シェーダー");
         let id2 = ResourceId::new("日本語シェーダー");
         let id3 = ResourceId::new("English_Shader");
-        
+
         assert_eq!(id1, id2, "Unicode文字列でも一貫性を保つべき");
         assert_ne!(id1, id3, "異なるUnicode文字列は異なるIDになるべき");
     }
+
+    const TEST_SHADER: &str = "
+        @vertex
+        fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+            return vec4<f32>(position, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0);
+        }
+    ";
+
+    #[test]
+    fn test_reload_shader_keeps_old_pipeline_on_error() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), TEST_SHADER).unwrap();
+
+        let shader_id = ResourceId::new("test_shader");
+        let pipeline_id = ResourceId::new("test_pipeline");
+        manager
+            .create_shader_from_path(shader_id, file.path(), Some("Test Shader"))
+            .unwrap();
+        manager
+            .create_pipeline(
+                pipeline_id,
+                shader_id,
+                wgpu::VertexBufferLayout {
+                    array_stride: 12,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3],
+                },
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                &[],
+                &PipelineOptions::default(),
+            )
+            .unwrap();
+        let original = manager.get_pipeline(&pipeline_id).unwrap();
+
+        // 壊れたWGSLではエラーを返し、以前のパイプラインを維持する
+        std::fs::write(file.path(), "fn broken(").unwrap();
+        let result = manager.reload_shader(shader_id);
+        assert!(matches!(result, Err(EngineError::ShaderCompilation(_))));
+        assert!(Arc::ptr_eq(
+            &original,
+            &manager.get_pipeline(&pipeline_id).unwrap()
+        ));
+
+        // 修正後は依存パイプラインが再構築される
+        std::fs::write(file.path(), TEST_SHADER).unwrap();
+        assert_eq!(manager.reload_shader(shader_id).unwrap(), 1);
+        assert!(!Arc::ptr_eq(
+            &original,
+            &manager.get_pipeline(&pipeline_id).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_reload_requires_file_backed_shader() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let shader_id = ResourceId::new("embedded_shader");
+        manager.create_shader(shader_id, TEST_SHADER, None).unwrap();
+
+        let result = manager.reload_shader(shader_id);
+
+        assert!(matches!(result, Err(EngineError::ResourceNotFound(_))));
+    }
+
+    #[test]
+    fn test_update_mesh_vertices_refreshes_bounds() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let id = ResourceId::new("dynamic_quad");
+        let mut vertices = Quad::create_vertices();
+        let indices = Quad::create_indices();
+        manager.create_dynamic_mesh(id, &vertices, indices.as_deref());
+
+        for vertex in &mut vertices {
+            vertex.position[0] *= 4.0;
+        }
+        manager.update_mesh_vertices(id, &vertices).unwrap();
+
+        let mesh = manager.get_mesh(&id).unwrap();
+        assert_eq!(mesh.aabb_max.x, 2.0, "更新後の頂点から境界を再計算する");
+        assert!(Arc::ptr_eq(
+            &mesh.vertex_buffer,
+            &manager.buffers[&ResourceId::new(&format!("{}_vertex", id.0))]
+        ));
+    }
+
+    #[test]
+    fn test_update_missing_mesh_is_not_found() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+
+        let result =
+            manager.update_mesh_vertices(ResourceId::new("missing"), &Quad::create_vertices());
+
+        assert!(matches!(result, Err(EngineError::ResourceNotFound(_))));
+    }
+
+    #[test]
+    fn test_compute_pipeline_requires_registered_shader() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let id = ResourceId::new("compute_pipeline");
+
+        let result =
+            manager.create_compute_pipeline(id, ResourceId::new("missing"), "cs_main", &[]);
+
+        assert!(matches!(result, Err(EngineError::ResourceNotFound(_))));
+        assert!(manager.get_compute_pipeline(&id).is_none());
+    }
+
+    #[test]
+    fn test_storage_buffer_reads_back_contents() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let id = ResourceId::new("storage");
+        let values = [1u32, 2, 3, 0xdead_beef];
+        let buffer = manager.create_storage_buffer(id, &values, true).unwrap();
+        assert!(buffer.usage().contains(wgpu::BufferUsages::STORAGE));
+
+        let bytes = manager.read_buffer(id).unwrap();
+        assert_eq!(bytemuck::cast_slice::<u8, u32>(&bytes), values);
+
+        // CPU からの書き換えも読み戻しに反映される
+        context
+            .queue
+            .write_buffer(&buffer, 4, bytemuck::cast_slice(&[42u32]));
+        let bytes = manager.read_buffer(id).unwrap();
+        assert_eq!(
+            bytemuck::cast_slice::<u8, u32>(&bytes),
+            [1, 42, 3, 0xdead_beef]
+        );
+    }
+
+    #[test]
+    fn test_read_buffer_requires_copy_src() {
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let id = ResourceId::new("write_only_storage");
+        manager
+            .create_storage_buffer(id, &[0u32; 4], false)
+            .unwrap();
+
+        assert!(matches!(
+            manager.read_buffer(id),
+            Err(EngineError::BufferRead(_))
+        ));
+        assert!(matches!(
+            manager.read_buffer(ResourceId::new("missing")),
+            Err(EngineError::ResourceNotFound(_))
+        ));
+    }
 }
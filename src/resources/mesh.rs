@@ -2,23 +2,153 @@ use std::sync::Arc;
 
 use wgpu::util::DeviceExt;
 
+use crate::{
+    core::error::{EngineError, EngineResult},
+    resources::vertex::VertexTrait,
+};
+
+/// Integer type usable in an index buffer.
+pub trait MeshIndex: bytemuck::Pod {
+    const FORMAT: wgpu::IndexFormat;
+
+    fn to_usize(self) -> usize;
+}
+
+impl MeshIndex for u16 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint16;
+
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+/// Needed once a mesh has more than 65 536 vertices.
+impl MeshIndex for u32 {
+    const FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint32;
+
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: glam::Vec3,
+    pub max: glam::Vec3,
+}
+
+impl Aabb {
+    /// Smallest box containing `points`; an empty iterator gives a
+    /// zero-sized box at the origin.
+    pub fn from_points(points: impl IntoIterator<Item = glam::Vec3>) -> Self {
+        let mut points = points.into_iter();
+        let Some(first) = points.next() else {
+            return Self {
+                min: glam::Vec3::ZERO,
+                max: glam::Vec3::ZERO,
+            };
+        };
+        points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |aabb, point| Self {
+                min: aabb.min.min(point),
+                max: aabb.max.max(point),
+            },
+        )
+    }
+
+    /// Box enclosing this one after `matrix` is applied.
+    ///
+    /// Rotated boxes grow to stay axis-aligned, so the result may be larger
+    /// than the transformed geometry.
+    pub fn transformed(&self, matrix: glam::Mat4) -> Self {
+        let center = matrix.transform_point3((self.min + self.max) * 0.5);
+        let half_extent = (self.max - self.min) * 0.5;
+        let linear = glam::Mat3::from_mat4(matrix);
+        // 各軸の寄与の絶対値を足すと、8頂点を変換した最小・最大と一致する
+        let half_extent = linear.x_axis.abs() * half_extent.x
+            + linear.y_axis.abs() * half_extent.y
+            + linear.z_axis.abs() * half_extent.z;
+
+        Self {
+            min: center - half_extent,
+            max: center + half_extent,
+        }
+    }
+
+    /// Distance along the ray to the first point inside the box, or `None`
+    /// if the ray misses it. A ray starting inside the box hits at 0.
+    ///
+    /// `direction` need not be normalized; the result is in its units.
+    pub fn intersect_ray(&self, origin: glam::Vec3, direction: glam::Vec3) -> Option<f32> {
+        // スラブ法: 各軸の2平面との交差区間の共通部分を求める
+        let inverse = direction.recip();
+        let t1 = (self.min - origin) * inverse;
+        let t2 = (self.max - origin) * inverse;
+        let t_near = t1.min(t2).max_element().max(0.0);
+        let t_far = t1.max(t2).min_element();
+
+        (t_near <= t_far).then_some(t_near)
+    }
+}
+
+#[derive(Clone)]
 pub struct Mesh {
     pub vertex_buffer: Arc<wgpu::Buffer>,
     pub index_buffer: Option<Arc<wgpu::Buffer>>,
+    /// Format of `index_buffer`, taken from the index type the mesh was built with.
+    pub index_format: wgpu::IndexFormat,
     pub vertex_count: u32,
     pub index_count: u32,
+    /// Distance from the object-space origin to the farthest vertex, used for
+    /// bounding-sphere culling.
+    pub bounding_radius: f32,
+    /// Object-space bounds of the vertex positions.
+    pub aabb_min: glam::Vec3,
+    pub aabb_max: glam::Vec3,
 }
 
 impl Mesh {
-    pub fn new<V: bytemuck::Pod>(
+    pub fn new<V: VertexTrait, I: MeshIndex>(
+        device: Arc<wgpu::Device>,
+        vertices: &[V],
+        indices: Option<&[I]>,
+    ) -> Self {
+        Self::with_vertex_usage(device, vertices, indices, wgpu::BufferUsages::VERTEX)
+    }
+
+    /// Creates a mesh whose vertices can be rewritten with
+    /// [`Mesh::update_vertices`] (animated or procedural geometry).
+    ///
+    /// The initial vertex count is the capacity; updates may shrink the mesh
+    /// but not grow it.
+    pub fn new_dynamic<V: VertexTrait, I: MeshIndex>(
         device: Arc<wgpu::Device>,
         vertices: &[V],
-        indices: Option<&[u16]>,
+        indices: Option<&[I]>,
+    ) -> Self {
+        Self::with_vertex_usage(
+            device,
+            vertices,
+            indices,
+            wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        )
+    }
+
+    fn with_vertex_usage<V: VertexTrait, I: MeshIndex>(
+        device: Arc<wgpu::Device>,
+        vertices: &[V],
+        indices: Option<&[I]>,
+        usage: wgpu::BufferUsages,
     ) -> Self {
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Vertex Buffer"),
             contents: bytemuck::cast_slice(vertices),
-            usage: wgpu::BufferUsages::VERTEX,
+            usage,
         });
 
         let (index_buffer, index_count) = if let Some(indices) = indices {
@@ -33,11 +163,424 @@ impl Mesh {
             (None, 0)
         };
 
-        Self {
+        let mut mesh = Self {
             vertex_buffer: Arc::new(vertex_buffer),
             index_buffer,
+            index_format: I::FORMAT,
             vertex_count: vertices.len() as u32,
             index_count,
+            bounding_radius: 0.0,
+            aabb_min: glam::Vec3::ZERO,
+            aabb_max: glam::Vec3::ZERO,
+        };
+        mesh.update_bounds(vertices);
+        mesh
+    }
+
+    /// Overwrites the vertex buffer of a mesh created with
+    /// [`Mesh::new_dynamic`] and recomputes its bounds.
+    ///
+    /// Fewer vertices than before shrink `vertex_count`; indices are left
+    /// unchanged, so they must stay within the new vertex count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::MeshUpdate` if the mesh is not dynamic or the
+    /// vertices exceed the buffer's capacity.
+    pub fn update_vertices<V: VertexTrait>(
+        &mut self,
+        queue: &wgpu::Queue,
+        vertices: &[V],
+    ) -> EngineResult<()> {
+        if !self
+            .vertex_buffer
+            .usage()
+            .contains(wgpu::BufferUsages::COPY_DST)
+        {
+            return Err(EngineError::MeshUpdate(
+                "mesh was not created with Mesh::new_dynamic".to_string(),
+            ));
+        }
+        let bytes: &[u8] = bytemuck::cast_slice(vertices);
+        if bytes.len() as wgpu::BufferAddress > self.vertex_buffer.size() {
+            return Err(EngineError::MeshUpdate(format!(
+                "{} bytes of vertices exceed the buffer capacity of {} bytes",
+                bytes.len(),
+                self.vertex_buffer.size()
+            )));
+        }
+
+        // write_buffer のサイズは4バイト単位である必要がある（頂点は f32 の並びなので満たす）
+        if !bytes.is_empty() {
+            queue.write_buffer(&self.vertex_buffer, 0, bytes);
+        }
+        self.vertex_count = vertices.len() as u32;
+        self.update_bounds(vertices);
+        Ok(())
+    }
+
+    fn update_bounds<V: VertexTrait>(&mut self, vertices: &[V]) {
+        let positions = vertices
+            .iter()
+            .map(|vertex| glam::Vec3::from(vertex.position()));
+        self.bounding_radius = positions
+            .clone()
+            .map(glam::Vec3::length)
+            .fold(0.0, f32::max);
+        let aabb = Aabb::from_points(positions);
+        self.aabb_min = aabb.min;
+        self.aabb_max = aabb.max;
+    }
+
+    /// Object-space bounding box of the mesh.
+    pub fn aabb(&self) -> Aabb {
+        Aabb {
+            min: self.aabb_min,
+            max: self.aabb_max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graphics::context::GpuContext,
+        resources::primitives::{Primitive, cube::Cube, quad::Quad},
+    };
+    use bytemuck::{Pod, Zeroable};
+
+    /// Clip-space 2D position, enough for the test shader.
+    #[repr(C)]
+    #[derive(Copy, Clone, Pod, Zeroable)]
+    struct Vertex2D([f32; 2]);
+
+    impl VertexTrait for Vertex2D {
+        fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+            const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x2];
+            wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<Vertex2D>() as wgpu::BufferAddress,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &ATTRIBUTES,
+            }
+        }
+
+        fn position(&self) -> [f32; 3] {
+            [self.0[0], self.0[1], 0.0]
+        }
+    }
+
+    const TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+    /// `bytes_per_row` must be a multiple of 256, so 64 RGBA8 pixels wide.
+    const TARGET_SIZE: u32 = 64;
+
+    const SHADER: &str = "
+        @vertex
+        fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
+            return vec4<f32>(position, 0.0, 1.0);
         }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+        }
+    ";
+
+    /// Draws `mesh` in white over black and returns the center pixel.
+    fn render_center_pixel(context: &GpuContext, mesh: &Mesh) -> [u8; 4] {
+        let device = &context.device;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Mesh Test Shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Mesh Test Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[Vertex2D::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(TARGET_FORMAT.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let size = wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Mesh Test Target"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TARGET_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Mesh Test Readback"),
+            size: (TARGET_SIZE * TARGET_SIZE * 4) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Mesh Test Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&pipeline);
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            let index_buffer = mesh
+                .index_buffer
+                .as_ref()
+                .expect("インデックスバッファがない");
+            render_pass.set_index_buffer(index_buffer.slice(..), mesh.index_format);
+            render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+        }
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(TARGET_SIZE * 4),
+                    rows_per_image: Some(TARGET_SIZE),
+                },
+            },
+            size,
+        );
+        context.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| result.expect("マップに失敗"));
+        device.poll(wgpu::PollType::Wait).expect("ポーリングに失敗");
+        let pixels = slice.get_mapped_range();
+        let center = ((TARGET_SIZE / 2) * TARGET_SIZE + TARGET_SIZE / 2) as usize * 4;
+        [
+            pixels[center],
+            pixels[center + 1],
+            pixels[center + 2],
+            pixels[center + 3],
+        ]
+    }
+
+    #[test]
+    fn test_u32_indices_beyond_u16_range_render() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+
+        // 先頭 65536 頂点は原点に潰れており、画面を覆う三角形はその後ろにある。
+        // インデックスが u16 に丸められると潰れた三角形を描いて真っ黒になる。
+        let mut vertices = vec![Vertex2D([0.0, 0.0]); u16::MAX as usize + 1];
+        vertices.extend([
+            Vertex2D([-1.0, -1.0]),
+            Vertex2D([3.0, -1.0]),
+            Vertex2D([-1.0, 3.0]),
+        ]);
+        let first = u16::MAX as u32 + 1;
+        let indices = [first, first + 1, first + 2];
+
+        let mesh = Mesh::new(context.device.clone(), &vertices, Some(&indices));
+
+        assert_eq!(mesh.index_format, wgpu::IndexFormat::Uint32);
+        assert_eq!(mesh.vertex_count, u16::MAX as u32 + 4);
+        assert_eq!(render_center_pixel(&context, &mesh), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_bounding_radius_reaches_farthest_vertex() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+
+        let vertices = [
+            Vertex2D([0.5, 0.0]),
+            Vertex2D([3.0, 4.0]),
+            Vertex2D([-1.0, -1.0]),
+        ];
+        let mesh = Mesh::new(context.device.clone(), &vertices, Some(&[0u16, 1, 2]));
+
+        assert!(
+            (mesh.bounding_radius - 5.0).abs() < 1e-5,
+            "半径が最遠頂点までの距離でない"
+        );
+    }
+
+    #[test]
+    fn test_cube_aabb_is_half_unit() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+
+        let mesh = Cube::create_mesh(context.device.clone());
+
+        assert_eq!(
+            mesh.aabb(),
+            Aabb {
+                min: glam::Vec3::splat(-0.5),
+                max: glam::Vec3::splat(0.5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_aabb_from_no_points_is_empty_at_origin() {
+        let aabb = Aabb::from_points(std::iter::empty());
+        assert_eq!(aabb.min, glam::Vec3::ZERO);
+        assert_eq!(aabb.max, glam::Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_transformed_aabb_grows_when_rotated() {
+        let aabb = Aabb {
+            min: glam::Vec3::splat(-0.5),
+            max: glam::Vec3::splat(0.5),
+        };
+        let matrix = glam::Mat4::from_rotation_translation(
+            glam::Quat::from_rotation_y(std::f32::consts::FRAC_PI_4),
+            glam::vec3(1.0, 0.0, 0.0),
+        );
+
+        let transformed = aabb.transformed(matrix);
+
+        // Y軸まわりに45°回すと XZ 方向の半径は √2/2 になる
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert!(
+            transformed
+                .min
+                .abs_diff_eq(glam::vec3(1.0 - half, -0.5, -half), 1e-5)
+        );
+        assert!(
+            transformed
+                .max
+                .abs_diff_eq(glam::vec3(1.0 + half, 0.5, half), 1e-5)
+        );
+    }
+
+    #[test]
+    fn test_ray_hits_box_at_near_face() {
+        let aabb = Aabb {
+            min: glam::Vec3::splat(-0.5),
+            max: glam::Vec3::splat(0.5),
+        };
+
+        let t = aabb.intersect_ray(glam::vec3(0.0, 0.0, 3.0), glam::Vec3::NEG_Z);
+        assert_eq!(t, Some(2.5));
+        // 箱の中から撃つと距離0
+        assert_eq!(
+            aabb.intersect_ray(glam::Vec3::ZERO, glam::Vec3::X),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_ray_misses_box() {
+        let aabb = Aabb {
+            min: glam::Vec3::splat(-0.5),
+            max: glam::Vec3::splat(0.5),
+        };
+
+        assert_eq!(
+            aabb.intersect_ray(glam::vec3(1.0, 0.0, 3.0), glam::Vec3::NEG_Z),
+            None,
+            "横を通り過ぎる"
+        );
+        assert_eq!(
+            aabb.intersect_ray(glam::vec3(0.0, 0.0, 3.0), glam::Vec3::Z),
+            None,
+            "箱は背後にある"
+        );
+    }
+
+    #[test]
+    fn test_dynamic_quad_update_changes_vertex_count() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let vertices = Quad::create_vertices();
+        let indices = Quad::create_indices();
+        let mut mesh = Mesh::new_dynamic(context.device.clone(), &vertices, indices.as_deref());
+
+        let mut moved = vertices.clone();
+        for vertex in &mut moved {
+            vertex.position[1] += 1.0;
+        }
+        mesh.update_vertices(&context.queue, &moved).unwrap();
+        assert_eq!(mesh.vertex_count, 4);
+        assert_eq!(mesh.aabb_max.y, 1.5);
+
+        // 容量以下なら頂点数を減らせる
+        mesh.update_vertices(&context.queue, &moved[..3]).unwrap();
+        assert_eq!(mesh.vertex_count, 3);
+        context
+            .device
+            .poll(wgpu::PollType::Wait)
+            .expect("ポーリングに失敗");
+    }
+
+    #[test]
+    fn test_dynamic_mesh_rejects_growth_beyond_capacity() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let vertices = Quad::create_vertices();
+        let mut mesh = Mesh::new_dynamic(
+            context.device.clone(),
+            &vertices,
+            Quad::create_indices().as_deref(),
+        );
+
+        let mut grown = vertices.clone();
+        grown.push(vertices[0]);
+        let result = mesh.update_vertices(&context.queue, &grown);
+
+        assert!(matches!(result, Err(EngineError::MeshUpdate(_))));
+        assert_eq!(mesh.vertex_count, 4, "失敗時は変更しない");
+    }
+
+    #[test]
+    fn test_static_mesh_cannot_be_updated() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mut mesh = Quad::create_mesh(context.device.clone());
+
+        let result = mesh.update_vertices(&context.queue, &Quad::create_vertices());
+
+        assert!(matches!(result, Err(EngineError::MeshUpdate(_))));
     }
 }
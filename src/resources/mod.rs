@@ -1,5 +1,9 @@
+pub mod import;
 pub mod manager;
 pub mod mesh;
+pub mod obj;
+pub mod pipeline;
 pub mod primitives;
+pub mod texture;
 pub mod uniforms;
 pub mod vertex;
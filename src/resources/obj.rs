@@ -0,0 +1,338 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{
+    core::error::{EngineError, EngineResult},
+    resources::{
+        import::{ImportOptions, WeldedMesh},
+        vertex::NormalVertex,
+    },
+};
+
+/// Vertex color of imported meshes; OBJ geometry carries no color.
+const OBJ_VERTEX_COLOR: [f32; 3] = [0.8, 0.8, 0.8];
+
+/// Triangulated geometry parsed from a Wavefront OBJ file.
+#[derive(Debug, Clone)]
+pub struct ObjMesh {
+    pub vertices: Vec<NormalVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl ObjMesh {
+    /// Applies the axis/scale correction of `options` and then its optional
+    /// welding step.
+    pub fn import(self, options: &ImportOptions) -> WeldedMesh<NormalVertex> {
+        let (mut positions, mut normals): (Vec<_>, Vec<_>) = self
+            .vertices
+            .iter()
+            .map(|vertex| (vertex.position, vertex.normal))
+            .unzip();
+        let mut indices = self.indices;
+        options.apply(&mut positions, &mut normals, &mut indices);
+
+        let vertices = positions
+            .into_iter()
+            .zip(normals)
+            .map(|(position, normal)| NormalVertex {
+                position,
+                normal,
+                color: OBJ_VERTEX_COLOR,
+            })
+            .collect();
+        options.weld(vertices, indices)
+    }
+}
+
+/// Reads and parses the OBJ file at `path` and imports it with `options`.
+///
+/// # Errors
+///
+/// Returns `EngineError::ResourceNotFound` if the file cannot be read and
+/// `EngineError::MeshLoad` if it is malformed.
+pub fn load_obj(
+    path: impl AsRef<Path>,
+    options: &ImportOptions,
+) -> EngineResult<WeldedMesh<NormalVertex>> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path).map_err(|e| {
+        EngineError::ResourceNotFound(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    Ok(parse_obj(&source)?.import(options))
+}
+
+/// Parses OBJ source: `v`, `vn` and `f` statements are used, everything else
+/// (texture coordinates, groups, materials) is ignored.
+///
+/// Polygons are triangulated as fans. Face corners without a normal get the
+/// face's geometric normal, so meshes exported without normals are flat shaded.
+///
+/// # Errors
+///
+/// Returns `EngineError::MeshLoad` on malformed numbers or out-of-range indices.
+pub fn parse_obj(source: &str) -> EngineResult<ObjMesh> {
+    let mut positions: Vec<glam::Vec3> = Vec::new();
+    let mut normals: Vec<glam::Vec3> = Vec::new();
+    let mut mesh = ObjMesh {
+        vertices: Vec::new(),
+        indices: Vec::new(),
+    };
+    // (位置, 法線) の組で頂点を共有する
+    let mut shared: HashMap<(usize, usize), u32> = HashMap::new();
+
+    for (line_number, line) in source.lines().enumerate() {
+        let line_number = line_number + 1;
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => positions.push(parse_vec3(tokens, line_number)?),
+            Some("vn") => normals.push(parse_vec3(tokens, line_number)?.normalize_or_zero()),
+            Some("f") => {
+                let corners = tokens
+                    .map(|token| parse_corner(token, positions.len(), normals.len(), line_number))
+                    .collect::<EngineResult<Vec<_>>>()?;
+                if corners.len() < 3 {
+                    return Err(mesh_error(line_number, "face has fewer than 3 vertices"));
+                }
+
+                let face_normal = (positions[corners[1].0] - positions[corners[0].0])
+                    .cross(positions[corners[2].0] - positions[corners[0].0])
+                    .normalize_or_zero();
+
+                let mut face_indices = Vec::with_capacity(corners.len());
+                for (position, normal) in corners {
+                    let index = match normal {
+                        Some(normal) => *shared.entry((position, normal)).or_insert_with(|| {
+                            push_vertex(&mut mesh.vertices, positions[position], normals[normal])
+                        }),
+                        None => push_vertex(&mut mesh.vertices, positions[position], face_normal),
+                    };
+                    face_indices.push(index);
+                }
+
+                for i in 1..face_indices.len() - 1 {
+                    mesh.indices
+                        .extend([face_indices[0], face_indices[i], face_indices[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(mesh)
+}
+
+fn push_vertex(vertices: &mut Vec<NormalVertex>, position: glam::Vec3, normal: glam::Vec3) -> u32 {
+    vertices.push(NormalVertex {
+        position: position.to_array(),
+        normal: normal.to_array(),
+        color: OBJ_VERTEX_COLOR,
+    });
+    (vertices.len() - 1) as u32
+}
+
+fn mesh_error(line_number: usize, message: &str) -> EngineError {
+    EngineError::MeshLoad(format!("line {}: {}", line_number, message))
+}
+
+fn parse_vec3<'a>(
+    tokens: impl Iterator<Item = &'a str>,
+    line_number: usize,
+) -> EngineResult<glam::Vec3> {
+    let values = tokens
+        .take(3)
+        .map(|token| {
+            token
+                .parse::<f32>()
+                .map_err(|_| mesh_error(line_number, &format!("invalid number '{}'", token)))
+        })
+        .collect::<EngineResult<Vec<_>>>()?;
+    match values[..] {
+        [x, y, z] => Ok(glam::vec3(x, y, z)),
+        _ => Err(mesh_error(line_number, "expected 3 components")),
+    }
+}
+
+/// Parses a face corner (`v`, `v/vt`, `v//vn` or `v/vt/vn`) into zero-based
+/// position and optional normal indices. Negative indices count from the end.
+fn parse_corner(
+    token: &str,
+    position_count: usize,
+    normal_count: usize,
+    line_number: usize,
+) -> EngineResult<(usize, Option<usize>)> {
+    let mut parts = token.split('/');
+    let position = resolve_index(parts.next(), position_count, line_number)?
+        .ok_or_else(|| mesh_error(line_number, "face corner without a position"))?;
+    let _tex_coord = parts.next();
+    let normal = resolve_index(parts.next(), normal_count, line_number)?;
+    Ok((position, normal))
+}
+
+fn resolve_index(
+    part: Option<&str>,
+    count: usize,
+    line_number: usize,
+) -> EngineResult<Option<usize>> {
+    let Some(part) = part.filter(|part| !part.is_empty()) else {
+        return Ok(None);
+    };
+    let index: i64 = part
+        .parse()
+        .map_err(|_| mesh_error(line_number, &format!("invalid index '{}'", part)))?;
+
+    // OBJ は1始まり、負の値は末尾からの相対参照
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    if resolved < 0 || resolved >= count as i64 {
+        return Err(mesh_error(
+            line_number,
+            &format!("index {} out of range", index),
+        ));
+    }
+    Ok(Some(resolved as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::import::UpAxis;
+
+    #[test]
+    fn test_parse_triangle_with_normals() {
+        let source = "
+            # 三角形1枚
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            vn 0 0 2
+            f 1//1 2//1 3//1
+        ";
+
+        let mesh = parse_obj(source).unwrap();
+
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2]);
+        assert_eq!(mesh.vertices[1].position, [1.0, 0.0, 0.0]);
+        assert_eq!(
+            mesh.vertices[0].normal,
+            [0.0, 0.0, 1.0],
+            "法線は正規化されるべき"
+        );
+    }
+
+    #[test]
+    fn test_quads_are_triangulated_and_share_vertices() {
+        let source = "
+            v -1 -1 0
+            v 1 -1 0
+            v 1 1 0
+            v -1 1 0
+            vt 0 0
+            vn 0 0 1
+            f 1/1/1 2/1/1 3/1/1 4/1/1
+        ";
+
+        let mesh = parse_obj(source).unwrap();
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_missing_normals_use_face_normal() {
+        let source = "
+            v 0 0 0
+            v 0 0 1
+            v 1 0 0
+            f 1 2 3
+        ";
+
+        let mesh = parse_obj(source).unwrap();
+
+        for vertex in &mesh.vertices {
+            assert_eq!(vertex.normal, [0.0, 1.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn test_negative_indices_count_from_end() {
+        let source = "
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            f -3 -2 -1
+        ";
+
+        let mesh = parse_obj(source).unwrap();
+
+        assert_eq!(mesh.vertices[2].position, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_out_of_range_index_is_an_error() {
+        let result = parse_obj("v 0 0 0\nf 1 2 3\n");
+
+        assert!(
+            matches!(result, Err(EngineError::MeshLoad(message)) if message.contains("line 2"))
+        );
+    }
+
+    #[test]
+    fn test_missing_file_is_resource_not_found() {
+        let result = load_obj(
+            "assets/models/does_not_exist.obj",
+            &ImportOptions::default(),
+        );
+
+        assert!(matches!(result, Err(EngineError::ResourceNotFound(_))));
+    }
+
+    #[test]
+    fn test_bundled_pyramid_loads() {
+        let mesh = load_obj("assets/models/pyramid.obj", &ImportOptions::default()).unwrap();
+
+        // 四角形の底面1枚（2三角形）+ 側面4枚
+        assert_eq!(mesh.indices.len(), 6 * 3);
+        assert!(
+            mesh.indices
+                .iter()
+                .all(|&i| (i as usize) < mesh.vertices.len())
+        );
+    }
+
+    #[test]
+    fn test_z_up_obj_is_corrected_and_welded() {
+        // Z-up で書き出された四角形（対角の頂点が重複している）
+        let source = "
+            v 0 0 0
+            v 1 0 0
+            v 1 0 1
+            v 0 0 0
+            v 1 0 1
+            v 0 0 1
+            vn 0 -1 0
+            f 1//1 2//1 3//1
+            f 4//1 5//1 6//1
+        ";
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("z_up.obj");
+        std::fs::write(&path, source).unwrap();
+        let options = ImportOptions {
+            up_axis: UpAxis::Z,
+            weld_epsilon: Some(1e-5),
+            ..Default::default()
+        };
+
+        let mesh = load_obj(&path, &options).unwrap();
+
+        assert_eq!(mesh.vertices.len(), 4, "重複頂点はマージされるべき");
+        assert_eq!(mesh.indices.len(), 6);
+        // Z-up の (1, 0, 1) は Y-up の (1, 1, 0) になる
+        let corner = mesh.vertices[mesh.indices[2] as usize];
+        assert!(glam::Vec3::from(corner.position).abs_diff_eq(glam::vec3(1.0, 1.0, 0.0), 1e-5));
+        assert!(glam::Vec3::from(corner.normal).abs_diff_eq(glam::Vec3::Z, 1e-5));
+        assert_eq!(mesh.indices_u16(), Some(vec![0, 1, 2, 0, 2, 3]));
+    }
+}
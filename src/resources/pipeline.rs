@@ -0,0 +1,227 @@
+/// MSAA sample counts accepted by the engine.
+pub const SUPPORTED_SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
+/// Per-pipeline rasterization options passed to `ResourceManager::create_pipeline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineOptions {
+    /// Enable conservative rasterization (requires `CONSERVATIVE_RASTERIZATION`).
+    ///
+    /// Every pixel touched by a triangle is rasterized, which keeps thin or
+    /// distant geometry from flickering in coverage-style passes.
+    pub conservative: bool,
+    /// MSAA sample count; must match the color target the pipeline renders into.
+    pub sample_count: u32,
+    /// Depth target format; `None` renders without depth testing.
+    pub depth_format: Option<wgpu::TextureFormat>,
+    /// Fill or wireframe rasterization; `Line` requires `POLYGON_MODE_LINE`.
+    pub polygon_mode: wgpu::PolygonMode,
+    /// Faces discarded by the rasterizer; `None` draws both sides.
+    pub cull_mode: Option<wgpu::Face>,
+    /// Winding of front faces. The mirrored variant uses the opposite winding.
+    pub front_face: wgpu::FrontFace,
+    /// How vertices are assembled; `LineList` for line meshes such as the grid.
+    pub topology: wgpu::PrimitiveTopology,
+}
+
+impl Default for PipelineOptions {
+    fn default() -> Self {
+        Self {
+            conservative: false,
+            sample_count: 1,
+            depth_format: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            cull_mode: Some(wgpu::Face::Back),
+            front_face: wgpu::FrontFace::Ccw,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+        }
+    }
+}
+
+/// Returns the opposite winding, used for objects with a mirroring transform.
+pub fn flipped_front_face(front_face: wgpu::FrontFace) -> wgpu::FrontFace {
+    match front_face {
+        wgpu::FrontFace::Ccw => wgpu::FrontFace::Cw,
+        wgpu::FrontFace::Cw => wgpu::FrontFace::Ccw,
+    }
+}
+
+/// Resolves whether conservative rasterization can actually be enabled.
+///
+/// Returns `true` only when it was requested and the device exposes
+/// `CONSERVATIVE_RASTERIZATION`; otherwise logs a warning and falls back to
+/// regular rasterization.
+pub fn conservative_rasterization(requested: bool, features: wgpu::Features) -> bool {
+    if !requested {
+        return false;
+    }
+
+    if features.contains(wgpu::Features::CONSERVATIVE_RASTERIZATION) {
+        true
+    } else {
+        log::warn!(
+            "Conservative rasterization requested but not supported by the device, falling back"
+        );
+        false
+    }
+}
+
+/// Resolves the polygon mode a pipeline can actually use.
+///
+/// `Line` and `Point` need `POLYGON_MODE_LINE` / `POLYGON_MODE_POINT`; when the
+/// device lacks the feature an error is logged and `Fill` is used instead.
+pub fn polygon_mode(requested: wgpu::PolygonMode, features: wgpu::Features) -> wgpu::PolygonMode {
+    let required = match requested {
+        wgpu::PolygonMode::Fill => return requested,
+        wgpu::PolygonMode::Line => wgpu::Features::POLYGON_MODE_LINE,
+        wgpu::PolygonMode::Point => wgpu::Features::POLYGON_MODE_POINT,
+    };
+
+    if features.contains(required) {
+        requested
+    } else {
+        log::error!(
+            "Polygon mode {:?} requires {:?}, which the device does not support; falling back to Fill",
+            requested,
+            required
+        );
+        wgpu::PolygonMode::Fill
+    }
+}
+
+/// Resolves the MSAA sample count to use for a color target.
+///
+/// Only counts in [`SUPPORTED_SAMPLE_COUNTS`] that the target format supports
+/// (according to `format_flags`) are accepted; anything else logs a warning
+/// and falls back to 1 (no multisampling).
+pub fn msaa_sample_count(requested: u32, format_flags: wgpu::TextureFormatFeatureFlags) -> u32 {
+    if requested == 1 {
+        return 1;
+    }
+
+    if !SUPPORTED_SAMPLE_COUNTS.contains(&requested) {
+        log::warn!(
+            "Unsupported MSAA sample count {} (expected one of {:?}), falling back to 1",
+            requested,
+            SUPPORTED_SAMPLE_COUNTS
+        );
+        return 1;
+    }
+
+    if format_flags.sample_count_supported(requested) {
+        requested
+    } else {
+        log::warn!(
+            "MSAA x{} not supported by the surface format, falling back to 1",
+            requested
+        );
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conservative_enabled_when_requested_and_supported() {
+        assert!(conservative_rasterization(
+            true,
+            wgpu::Features::CONSERVATIVE_RASTERIZATION
+        ));
+    }
+
+    #[test]
+    fn test_conservative_falls_back_without_feature() {
+        assert!(!conservative_rasterization(true, wgpu::Features::empty()));
+    }
+
+    #[test]
+    fn test_conservative_disabled_when_not_requested() {
+        assert!(!conservative_rasterization(
+            false,
+            wgpu::Features::CONSERVATIVE_RASTERIZATION
+        ));
+        assert!(!conservative_rasterization(false, wgpu::Features::empty()));
+    }
+
+    #[test]
+    fn test_msaa_accepts_supported_counts() {
+        let flags = wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X2
+            | wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X4
+            | wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X8;
+
+        for count in SUPPORTED_SAMPLE_COUNTS {
+            assert_eq!(msaa_sample_count(count, flags), count);
+        }
+    }
+
+    #[test]
+    fn test_msaa_rejects_invalid_counts() {
+        let flags = wgpu::TextureFormatFeatureFlags::all();
+
+        assert_eq!(msaa_sample_count(0, flags), 1);
+        assert_eq!(msaa_sample_count(3, flags), 1);
+        assert_eq!(msaa_sample_count(16, flags), 1);
+    }
+
+    #[test]
+    fn test_msaa_falls_back_when_format_lacks_support() {
+        let flags = wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X4;
+
+        assert_eq!(msaa_sample_count(4, flags), 4);
+        assert_eq!(
+            msaa_sample_count(8, flags),
+            1,
+            "未対応のサンプル数は1に戻るべき"
+        );
+    }
+
+    #[test]
+    fn test_default_options_are_single_sampled() {
+        assert_eq!(PipelineOptions::default().sample_count, 1);
+        assert_eq!(
+            PipelineOptions::default().polygon_mode,
+            wgpu::PolygonMode::Fill
+        );
+        assert_eq!(
+            PipelineOptions::default().topology,
+            wgpu::PrimitiveTopology::TriangleList
+        );
+    }
+
+    #[test]
+    fn test_default_options_cull_back_faces_wound_ccw() {
+        let options = PipelineOptions::default();
+        assert_eq!(options.cull_mode, Some(wgpu::Face::Back));
+        assert_eq!(options.front_face, wgpu::FrontFace::Ccw);
+    }
+
+    #[test]
+    fn test_mirrored_winding_is_flipped() {
+        assert_eq!(
+            flipped_front_face(wgpu::FrontFace::Ccw),
+            wgpu::FrontFace::Cw
+        );
+        assert_eq!(
+            flipped_front_face(wgpu::FrontFace::Cw),
+            wgpu::FrontFace::Ccw
+        );
+    }
+
+    #[test]
+    fn test_line_polygon_mode_requires_feature() {
+        assert_eq!(
+            polygon_mode(wgpu::PolygonMode::Line, wgpu::Features::POLYGON_MODE_LINE),
+            wgpu::PolygonMode::Line
+        );
+        assert_eq!(
+            polygon_mode(wgpu::PolygonMode::Line, wgpu::Features::empty()),
+            wgpu::PolygonMode::Fill,
+            "機能がなければ塗りつぶしに戻るべき"
+        );
+        assert_eq!(
+            polygon_mode(wgpu::PolygonMode::Fill, wgpu::Features::empty()),
+            wgpu::PolygonMode::Fill
+        );
+    }
+}
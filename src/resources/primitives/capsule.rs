@@ -0,0 +1,150 @@
+use std::f32::consts::PI;
+
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
+
+/// Capsule along the Y axis: a cylinder capped with two hemispheres, spanning
+/// `y = -0.5..0.5` like the cube.
+pub struct Capsule;
+
+impl Capsule {
+    const SECTORS: i32 = 32;
+    /// Rings per hemisphere, from the pole to the equator.
+    const CAP_STACKS: i32 = 8;
+    const RADIUS: f32 = 0.25;
+    const HALF_HEIGHT: f32 = 0.5;
+
+    /// Half the length of the cylinder between the two cap centers.
+    fn body_half_height() -> f32 {
+        Self::HALF_HEIGHT - Self::RADIUS
+    }
+
+    /// Rings from the top pole to the bottom pole; each hemisphere includes
+    /// its equator, so the two equators bound the cylinder.
+    fn ring_count() -> i32 {
+        2 * (Self::CAP_STACKS + 1)
+    }
+}
+
+impl Primitive for Capsule {
+    type Vertex = NormalVertex;
+    type Index = u16;
+
+    fn create_vertices() -> Vec<Self::Vertex> {
+        let mut vertices = Vec::new();
+
+        for ring in 0..Self::ring_count() {
+            // 上半球は極から赤道へ、下半球は赤道から極へ
+            let (stack, center_y) = if ring <= Self::CAP_STACKS {
+                (ring, Self::body_half_height())
+            } else {
+                (ring - 1, -Self::body_half_height())
+            };
+            let polar_angle = (stack as f32) * PI / (2 * Self::CAP_STACKS) as f32;
+            let y = center_y + Self::RADIUS * polar_angle.cos();
+            let ring_radius = Self::RADIUS * polar_angle.sin();
+
+            // 上から下へのグラデーション
+            let t = (Self::HALF_HEIGHT - y) / (2.0 * Self::HALF_HEIGHT);
+            let color = [1.0 - 0.6 * t, 0.5, 0.4 + 0.6 * t];
+
+            for j in 0..=Self::SECTORS {
+                let sector_angle = (j as f32) * 2.0 * PI / Self::SECTORS as f32;
+                vertices.push(NormalVertex::new(
+                    [
+                        ring_radius * sector_angle.cos(),
+                        y,
+                        ring_radius * sector_angle.sin(),
+                    ],
+                    color,
+                ));
+            }
+        }
+        vertices
+    }
+
+    fn create_normals() -> Option<Vec<[f32; 3]>> {
+        // 半球は中心からの放射方向、円柱部分は水平方向
+        let normals = Self::create_vertices()
+            .iter()
+            .map(|vertex| {
+                let position = glam::Vec3::from(vertex.position);
+                let body = Self::body_half_height();
+                let center = glam::vec3(0.0, position.y.clamp(-body, body), 0.0);
+                (position - center).normalize().to_array()
+            })
+            .collect();
+        Some(normals)
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        let mut indices = Vec::new();
+        let last_band = Self::ring_count() - 2;
+
+        // 隣り合うリングを帯でつなぐ（上半球と円柱、円柱と下半球の境目も同じ）
+        for ring in 0..=last_band {
+            let k1 = ring * (Self::SECTORS + 1);
+            let k2 = k1 + Self::SECTORS + 1;
+            for j in 0..Self::SECTORS {
+                // 極では片方の三角形が潰れるので省く
+                if ring != 0 {
+                    indices.push((k1 + j) as u16);
+                    indices.push((k1 + j + 1) as u16);
+                    indices.push((k2 + j) as u16);
+                }
+
+                if ring != last_band {
+                    indices.push((k1 + j + 1) as u16);
+                    indices.push((k2 + j + 1) as u16);
+                    indices.push((k2 + j) as u16);
+                }
+            }
+        }
+
+        Some(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capsule_triangles_face_outward() {
+        let vertices = Capsule::create_vertices_with_normals();
+        let indices = Capsule::create_indices().unwrap();
+
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|n| vertices[triangle[n] as usize]);
+            let face = (glam::Vec3::from(b.position) - glam::Vec3::from(a.position))
+                .cross(glam::Vec3::from(c.position) - glam::Vec3::from(a.position));
+            // 潰れた三角形がなく、反時計回りの面が外側を向く
+            assert!(
+                face.dot(glam::Vec3::from(a.normal)) > 0.0,
+                "三角形 {:?} が内側を向いているか潰れている",
+                triangle
+            );
+        }
+    }
+
+    #[test]
+    fn test_capsule_spans_unit_height_with_vertical_body() {
+        let vertices = Capsule::create_vertices_with_normals();
+
+        let (min_y, max_y) = vertices.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(v.position[1]), max.max(v.position[1]))
+        });
+        assert!((min_y + Capsule::HALF_HEIGHT).abs() < 1e-6);
+        assert!((max_y - Capsule::HALF_HEIGHT).abs() < 1e-6);
+
+        // 円柱部分は両端の赤道だけでつなぎ、その法線は水平
+        let body = Capsule::body_half_height();
+        assert!(vertices.iter().all(|v| v.position[1].abs() > body - 1e-6));
+        for vertex in vertices
+            .iter()
+            .filter(|v| (v.position[1].abs() - body).abs() < 1e-6)
+        {
+            assert!(vertex.normal[1].abs() < 1e-5, "{:?}", vertex.normal);
+        }
+    }
+}
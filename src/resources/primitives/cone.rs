@@ -0,0 +1,127 @@
+use std::f32::consts::PI;
+
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
+
+/// Cone along the Y axis with its base at `y = -0.5` and apex at `y = 0.5`.
+pub struct Cone;
+
+impl Cone {
+    const SECTORS: i32 = 32;
+    const RADIUS: f32 = 0.5;
+    const HALF_HEIGHT: f32 = 0.5;
+
+    /// Side normal at `angle`, perpendicular to the slanted surface.
+    fn side_normal(angle: f32) -> [f32; 3] {
+        let height = 2.0 * Self::HALF_HEIGHT;
+        glam::vec3(angle.cos() * height, Self::RADIUS, angle.sin() * height)
+            .normalize()
+            .to_array()
+    }
+
+    fn sector_angle(j: i32) -> f32 {
+        (j as f32) * 2.0 * PI / Self::SECTORS as f32
+    }
+}
+
+impl Primitive for Cone {
+    type Vertex = NormalVertex;
+    type Index = u16;
+
+    fn create_vertices() -> Vec<Self::Vertex> {
+        let mut vertices = Vec::new();
+
+        // 側面の底辺リング: 0..=SECTORS
+        for j in 0..=Self::SECTORS {
+            let angle = Self::sector_angle(j);
+            vertices.push(NormalVertex::new(
+                [
+                    Self::RADIUS * angle.cos(),
+                    -Self::HALF_HEIGHT,
+                    Self::RADIUS * angle.sin(),
+                ],
+                [0.2, 0.4, 1.0],
+            ));
+        }
+
+        // 頂点はセクターごとに分ける（法線は中間の角度）
+        for _ in 0..Self::SECTORS {
+            vertices.push(NormalVertex::new(
+                [0.0, Self::HALF_HEIGHT, 0.0],
+                [1.0, 1.0, 1.0],
+            ));
+        }
+
+        // 底面: 中心 + リング
+        vertices.push(NormalVertex::new(
+            [0.0, -Self::HALF_HEIGHT, 0.0],
+            [0.1, 0.1, 0.4],
+        ));
+        for j in 0..=Self::SECTORS {
+            let angle = Self::sector_angle(j);
+            vertices.push(NormalVertex::new(
+                [
+                    Self::RADIUS * angle.cos(),
+                    -Self::HALF_HEIGHT,
+                    Self::RADIUS * angle.sin(),
+                ],
+                [0.1, 0.1, 0.4],
+            ));
+        }
+
+        vertices
+    }
+
+    fn create_normals() -> Option<Vec<[f32; 3]>> {
+        // 側面は斜面に垂直、頂点はセクターの中間の角度、底面は -Y
+        let ring = (0..=Self::SECTORS).map(|j| Self::side_normal(Self::sector_angle(j)));
+        let apex = (0..Self::SECTORS)
+            .map(|j| Self::side_normal((Self::sector_angle(j) + Self::sector_angle(j + 1)) * 0.5));
+        let base = std::iter::repeat_n([0.0, -1.0, 0.0], Self::SECTORS as usize + 2);
+        Some(ring.chain(apex).chain(base).collect())
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        let mut indices = Vec::new();
+        let apex_start = Self::SECTORS + 1;
+        let base_center = apex_start + Self::SECTORS;
+        let base_start = base_center + 1;
+
+        for j in 0..Self::SECTORS {
+            // 側面（外側から見て反時計回り）
+            indices.push(j as u16);
+            indices.push((apex_start + j) as u16);
+            indices.push((j + 1) as u16);
+
+            // 底面（-Y から見て反時計回り）
+            indices.push(base_center as u16);
+            indices.push((base_start + j) as u16);
+            indices.push((base_start + j + 1) as u16);
+        }
+
+        Some(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cone_triangles_face_outward() {
+        let vertices = Cone::create_vertices_with_normals();
+        let indices = Cone::create_indices().unwrap();
+
+        assert_eq!(indices.len(), Cone::SECTORS as usize * 6);
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|n| vertices[triangle[n] as usize]);
+            let face = (glam::Vec3::from(b.position) - glam::Vec3::from(a.position))
+                .cross(glam::Vec3::from(c.position) - glam::Vec3::from(a.position));
+            // 反時計回りの面の向きが頂点法線と一致する（背面カリングで消えない）
+            assert!(
+                face.dot(glam::Vec3::from(a.normal)) > 0.0,
+                "三角形 {:?} が内側を向いている",
+                triangle
+            );
+        }
+    }
+}
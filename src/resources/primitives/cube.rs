@@ -1,119 +1,48 @@
-use crate::resources::{primitives::Primitive, vertex::ColorVertex};
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
 
 pub struct Cube;
 
 impl Primitive for Cube {
-    type Vertex = ColorVertex;
+    type Vertex = NormalVertex;
+    type Index = u16;
 
     fn create_vertices() -> Vec<Self::Vertex> {
         let s = 0.5f32;
         vec![
             // 前面 (Z+) - 赤系
-            ColorVertex {
-                position: [-s, -s, s],
-                color: [1.0, 0.0, 0.0],
-            }, // 0
-            ColorVertex {
-                position: [s, -s, s],
-                color: [1.0, 0.2, 0.0],
-            }, // 1
-            ColorVertex {
-                position: [s, s, s],
-                color: [1.0, 0.4, 0.0],
-            }, // 2
-            ColorVertex {
-                position: [-s, s, s],
-                color: [1.0, 0.6, 0.0],
-            }, // 3
+            NormalVertex::new([-s, -s, s], [1.0, 0.0, 0.0]), // 0
+            NormalVertex::new([s, -s, s], [1.0, 0.2, 0.0]),  // 1
+            NormalVertex::new([s, s, s], [1.0, 0.4, 0.0]),   // 2
+            NormalVertex::new([-s, s, s], [1.0, 0.6, 0.0]),  // 3
             // 後面 (Z-) - 青系
-            ColorVertex {
-                position: [s, -s, -s],
-                color: [0.0, 0.0, 1.0],
-            }, // 4
-            ColorVertex {
-                position: [-s, -s, -s],
-                color: [0.0, 0.2, 1.0],
-            }, // 5
-            ColorVertex {
-                position: [-s, s, -s],
-                color: [0.0, 0.4, 1.0],
-            }, // 6
-            ColorVertex {
-                position: [s, s, -s],
-                color: [0.0, 0.6, 1.0],
-            }, // 7
+            NormalVertex::new([s, -s, -s], [0.0, 0.0, 1.0]), // 4
+            NormalVertex::new([-s, -s, -s], [0.0, 0.2, 1.0]), // 5
+            NormalVertex::new([-s, s, -s], [0.0, 0.4, 1.0]), // 6
+            NormalVertex::new([s, s, -s], [0.0, 0.6, 1.0]),  // 7
             // 左面 (X-) - 緑系
-            ColorVertex {
-                position: [-s, -s, -s],
-                color: [0.0, 1.0, 0.0],
-            }, // 8
-            ColorVertex {
-                position: [-s, -s, s],
-                color: [0.2, 1.0, 0.0],
-            }, // 9
-            ColorVertex {
-                position: [-s, s, s],
-                color: [0.4, 1.0, 0.0],
-            }, // 10
-            ColorVertex {
-                position: [-s, s, -s],
-                color: [0.6, 1.0, 0.0],
-            }, // 11
+            NormalVertex::new([-s, -s, -s], [0.0, 1.0, 0.0]), // 8
+            NormalVertex::new([-s, -s, s], [0.2, 1.0, 0.0]),  // 9
+            NormalVertex::new([-s, s, s], [0.4, 1.0, 0.0]),   // 10
+            NormalVertex::new([-s, s, -s], [0.6, 1.0, 0.0]),  // 11
             // 右面 (X+) - マゼンタ系
-            ColorVertex {
-                position: [s, -s, s],
-                color: [1.0, 0.0, 1.0],
-            }, // 12
-            ColorVertex {
-                position: [s, -s, -s],
-                color: [1.0, 0.2, 1.0],
-            }, // 13
-            ColorVertex {
-                position: [s, s, -s],
-                color: [1.0, 0.4, 1.0],
-            }, // 14
-            ColorVertex {
-                position: [s, s, s],
-                color: [1.0, 0.6, 1.0],
-            }, // 15
+            NormalVertex::new([s, -s, s], [1.0, 0.0, 1.0]), // 12
+            NormalVertex::new([s, -s, -s], [1.0, 0.2, 1.0]), // 13
+            NormalVertex::new([s, s, -s], [1.0, 0.4, 1.0]), // 14
+            NormalVertex::new([s, s, s], [1.0, 0.6, 1.0]),  // 15
             // 上面 (Y+) - シアン系
-            ColorVertex {
-                position: [-s, s, s],
-                color: [0.0, 1.0, 1.0],
-            }, // 16
-            ColorVertex {
-                position: [s, s, s],
-                color: [0.2, 1.0, 1.0],
-            }, // 17
-            ColorVertex {
-                position: [s, s, -s],
-                color: [0.4, 1.0, 1.0],
-            }, // 18
-            ColorVertex {
-                position: [-s, s, -s],
-                color: [0.6, 1.0, 1.0],
-            }, // 19
+            NormalVertex::new([-s, s, s], [0.0, 1.0, 1.0]), // 16
+            NormalVertex::new([s, s, s], [0.2, 1.0, 1.0]),  // 17
+            NormalVertex::new([s, s, -s], [0.4, 1.0, 1.0]), // 18
+            NormalVertex::new([-s, s, -s], [0.6, 1.0, 1.0]), // 19
             // 下面 (Y-) - 黄系
-            ColorVertex {
-                position: [-s, -s, -s],
-                color: [1.0, 1.0, 0.0],
-            }, // 20
-            ColorVertex {
-                position: [s, -s, -s],
-                color: [1.0, 1.0, 0.2],
-            }, // 21
-            ColorVertex {
-                position: [s, -s, s],
-                color: [1.0, 1.0, 0.4],
-            }, // 22
-            ColorVertex {
-                position: [-s, -s, s],
-                color: [1.0, 1.0, 0.6],
-            }, // 23
+            NormalVertex::new([-s, -s, -s], [1.0, 1.0, 0.0]), // 20
+            NormalVertex::new([s, -s, -s], [1.0, 1.0, 0.2]),  // 21
+            NormalVertex::new([s, -s, s], [1.0, 1.0, 0.4]),   // 22
+            NormalVertex::new([-s, -s, s], [1.0, 1.0, 0.6]),  // 23
         ]
     }
 
-    fn create_indices() -> Option<Vec<u16>> {
+    fn create_indices() -> Option<Vec<Self::Index>> {
         Some(vec![
             // 前面 (Z+)
             0, 1, 2, 2, 3, 0, // 後面 (Z-)
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::resources::{mesh::Mesh, vertex::ColorVertex};
+
+/// Reference grid on the XZ plane, drawn as a line list (two vertices per
+/// line) with the `LineList` topology rather than as triangles.
+///
+/// Lines are 1 unit apart over a `size`×`size` area centered on the origin;
+/// odd sizes are rounded down so a line always runs through the origin.
+pub struct Grid {
+    size: u32,
+}
+
+impl Grid {
+    /// Color of the center line along X (`z = 0`).
+    pub const X_AXIS_COLOR: [f32; 3] = [0.8, 0.2, 0.2];
+    /// Color of the center line along Z (`x = 0`).
+    pub const Z_AXIS_COLOR: [f32; 3] = [0.2, 0.4, 0.9];
+    pub const LINE_COLOR: [f32; 3] = [0.4, 0.4, 0.4];
+
+    pub fn new(size: u32) -> Self {
+        Self { size }
+    }
+
+    /// Distance from the origin to the outermost lines.
+    pub fn half_extent(&self) -> f32 {
+        (self.size / 2) as f32
+    }
+
+    pub fn create_vertices(&self) -> Vec<ColorVertex> {
+        let half = (self.size / 2) as i32;
+        let extent = self.half_extent();
+        let mut vertices = Vec::new();
+
+        for i in -half..=half {
+            let offset = i as f32;
+            // X方向の線（z = offset）と Z方向の線（x = offset）
+            let (x_line_color, z_line_color) = if i == 0 {
+                (Self::X_AXIS_COLOR, Self::Z_AXIS_COLOR)
+            } else {
+                (Self::LINE_COLOR, Self::LINE_COLOR)
+            };
+            vertices.extend([
+                ColorVertex {
+                    position: [-extent, 0.0, offset],
+                    color: x_line_color,
+                },
+                ColorVertex {
+                    position: [extent, 0.0, offset],
+                    color: x_line_color,
+                },
+                ColorVertex {
+                    position: [offset, 0.0, -extent],
+                    color: z_line_color,
+                },
+                ColorVertex {
+                    position: [offset, 0.0, extent],
+                    color: z_line_color,
+                },
+            ]);
+        }
+        vertices
+    }
+
+    pub fn create_mesh(&self, device: Arc<wgpu::Device>) -> Mesh {
+        Mesh::new::<_, u16>(device, &self.create_vertices(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_has_two_lines_per_unit_step() {
+        let vertices = Grid::new(4).create_vertices();
+
+        // -2..=2 の5本ずつ、1本につき2頂点
+        assert_eq!(vertices.len(), 5 * 2 * 2);
+        assert!(vertices.iter().all(|v| v.position[1] == 0.0));
+        assert!(
+            vertices
+                .iter()
+                .all(|v| v.position[0].abs() <= 2.0 && v.position[2].abs() <= 2.0)
+        );
+    }
+
+    #[test]
+    fn test_grid_center_lines_are_colored_distinctly() {
+        let vertices = Grid::new(10).create_vertices();
+
+        let color_at = |x: f32, z: f32| {
+            vertices
+                .chunks(2)
+                .find(|line| line[0].position[0] == x && line[0].position[2] == z)
+                .map(|line| line[0].color)
+        };
+        assert_eq!(color_at(-5.0, 0.0), Some(Grid::X_AXIS_COLOR));
+        assert_eq!(color_at(0.0, -5.0), Some(Grid::Z_AXIS_COLOR));
+        assert_eq!(color_at(-5.0, 1.0), Some(Grid::LINE_COLOR));
+    }
+
+    #[test]
+    fn test_odd_grid_size_rounds_down() {
+        assert_eq!(Grid::new(5).half_extent(), 2.0);
+        assert_eq!(Grid::new(5).create_vertices().len(), 5 * 2 * 2);
+    }
+}
@@ -1,29 +1,200 @@
+pub mod capsule;
+pub mod cone;
 pub mod cube;
+pub mod grid;
+pub mod plane;
 pub mod quad;
 pub mod sphere;
+pub mod torus;
 pub mod triangle;
 
 use std::sync::Arc;
 
-use crate::resources::mesh::Mesh;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{
+    mesh::{Mesh, MeshIndex},
+    vertex::VertexTrait,
+};
 
 pub trait Primitive {
-    type Vertex: bytemuck::Pod;
+    type Vertex: VertexTrait;
+    /// `u16` unless the primitive can exceed 65 536 vertices.
+    type Index: MeshIndex;
 
     fn create_vertices() -> Vec<Self::Vertex>;
-    fn create_indices() -> Option<Vec<u16>>;
+    fn create_indices() -> Option<Vec<Self::Index>>;
+
+    /// Per-vertex normals in `create_vertices` order, for curved surfaces
+    /// whose exact normals are known. `None` derives them from the triangles
+    /// with [`face_normals`].
+    fn create_normals() -> Option<Vec<[f32; 3]>> {
+        None
+    }
+
+    /// `create_vertices` with the normals from `create_normals` (or derived
+    /// from the triangles) written into vertex layouts that have one.
+    fn create_vertices_with_normals() -> Vec<Self::Vertex> {
+        let mut vertices = Self::create_vertices();
+        if vertices
+            .first_mut()
+            .is_none_or(|vertex| vertex.normal_mut().is_none())
+        {
+            return vertices;
+        }
+
+        let normals = Self::create_normals().unwrap_or_else(|| {
+            let positions: Vec<_> = vertices.iter().map(VertexTrait::position).collect();
+            face_normals(&positions, Self::create_indices().as_deref())
+        });
+        for (vertex, normal) in vertices.iter_mut().zip(normals) {
+            if let Some(slot) = vertex.normal_mut() {
+                *slot = normal;
+            }
+        }
+        vertices
+    }
 
     fn create_mesh(device: Arc<wgpu::Device>) -> Mesh {
-        let vertices = Self::create_vertices();
+        let vertices = Self::create_vertices_with_normals();
         let indices = Self::create_indices();
 
         Mesh::new(device, &vertices, indices.as_deref())
     }
 }
 
+/// Per-vertex normals from the counter-clockwise triangles each vertex belongs
+/// to, weighted by triangle area. Vertices only shared within a flat face get
+/// that face's normal; without `indices` every three vertices form a triangle.
+pub fn face_normals<I: MeshIndex>(positions: &[[f32; 3]], indices: Option<&[I]>) -> Vec<[f32; 3]> {
+    let mut sums = vec![glam::Vec3::ZERO; positions.len()];
+    let triangles: Vec<usize> = match indices {
+        Some(indices) => indices.iter().map(|&index| index.to_usize()).collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    for triangle in triangles.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|n| glam::Vec3::from(positions[triangle[n]]));
+        // 外積の長さは面積の2倍なので、そのまま足すと面積で重み付けされる
+        let normal = (b - a).cross(c - a);
+        for &index in triangle {
+            sums[index] += normal;
+        }
+    }
+
+    sums.into_iter()
+        .map(|sum| sum.normalize_or_zero().to_array())
+        .collect()
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ObjectType {
     Triangle,
     Quad,
     Cube,
     Sphere,
+    /// Quad with UVs, drawn with the textured pipeline (white until textured).
+    TexturedQuad,
+    /// Subdivided ground grid on the XZ plane.
+    Plane,
+    /// Cone along Y with a flat base, same 0.5 half-extent as the cube.
+    Cone,
+    /// Smooth ring, tessellation capped to fit u16 indices.
+    Torus,
+    /// Cylinder with hemispherical caps along Y, the usual character collider.
+    Capsule,
+}
+
+impl ObjectType {
+    /// All built-in primitive types, in declaration order.
+    pub const ALL: [ObjectType; 9] = [
+        ObjectType::Triangle,
+        ObjectType::Quad,
+        ObjectType::Cube,
+        ObjectType::Sphere,
+        ObjectType::TexturedQuad,
+        ObjectType::Plane,
+        ObjectType::Cone,
+        ObjectType::Torus,
+        ObjectType::Capsule,
+    ];
+
+    /// Returns `true` for primitives without volume, whose back side is
+    /// visible when viewed from behind.
+    pub fn is_flat(self) -> bool {
+        matches!(
+            self,
+            ObjectType::Triangle | ObjectType::Quad | ObjectType::TexturedQuad | ObjectType::Plane
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::primitives::{
+        cube::Cube, quad::Quad, sphere::Sphere, triangle::Triangle,
+    };
+
+    #[test]
+    fn test_cube_faces_get_axis_aligned_normals() {
+        let vertices = Cube::create_vertices_with_normals();
+
+        for vertex in &vertices {
+            let normal = glam::Vec3::from(vertex.normal);
+            let position = glam::Vec3::from(vertex.position);
+            // 面の法線は座標軸のいずれかに一致し、外側を向く
+            let axis_count = normal.to_array().iter().filter(|c| c.abs() > 1e-6).count();
+            assert_eq!(axis_count, 1, "軸に沿っていない法線: {:?}", normal);
+            assert!((normal.length() - 1.0).abs() < 1e-6);
+            assert!((position.dot(normal) - 0.5).abs() < 1e-6, "外側を向くべき");
+        }
+    }
+
+    #[test]
+    fn test_sphere_gets_radial_normals() {
+        for vertex in Sphere::create_vertices_with_normals() {
+            let radial = glam::Vec3::from(vertex.position).normalize();
+            let normal = glam::Vec3::from(vertex.normal);
+            assert!(
+                normal.abs_diff_eq(radial, 1e-5),
+                "{:?} != {:?}",
+                normal,
+                radial
+            );
+        }
+    }
+
+    #[test]
+    fn test_flat_primitives_face_positive_z() {
+        // インデックスなしの三角形とインデックス付きの四角形
+        let normals = Triangle::create_vertices_with_normals()
+            .into_iter()
+            .chain(Quad::create_vertices_with_normals())
+            .map(|vertex| vertex.normal);
+
+        for normal in normals {
+            assert_eq!(normal, [0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_shared_vertices_average_adjacent_faces() {
+        // 直角に折れた2枚の三角形が辺 (0, 1) を共有する
+        let positions = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, -1.0],
+        ];
+        let indices: [u16; 6] = [0, 1, 2, 0, 1, 3];
+
+        let normals = face_normals(&positions, Some(&indices));
+
+        assert_eq!(normals[2], [0.0, 0.0, 1.0]);
+        assert_eq!(normals[3], [0.0, 1.0, 0.0]);
+        let shared = glam::Vec3::from(normals[0]);
+        assert!(shared.abs_diff_eq(glam::vec3(0.0, 1.0, 1.0).normalize(), 1e-6));
+    }
 }
@@ -0,0 +1,78 @@
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
+
+/// Flat ground on the XZ plane facing +Y, split into a grid of quads.
+pub struct Plane;
+
+impl Plane {
+    /// Quads along each edge of the grid.
+    const SUBDIVISIONS: i32 = 16;
+    const HALF_EXTENT: f32 = 0.5;
+}
+
+impl Primitive for Plane {
+    type Vertex = NormalVertex;
+    type Index = u16;
+
+    fn create_vertices() -> Vec<Self::Vertex> {
+        let mut vertices = Vec::new();
+        let step = 2.0 * Self::HALF_EXTENT / Self::SUBDIVISIONS as f32;
+
+        for i in 0..=Self::SUBDIVISIONS {
+            let z = -Self::HALF_EXTENT + i as f32 * step;
+            for j in 0..=Self::SUBDIVISIONS {
+                let x = -Self::HALF_EXTENT + j as f32 * step;
+
+                // 格子ごとに明暗を付けて床の広がりを分かりやすくする
+                let shade = if (i + j) % 2 == 0 { 0.7 } else { 0.55 };
+                vertices.push(NormalVertex::new([x, 0.0, z], [shade, shade, shade]));
+            }
+        }
+        vertices
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        let mut indices = Vec::new();
+        let row = Self::SUBDIVISIONS + 1;
+
+        for i in 0..Self::SUBDIVISIONS {
+            for j in 0..Self::SUBDIVISIONS {
+                let k1 = i * row + j;
+                let k2 = k1 + row;
+
+                // +Y から見て反時計回り
+                indices.push(k1 as u16);
+                indices.push(k2 as u16);
+                indices.push((k1 + 1) as u16);
+
+                indices.push((k1 + 1) as u16);
+                indices.push(k2 as u16);
+                indices.push((k2 + 1) as u16);
+            }
+        }
+
+        Some(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plane_triangles_face_up() {
+        let vertices = Plane::create_vertices();
+        let indices = Plane::create_indices().unwrap();
+
+        let quads = (Plane::SUBDIVISIONS * Plane::SUBDIVISIONS) as usize;
+        assert_eq!(indices.len(), quads * 6);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+
+        // 反時計回りの三角形は法線 +Y を向く（背面カリングで消えない）
+        for triangle in indices.chunks(3) {
+            let [a, b, c] =
+                [0, 1, 2].map(|n| glam::Vec3::from(vertices[triangle[n] as usize].position));
+            let normal = (b - a).cross(c - a);
+            assert!(normal.y > 0.0, "三角形 {:?} が下を向いている", triangle);
+        }
+    }
+}
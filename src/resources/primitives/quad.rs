@@ -1,32 +1,57 @@
-use crate::resources::{primitives::Primitive, vertex::ColorVertex};
+use crate::resources::{
+    primitives::Primitive,
+    vertex::{NormalVertex, TextureVertex},
+};
 
 pub struct Quad;
 
 impl Primitive for Quad {
-    type Vertex = ColorVertex;
+    type Vertex = NormalVertex;
+    type Index = u16;
 
     fn create_vertices() -> Vec<Self::Vertex> {
         vec![
-            ColorVertex {
+            NormalVertex::new([-0.5, -0.5, 0.0], [1.0, 0.0, 0.0]),
+            NormalVertex::new([0.5, -0.5, 0.0], [1.0, 1.0, 0.0]),
+            NormalVertex::new([0.5, 0.5, 0.0], [0.0, 0.0, 1.0]),
+            NormalVertex::new([-0.5, 0.5, 0.0], [1.0, 1.0, 0.0]),
+        ]
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        Some(vec![0, 1, 2, 0, 2, 3])
+    }
+}
+
+/// Unit quad with texture coordinates covering the whole image (V down).
+pub struct TexturedQuad;
+
+impl Primitive for TexturedQuad {
+    type Vertex = TextureVertex;
+    type Index = u16;
+
+    fn create_vertices() -> Vec<Self::Vertex> {
+        vec![
+            TextureVertex {
                 position: [-0.5, -0.5, 0.0],
-                color: [1.0, 0.0, 0.0],
+                tex_coords: [0.0, 1.0],
             },
-            ColorVertex {
+            TextureVertex {
                 position: [0.5, -0.5, 0.0],
-                color: [1.0, 1.0, 0.0],
+                tex_coords: [1.0, 1.0],
             },
-            ColorVertex {
+            TextureVertex {
                 position: [0.5, 0.5, 0.0],
-                color: [0.0, 0.0, 1.0],
+                tex_coords: [1.0, 0.0],
             },
-            ColorVertex {
+            TextureVertex {
                 position: [-0.5, 0.5, 0.0],
-                color: [1.0, 1.0, 0.0],
+                tex_coords: [0.0, 0.0],
             },
         ]
     }
 
-    fn create_indices() -> Option<Vec<u16>> {
-        Some(vec![0, 1, 2, 0, 2, 3])
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        Quad::create_indices()
     }
 }
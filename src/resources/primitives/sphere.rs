@@ -1,6 +1,6 @@
 use std::f32::consts::PI;
 
-use crate::resources::{primitives::Primitive, vertex::ColorVertex};
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
 
 pub struct Sphere;
 
@@ -10,7 +10,8 @@ impl Sphere {
 }
 
 impl Primitive for Sphere {
-    type Vertex = ColorVertex;
+    type Vertex = NormalVertex;
+    type Index = u16;
 
     fn create_vertices() -> Vec<Self::Vertex> {
         let mut vertices = Vec::new();
@@ -28,16 +29,25 @@ impl Primitive for Sphere {
                 let x = xy * sector_angle.cos();
                 let y = xy * sector_angle.sin();
 
-                vertices.push(Self::Vertex {
-                    position: [x, y, z],
-                    color: [(x + 0.5), (y + 0.5), (z + 0.5)],
-                });
+                // 単位方向ベクトルを [0, 1] に写して滑らかなグラデーションにする
+                let direction = glam::vec3(x, y, z) / redius;
+                let color = direction * 0.5 + 0.5;
+                vertices.push(NormalVertex::new([x, y, z], color.to_array()));
             }
         }
         vertices
     }
 
-    fn create_indices() -> Option<Vec<u16>> {
+    fn create_normals() -> Option<Vec<[f32; 3]>> {
+        // 球の法線は中心からの放射方向
+        let normals = Self::create_vertices()
+            .iter()
+            .map(|vertex| glam::Vec3::from(vertex.position).normalize().to_array())
+            .collect();
+        Some(normals)
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
         let mut indecies = Vec::new();
 
         for i in 0..Self::STACKS {
@@ -61,3 +71,19 @@ impl Primitive for Sphere {
         Some(indecies)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_colors_stay_in_unit_range() {
+        for vertex in Sphere::create_vertices() {
+            assert!(
+                vertex.color.iter().all(|c| (0.0..=1.0).contains(c)),
+                "色が範囲外: {:?}",
+                vertex.color
+            );
+        }
+    }
+}
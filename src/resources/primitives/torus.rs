@@ -0,0 +1,114 @@
+use std::f32::consts::PI;
+
+use crate::resources::{primitives::Primitive, vertex::NormalVertex};
+
+/// Torus lying in the XZ plane, fitting the 0.5 half-extent of the cube.
+pub struct Torus;
+
+impl Torus {
+    /// Segments around the main ring.
+    const MAJOR_SEGMENTS: i32 = 48;
+    /// Segments around the tube.
+    const MINOR_SEGMENTS: i32 = 24;
+    const MAJOR_RADIUS: f32 = 0.35;
+    const MINOR_RADIUS: f32 = 0.15;
+}
+
+// u16 インデックスに収まる分割数に制限する（超える場合は `type Index = u32` にする）
+const _: () = assert!(
+    (Torus::MAJOR_SEGMENTS + 1) * (Torus::MINOR_SEGMENTS + 1) <= u16::MAX as i32 + 1,
+    "Torus vertex count exceeds u16 index range"
+);
+
+impl Primitive for Torus {
+    type Vertex = NormalVertex;
+    type Index = u16;
+
+    fn create_vertices() -> Vec<Self::Vertex> {
+        let mut vertices = Vec::new();
+
+        for i in 0..=Self::MAJOR_SEGMENTS {
+            let major_angle = (i as f32) * 2.0 * PI / Self::MAJOR_SEGMENTS as f32;
+
+            for j in 0..=Self::MINOR_SEGMENTS {
+                let minor_angle = (j as f32) * 2.0 * PI / Self::MINOR_SEGMENTS as f32;
+
+                let direction = glam::vec3(
+                    minor_angle.cos() * major_angle.cos(),
+                    minor_angle.sin(),
+                    minor_angle.cos() * major_angle.sin(),
+                );
+                let center =
+                    glam::vec3(major_angle.cos(), 0.0, major_angle.sin()) * Self::MAJOR_RADIUS;
+                let position = center + direction * Self::MINOR_RADIUS;
+
+                vertices.push(NormalVertex::new(
+                    position.to_array(),
+                    [
+                        0.5 + 0.5 * major_angle.cos(),
+                        0.5 + 0.5 * minor_angle.sin(),
+                        0.5 + 0.5 * major_angle.sin(),
+                    ],
+                ));
+            }
+        }
+        vertices
+    }
+
+    fn create_normals() -> Option<Vec<[f32; 3]>> {
+        // 法線はチューブの中心線からの放射方向
+        let normals = Self::create_vertices()
+            .iter()
+            .map(|vertex| {
+                let position = glam::Vec3::from(vertex.position);
+                let center =
+                    glam::vec3(position.x, 0.0, position.z).normalize() * Self::MAJOR_RADIUS;
+                (position - center).normalize().to_array()
+            })
+            .collect();
+        Some(normals)
+    }
+
+    fn create_indices() -> Option<Vec<Self::Index>> {
+        let mut indices = Vec::new();
+
+        for i in 0..Self::MAJOR_SEGMENTS {
+            let k1 = i * (Self::MINOR_SEGMENTS + 1);
+            let k2 = k1 + Self::MINOR_SEGMENTS + 1;
+            for j in 0..Self::MINOR_SEGMENTS {
+                indices.push((k1 + j) as u16);
+                indices.push((k1 + j + 1) as u16);
+                indices.push((k2 + j) as u16);
+
+                indices.push((k1 + j + 1) as u16);
+                indices.push((k2 + j + 1) as u16);
+                indices.push((k2 + j) as u16);
+            }
+        }
+
+        Some(indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_torus_triangles_face_outward() {
+        let vertices = Torus::create_vertices_with_normals();
+        let indices = Torus::create_indices().unwrap();
+
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|n| vertices[triangle[n] as usize]);
+            let face = (glam::Vec3::from(b.position) - glam::Vec3::from(a.position))
+                .cross(glam::Vec3::from(c.position) - glam::Vec3::from(a.position));
+            assert!(
+                face.dot(glam::Vec3::from(a.normal)) > 0.0,
+                "三角形 {:?} が内側を向いている",
+                triangle
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::core::error::{EngineError, EngineResult};
+
+/// Color format of textures created from images; image data is sRGB encoded.
+pub const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+/// Depth format of shadow maps.
+pub const SHADOW_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// GPU texture with the view and sampler shaders bind it through.
+pub struct Texture {
+    #[allow(dead_code)]
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub sampler: wgpu::Sampler,
+    #[allow(dead_code)]
+    pub size: (u32, u32),
+}
+
+/// Decodes an encoded image (PNG or JPEG) into 8-bit RGBA pixels.
+///
+/// # Errors
+///
+/// Returns `EngineError::TextureLoad` if the format is unsupported or the data
+/// is corrupt.
+pub fn decode_rgba8(bytes: &[u8]) -> EngineResult<image::RgbaImage> {
+    image::load_from_memory(bytes)
+        .map(|image| image.to_rgba8())
+        .map_err(|e| EngineError::TextureLoad(format!("Failed to decode image: {}", e)))
+}
+
+impl Texture {
+    /// Decodes `bytes` and uploads the pixels into a new sampled texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::TextureLoad` if the image cannot be decoded.
+    pub fn from_bytes(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        bytes: &[u8],
+        label: Option<&str>,
+    ) -> EngineResult<Self> {
+        let image = decode_rgba8(bytes)?;
+        Ok(Self::from_rgba8(device, queue, &image, label))
+    }
+
+    pub fn from_rgba8(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        image: &image::RgbaImage,
+        label: Option<&str>,
+    ) -> Self {
+        let (width, height) = image.dimensions();
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            image.as_raw(),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label,
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            texture,
+            view,
+            sampler,
+            size: (width, height),
+        }
+    }
+
+    /// Creates a `size`×`size` depth texture rendered into by a shadow pass,
+    /// with a comparison sampler for `textureSampleCompare` lookups.
+    pub fn shadow_map(device: &wgpu::Device, size: u32, label: Option<&str>) -> Self {
+        let size = size.max(1);
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label,
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SHADOW_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // 線形フィルタで隣接する4テクセルの比較結果が補間され、影の縁がなめらかになる
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+
+        Self {
+            texture,
+            view,
+            sampler,
+            size: (size, size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_png(image: &image::RgbaImage) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .expect("PNGエンコードに失敗");
+        bytes
+    }
+
+    #[test]
+    fn test_decode_png_to_rgba() {
+        let mut source = image::RgbaImage::new(3, 2);
+        source.put_pixel(2, 1, image::Rgba([10, 20, 30, 40]));
+
+        let decoded = decode_rgba8(&encode_png(&source)).unwrap();
+
+        assert_eq!(decoded.dimensions(), (3, 2));
+        assert_eq!(decoded.get_pixel(2, 1), &image::Rgba([10, 20, 30, 40]));
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_data() {
+        let result = decode_rgba8(b"not an image");
+
+        assert!(matches!(result, Err(EngineError::TextureLoad(_))));
+    }
+
+    #[test]
+    fn test_bundled_checker_texture_decodes() {
+        let decoded = decode_rgba8(include_bytes!("../../assets/textures/checker.png")).unwrap();
+
+        assert_eq!(decoded.dimensions(), (64, 64));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::scene::camera::Camera;
+use crate::scene::{camera::Camera, light::PointLight};
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
@@ -18,8 +18,173 @@ impl CameraUniform {
     }
 }
 
+/// Per-frame values shared by every shader, bound next to the camera at
+/// group 0, binding 1. `resolution` is a `vec2` and must start on an 8-byte
+/// boundary, hence the padding after `time`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GlobalUniform {
+    /// Seconds accumulated from frame deltas since the engine started.
+    pub time: f32,
+    pub _padding: f32,
+    /// Render target size in pixels.
+    pub resolution: [f32; 2],
+}
+
+impl GlobalUniform {
+    pub fn new(time: f32, resolution: [f32; 2]) -> Self {
+        Self {
+            time,
+            _padding: 0.0,
+            resolution,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct ModelUniform {
     pub model: [[f32; 4]; 4],
 }
+
+/// Per-object tint multiplied into the vertex or texture color; bound next
+/// to the model matrix in the object's group 1.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MaterialUniform {
+    /// Linear RGBA; alpha below 1 blends with what was drawn before (objects
+    /// are not depth-sorted).
+    pub color: [f32; 4],
+}
+
+impl MaterialUniform {
+    pub fn new(color: [f32; 4]) -> Self {
+        Self { color }
+    }
+}
+
+impl Default for MaterialUniform {
+    fn default() -> Self {
+        Self::new([1.0; 4])
+    }
+}
+
+/// Maximum number of point lights uploaded in [`LightUniform`].
+pub const MAX_POINT_LIGHTS: usize = 4;
+
+/// One point light; 32 bytes so the uniform array stride stays 16-byte aligned.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PointLightUniform {
+    pub position: [f32; 3],
+    /// Distance at which the contribution reaches zero.
+    pub radius: f32,
+    pub color: [f32; 3],
+    pub _padding: f32,
+}
+
+/// Scene lighting: one directional light plus up to [`MAX_POINT_LIGHTS`] point
+/// lights. Each `vec3` is padded to 16 bytes as WGSL requires; the point light
+/// count occupies the padding after `color`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LightUniform {
+    /// Normalized direction the light travels.
+    pub direction: [f32; 3],
+    pub _padding0: f32,
+    pub color: [f32; 3],
+    pub point_light_count: u32,
+    pub point_lights: [PointLightUniform; MAX_POINT_LIGHTS],
+}
+
+impl LightUniform {
+    pub fn new(direction: glam::Vec3, color: glam::Vec3) -> Self {
+        Self {
+            direction: direction.normalize_or(glam::Vec3::NEG_Y).to_array(),
+            _padding0: 0.0,
+            color: color.to_array(),
+            point_light_count: 0,
+            point_lights: [PointLightUniform::default(); MAX_POINT_LIGHTS],
+        }
+    }
+
+    /// Copies the first [`MAX_POINT_LIGHTS`] lights; the rest are ignored.
+    pub fn with_point_lights(mut self, lights: &[PointLight]) -> Self {
+        let count = lights.len().min(MAX_POINT_LIGHTS);
+        for (slot, light) in self.point_lights.iter_mut().zip(&lights[..count]) {
+            *slot = light.uniform();
+        }
+        self.point_light_count = count as u32;
+        self
+    }
+}
+
+/// Directional light shadow map parameters: the light's view-projection
+/// used by the depth pass and by lit shaders to look up the shadow map.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ShadowUniform {
+    pub light_view_proj: [[f32; 4]; 4],
+    /// Depth offset subtracted before the comparison to avoid shadow acne.
+    pub bias: f32,
+    /// Non-zero when the shadow map holds the scene; otherwise everything is lit.
+    pub enabled: u32,
+    pub _padding: [u32; 2],
+}
+
+impl ShadowUniform {
+    pub fn new(light_view_proj: glam::Mat4, bias: f32, enabled: bool) -> Self {
+        Self {
+            light_view_proj: light_view_proj.to_cols_array_2d(),
+            bias,
+            enabled: enabled as u32,
+            _padding: [0; 2],
+        }
+    }
+}
+
+impl Default for ShadowUniform {
+    fn default() -> Self {
+        Self::new(glam::Mat4::IDENTITY, 0.0, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_uniform_layout_matches_wgsl() {
+        // WGSL側: vec3 + f32 が2組で32バイト、配列要素は32バイト
+        assert_eq!(std::mem::size_of::<PointLightUniform>(), 32);
+        assert_eq!(
+            std::mem::size_of::<LightUniform>(),
+            32 + 32 * MAX_POINT_LIGHTS
+        );
+    }
+
+    #[test]
+    fn test_shadow_uniform_layout_matches_wgsl() {
+        // WGSL側: mat4x4 の後に f32 と u32、構造体は16バイト境界に丸められる
+        assert_eq!(std::mem::size_of::<ShadowUniform>(), 80);
+        assert_eq!(std::mem::offset_of!(ShadowUniform, enabled), 68);
+    }
+
+    #[test]
+    fn test_global_uniform_layout_matches_wgsl() {
+        // WGSL側: f32 の後に 8 バイト境界の vec2 が続き、全体で16バイト
+        assert_eq!(std::mem::size_of::<GlobalUniform>(), 16);
+        assert_eq!(std::mem::offset_of!(GlobalUniform, resolution), 8);
+    }
+
+    #[test]
+    fn test_point_lights_are_capped() {
+        let lights = vec![PointLight::new(glam::Vec3::ONE, glam::Vec3::ONE, 2.0); 6];
+
+        let uniform =
+            LightUniform::new(glam::Vec3::NEG_Y, glam::Vec3::ONE).with_point_lights(&lights);
+
+        assert_eq!(uniform.point_light_count, MAX_POINT_LIGHTS as u32);
+        assert_eq!(uniform.point_lights[3].radius, 2.0);
+    }
+}
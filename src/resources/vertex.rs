@@ -2,6 +2,14 @@ use bytemuck::{Pod, Zeroable};
 
 pub trait VertexTrait: bytemuck::Pod {
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a>;
+
+    /// Object-space position of the vertex.
+    fn position(&self) -> [f32; 3];
+
+    /// Mutable normal of the vertex, or `None` for layouts without one.
+    fn normal_mut(&mut self) -> Option<&mut [f32; 3]> {
+        None
+    }
 }
 
 // 基本的な頂点（位置と色）
@@ -31,6 +39,97 @@ impl VertexTrait for ColorVertex {
             ],
         }
     }
+
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
+}
+
+// ライティング用の頂点（位置・法線・色）
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct NormalVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub color: [f32; 3],
+}
+
+impl NormalVertex {
+    /// Vertex with a zero normal, filled in by `Primitive::create_mesh`.
+    pub fn new(position: [f32; 3], color: [f32; 3]) -> Self {
+        Self {
+            position,
+            normal: [0.0; 3],
+            color,
+        }
+    }
+}
+
+impl VertexTrait for NormalVertex {
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 24,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+            ],
+        }
+    }
+
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    fn normal_mut(&mut self) -> Option<&mut [f32; 3]> {
+        Some(&mut self.normal)
+    }
+}
+
+// テクスチャ付き頂点（位置とUV）
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct TextureVertex {
+    pub position: [f32; 3],
+    pub tex_coords: [f32; 2],
+}
+
+impl VertexTrait for TextureVertex {
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+            ],
+        }
+    }
+
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
 }
 
 // PBR対応
@@ -72,4 +171,41 @@ impl VertexTrait for Vertex {
             ],
         }
     }
+
+    fn position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    fn normal_mut(&mut self) -> Option<&mut [f32; 3]> {
+        Some(&mut self.normal)
+    }
+}
+
+// インスタンス描画用のオブジェクトごとのデータ（モデル行列と色）
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct InstanceData {
+    pub model: [[f32; 4]; 4],
+    pub color: [f32; 4],
+}
+
+impl InstanceData {
+    /// Model matrix columns at locations 5-8 and color at 9, above every
+    /// vertex layout's locations.
+    const ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Float32x4,
+        8 => Float32x4,
+        9 => Float32x4,
+    ];
+
+    /// Layout of the per-instance buffer bound at vertex slot 1.
+    pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
 }
@@ -1,9 +1,41 @@
-use crate::core::config::CameraConfig;
+use crate::{
+    core::config::{CameraConfig, CameraMode, CameraMovement, ProjectionConfig},
+    graphics::taa::jittered_projection,
+};
+
+/// Pitch limit for orbiting, just short of the poles to avoid flipping.
+const MAX_ORBIT_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+/// Closest distance the orbit camera can zoom to its target.
+const MIN_ORBIT_RADIUS: f32 = 0.1;
+/// Field-of-view range reachable by zooming, in degrees.
+const MIN_FOV_DEGREES: f32 = 10.0;
+const MAX_FOV_DEGREES: f32 = 120.0;
+
+/// Projection used to map view space to clip space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    /// Perspective projection with a vertical field of view in radians.
+    Perspective { fovy: f32 },
+    /// Orthographic projection showing `height` world units vertically; the
+    /// width follows from the aspect ratio.
+    Orthographic { height: f32 },
+}
+
+impl Projection {
+    pub fn from_config(config: &CameraConfig) -> Self {
+        match config.projection {
+            ProjectionConfig::Perspective => Projection::Perspective {
+                fovy: config.fov_degrees.to_radians(),
+            },
+            ProjectionConfig::Orthographic { height } => Projection::Orthographic { height },
+        }
+    }
+}
 
 /// 3D camera for view and projection matrix calculations.
 ///
 /// Provides first-person camera controls with position, target-based rotation,
-/// and perspective projection. Supports movement and rotation operations
+/// and perspective or orthographic projection. Supports movement and rotation operations
 /// commonly used in 3D applications.
 ///
 /// # Fields
@@ -12,7 +44,7 @@ use crate::core::config::CameraConfig;
 /// - `target` - Point the camera is looking at
 /// - `up` - Camera's up direction vector (usually Y-axis)
 /// - `aspect` - Aspect ratio (window_width / window_height)
-/// - `fovy` - Field of view angle in radians
+/// - `projection` - Perspective (field of view) or orthographic (view height)
 /// - `znear` - Near clipping plane distance (0.1 - 1.0 typical)
 /// - `zfar` - Far clipping plane distance (100.0 - 10000.0 typical)
 ///
@@ -29,9 +61,18 @@ pub struct Camera {
     pub target: glam::Vec3,
     pub up: glam::Vec3,
     pub aspect: f32,
-    pub fovy: f32,
+    pub projection: Projection,
     pub znear: f32,
     pub zfar: f32,
+    pub mode: CameraMode,
+    /// Whether FPS movement follows the view (fly) or stays level (walk).
+    pub movement: CameraMovement,
+    /// Subpixel projection offset in NDC units (temporal anti-aliasing).
+    pub jitter: glam::Vec2,
+    /// Rate at which the field of view approaches `target_fov`, per second.
+    pub zoom_speed: f32,
+    /// Field of view being zoomed towards, in degrees; `None` when idle.
+    target_fov: Option<f32>,
 }
 
 impl Camera {
@@ -45,38 +86,284 @@ impl Camera {
             target: glam::Vec3::ZERO,
             up: glam::Vec3::Y,
             aspect,
-            fovy: config.fov_degrees.to_radians(),
+            projection: Projection::from_config(config),
             znear: config.znear,
             zfar: config.zfar,
+            mode: config.mode,
+            movement: config.movement,
+            jitter: glam::Vec2::ZERO,
+            zoom_speed: config.zoom_speed,
+            target_fov: None,
+        }
+    }
+
+    /// 位置・注視点・上方向をまとめて設定する
+    #[allow(dead_code)]
+    pub fn look_at(&mut self, eye: glam::Vec3, target: glam::Vec3, up: glam::Vec3) {
+        self.eye = eye;
+        self.target = target;
+        self.up = up;
+    }
+
+    /// ターゲットを中心にカメラを周回（半径は維持、ピッチは極付近で制限）
+    pub fn orbit(&mut self, yaw_delta: f32, pitch_delta: f32) {
+        let offset = self.eye - self.target;
+        let radius = offset.length();
+        if radius <= f32::EPSILON {
+            return;
         }
+
+        let yaw = offset.x.atan2(offset.z) + yaw_delta;
+        let pitch = ((offset.y / radius).clamp(-1.0, 1.0).asin() + pitch_delta)
+            .clamp(-MAX_ORBIT_PITCH, MAX_ORBIT_PITCH);
+
+        self.eye = self.target + Self::spherical_offset(radius, yaw, pitch);
     }
 
+    /// ターゲットとの距離を変更（正の値で近づく）
+    pub fn zoom(&mut self, delta: f32) {
+        let offset = self.eye - self.target;
+        let radius = offset.length();
+        if radius <= f32::EPSILON {
+            return;
+        }
+
+        let new_radius = (radius - delta).max(MIN_ORBIT_RADIUS);
+        self.eye = self.target + offset / radius * new_radius;
+    }
+
+    /// 視野角を狭めてズーム（正の値でズームイン、度単位）
+    ///
+    /// 正射影では表示する高さを同じ比率で変える。
+    pub fn zoom_fov(&mut self, delta_degrees: f32) {
+        match &mut self.projection {
+            Projection::Perspective { fovy } => {
+                let degrees =
+                    (fovy.to_degrees() - delta_degrees).clamp(MIN_FOV_DEGREES, MAX_FOV_DEGREES);
+                *fovy = degrees.to_radians();
+            }
+            Projection::Orthographic { height } => {
+                let scale = (1.0 - delta_degrees / 100.0).max(0.1);
+                *height = (*height * scale).max(f32::EPSILON);
+            }
+        }
+    }
+
+    /// 視野角をすぐには変えず、`update_zoom` で徐々に近づける目標を設定する（度単位）
+    ///
+    /// 目標は 10°〜120° に制限する。正射影では何もしない。
+    pub fn set_target_fov(&mut self, degrees: f32) {
+        if let Projection::Perspective { .. } = self.projection {
+            self.target_fov = Some(degrees.clamp(MIN_FOV_DEGREES, MAX_FOV_DEGREES));
+        }
+    }
+
+    /// Field of view the camera is zooming towards (or its current one when
+    /// idle), in degrees; `None` for orthographic projections.
+    pub fn target_fov(&self) -> Option<f32> {
+        match self.projection {
+            Projection::Perspective { fovy } => Some(self.target_fov.unwrap_or(fovy.to_degrees())),
+            Projection::Orthographic { .. } => None,
+        }
+    }
+
+    /// Moves the field of view towards the target set by `set_target_fov`.
+    ///
+    /// The remaining difference decays exponentially with `zoom_speed`, so
+    /// the motion is the same at any frame rate.
+    pub fn update_zoom(&mut self, dt: f32) {
+        let (Some(target), Projection::Perspective { fovy }) =
+            (self.target_fov, &mut self.projection)
+        else {
+            return;
+        };
+
+        let t = 1.0 - (-self.zoom_speed * dt).exp();
+        let degrees = fovy.to_degrees() + (target - fovy.to_degrees()) * t;
+        // 十分近づいたら目標に合わせて終了する
+        if (target - degrees).abs() < 0.01 {
+            *fovy = target.to_radians();
+            self.target_fov = None;
+        } else {
+            *fovy = degrees.to_radians();
+        }
+    }
+
+    fn spherical_offset(radius: f32, yaw: f32, pitch: f32) -> glam::Vec3 {
+        glam::vec3(
+            pitch.cos() * yaw.sin(),
+            pitch.sin(),
+            pitch.cos() * yaw.cos(),
+        ) * radius
+    }
+
+    /// Clamps `eye` into the axis-aligned box `[min, max]`.
+    ///
+    /// `target` is shifted by the same amount so the view direction is kept.
+    pub fn clamp_to_bounds(&mut self, min: glam::Vec3, max: glam::Vec3) {
+        let clamped = self.eye.clamp(min, max);
+        let offset = clamped - self.eye;
+        self.eye = clamped;
+        self.target += offset;
+    }
+
+    /// Updates the aspect ratio from a viewport size (ignored if either side is 0).
+    pub fn set_viewport(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.aspect = width as f32 / height as f32;
+    }
+
+    /// Builds the view-projection matrix used for rendering, including `jitter`.
     pub fn build_view_proj_matrix(&self) -> glam::Mat4 {
+        jittered_projection(self.build_unjittered_view_proj_matrix(), self.jitter)
+    }
+
+    pub fn build_unjittered_view_proj_matrix(&self) -> glam::Mat4 {
         let veiw = glam::Mat4::look_at_rh(self.eye, self.target, self.up);
-        let proj = glam::Mat4::perspective_rh(self.fovy, self.aspect, self.znear, self.zfar);
+        let proj = match self.projection {
+            Projection::Perspective { fovy } => {
+                glam::Mat4::perspective_rh(fovy, self.aspect, self.znear, self.zfar)
+            }
+            Projection::Orthographic { height } => {
+                let half_height = height * 0.5;
+                let half_width = half_height * self.aspect;
+                glam::Mat4::orthographic_rh(
+                    -half_width,
+                    half_width,
+                    -half_height,
+                    half_height,
+                    self.znear,
+                    self.zfar,
+                )
+            }
+        };
 
         proj * veiw
     }
 
-    /// カメラを前後に移動
+    /// Returns the world-space ray `(origin, direction)` through a screen
+    /// position, for picking.
+    ///
+    /// `mouse_pos` is in pixels from the top-left corner of a viewport of
+    /// `viewport_size` pixels. The origin lies on the near plane and the
+    /// direction is normalized; with an orthographic projection all rays are
+    /// parallel.
+    pub fn screen_to_ray(
+        &self,
+        mouse_pos: glam::Vec2,
+        viewport_size: glam::Vec2,
+    ) -> (glam::Vec3, glam::Vec3) {
+        // 画面座標は下向きが +Y なので NDC では反転する
+        let ndc = glam::vec2(
+            mouse_pos.x / viewport_size.x * 2.0 - 1.0,
+            1.0 - mouse_pos.y / viewport_size.y * 2.0,
+        );
+        let inverse_view_proj = self.build_unjittered_view_proj_matrix().inverse();
+        let near = inverse_view_proj.project_point3(ndc.extend(0.0));
+        let far = inverse_view_proj.project_point3(ndc.extend(1.0));
+
+        (near, (far - near).normalize())
+    }
+
+    /// Camera-to-world transform (inverse of the view matrix): the camera sits
+    /// at `eye` looking down its local -Z axis.
+    pub fn world_matrix(&self) -> glam::Mat4 {
+        glam::Mat4::look_at_rh(self.eye, self.target, self.up).inverse()
+    }
+
+    /// Unit vector from the eye toward the target.
+    pub fn forward(&self) -> glam::Vec3 {
+        (self.target - self.eye).normalize()
+    }
+
+    /// Camera-local right vector (forward × world up); stays horizontal
+    /// however the camera is pitched.
+    pub fn right(&self) -> glam::Vec3 {
+        self.forward().cross(self.up).normalize()
+    }
+
+    /// Camera-local up vector (right × forward), tilting with the pitch.
+    pub fn local_up(&self) -> glam::Vec3 {
+        self.right().cross(self.forward())
+    }
+
+    /// Moves the eye without changing the target.
+    #[allow(dead_code)]
+    pub fn set_eye(&mut self, eye: glam::Vec3) {
+        self.eye = eye;
+    }
+
+    /// Points the camera at `target` without moving the eye.
+    #[allow(dead_code)]
+    pub fn set_target(&mut self, target: glam::Vec3) {
+        self.target = target;
+    }
+
+    /// Distance from the eye to the target (the orbit radius).
+    #[allow(dead_code)]
+    pub fn distance_to_target(&self) -> f32 {
+        self.eye.distance(self.target)
+    }
+
+    /// Targets the center of the box `[min, max]` and backs the eye off along
+    /// the current view direction until the whole box is in view.
+    ///
+    /// The box's bounding sphere is fitted to the narrower of the vertical
+    /// and horizontal field of view. Orthographic cameras instead change
+    /// their view height to fit the sphere.
+    pub fn frame_bounds(&mut self, min: glam::Vec3, max: glam::Vec3) {
+        let center = (min + max) * 0.5;
+        let radius = ((max - min) * 0.5).length().max(f32::EPSILON);
+        let direction = (self.target - self.eye)
+            .try_normalize()
+            .unwrap_or(glam::Vec3::NEG_Z);
+
+        let distance = match &mut self.projection {
+            Projection::Perspective { fovy } => {
+                let half_fovy = *fovy * 0.5;
+                let half_fovx = (half_fovy.tan() * self.aspect).atan();
+                radius / half_fovy.min(half_fovx).sin()
+            }
+            Projection::Orthographic { height } => {
+                *height = 2.0 * radius * (1.0 / self.aspect).max(1.0);
+                // 手前のクリップ面に掛からないよう半径より離す
+                2.0 * radius + self.znear
+            }
+        };
+
+        self.target = center;
+        self.eye = center - direction * distance;
+    }
+
+    /// カメラを前後に移動（歩行時は高さを変えない）
     pub fn move_forward(&mut self, delta: f32) {
-        let forward = (self.target - self.eye).normalize();
-        self.eye += forward * delta;
-        self.target += forward * delta;
+        let forward = match self.movement {
+            CameraMovement::Fly => self.forward(),
+            CameraMovement::Walk => self.forward().reject_from(self.up).normalize_or_zero(),
+        };
+        self.translate(forward * delta);
     }
 
-    /// カメラを左右に移動
+    /// カメラを左右に移動（右方向は常に水平）
     pub fn move_right(&mut self, delta: f32) {
-        let forward = (self.target - self.eye).normalize();
-        let right = forward.cross(self.up).normalize();
-        self.eye += right * delta;
-        self.target += right * delta;
+        self.translate(self.right() * delta);
     }
 
-    /// カメラを上下に移動
+    /// カメラを上下に移動（飛行時はカメラの上方向、歩行時はワールドの上方向）
     pub fn move_up(&mut self, delta: f32) {
-        self.eye += self.up * delta;
-        self.target += self.up * delta;
+        let up = match self.movement {
+            CameraMovement::Fly => self.local_up(),
+            CameraMovement::Walk => self.up,
+        };
+        self.translate(up * delta);
+    }
+
+    /// 視線方向を保ったまま位置と注視点を動かす
+    fn translate(&mut self, offset: glam::Vec3) {
+        self.eye += offset;
+        self.target += offset;
     }
 
     /// カメラを回転（水平）
@@ -89,10 +376,8 @@ impl Camera {
 
     /// カメラを回転（垂直）
     pub fn rotate_vertical(&mut self, angle: f32) {
-        let forward = (self.target - self.eye).normalize();
-        let right = forward.cross(self.up).normalize();
-        let rotation = glam::Mat3::from_axis_angle(right, angle);
-        let new_direction = rotation * forward;
+        let rotation = glam::Mat3::from_axis_angle(self.right(), angle);
+        let new_direction = rotation * self.forward();
         self.target = self.eye + new_direction;
     }
 }
@@ -158,6 +443,61 @@ mod tests {
         assert!(camera.eye.y > initial_eye.y);
     }
 
+    /// Default camera looking 45° down.
+    fn pitched_camera(movement: CameraMovement) -> Camera {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+        camera.movement = movement;
+        camera.rotate_vertical(-std::f32::consts::FRAC_PI_4);
+        camera
+    }
+
+    #[test]
+    fn test_pitched_strafe_stays_horizontal() {
+        for movement in [CameraMovement::Fly, CameraMovement::Walk] {
+            let mut camera = pitched_camera(movement);
+            let initial_eye = camera.eye;
+
+            camera.move_right(1.0);
+
+            let offset = camera.eye - initial_eye;
+            assert!(
+                offset.y.abs() < 1e-5,
+                "横移動で高さが変わった: {:?}",
+                offset
+            );
+            assert!((offset.length() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_fly_move_up_follows_local_up() {
+        let mut camera = pitched_camera(CameraMovement::Fly);
+        let local_up = camera.local_up();
+        let initial_eye = camera.eye;
+
+        camera.move_up(1.0);
+
+        // 下を向いたカメラの上方向は前に傾く
+        assert!(local_up.z < -0.5, "{:?}", local_up);
+        assert!((camera.eye - initial_eye).abs_diff_eq(local_up, 1e-5));
+        assert!(local_up.dot(camera.forward()).abs() < 1e-5);
+        assert!(local_up.dot(camera.right()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_walk_locks_height_except_move_up() {
+        let mut camera = pitched_camera(CameraMovement::Walk);
+        let initial_eye = camera.eye;
+
+        camera.move_forward(2.0);
+        assert!((camera.eye.y - initial_eye.y).abs() < 1e-5);
+        assert!((camera.eye.z - (initial_eye.z - 2.0)).abs() < 1e-5);
+
+        camera.move_up(1.0);
+        assert!((camera.eye.y - (initial_eye.y + 1.0)).abs() < 1e-5);
+    }
+
     #[test]
     fn test_camera_rotate_horizontal() {
         let config = AppConfig::default();
@@ -213,8 +553,328 @@ mod tests {
         let camera = Camera::new(1.0, &config.camera);
 
         // 視野角が妥当な範囲内にあることを確認
-        assert!(camera.fovy > 0.0 && camera.fovy < std::f32::consts::PI);
+        let Projection::Perspective { fovy } = camera.projection else {
+            panic!("デフォルトは透視投影であるべき");
+        };
+        assert!(fovy > 0.0 && fovy < std::f32::consts::PI);
         assert!(camera.znear > 0.0);
         assert!(camera.zfar > camera.znear);
     }
+
+    #[test]
+    fn test_set_viewport_updates_aspect() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(800.0 / 600.0, &config.camera);
+
+        camera.set_viewport(1920, 600);
+        assert_eq!(camera.aspect, 3.2);
+
+        // 最小化時（0サイズ）はアスペクト比を維持する
+        camera.set_viewport(0, 0);
+        assert_eq!(camera.aspect, 3.2);
+    }
+
+    #[test]
+    fn test_clamp_to_bounds_keeps_view_direction() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+        let min = glam::Vec3::splat(-5.0);
+        let max = glam::Vec3::splat(5.0);
+
+        let initial_direction = camera.target - camera.eye;
+        // 境界を越えるまで前進（-Z方向）
+        camera.move_forward(20.0);
+        camera.clamp_to_bounds(min, max);
+
+        assert_eq!(camera.eye, glam::vec3(0.0, 0.0, -5.0), "境界で止まるべき");
+        let direction = camera.target - camera.eye;
+        assert!(
+            (direction - initial_direction).length() < 1e-5,
+            "視線方向は維持されるべき"
+        );
+    }
+
+    #[test]
+    fn test_clamp_to_bounds_inside_is_noop() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+        let (eye, target) = (camera.eye, camera.target);
+
+        camera.clamp_to_bounds(glam::Vec3::splat(-5.0), glam::Vec3::splat(5.0));
+
+        assert_eq!(camera.eye, eye);
+        assert_eq!(camera.target, target);
+    }
+
+    #[test]
+    fn test_orbit_keeps_radius_and_target() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.orbit(std::f32::consts::FRAC_PI_2, 0.0);
+
+        // Z+ から90度回るとX+側に来るはず
+        assert!((camera.eye - glam::vec3(3.0, 0.0, 0.0)).length() < 1e-5);
+        assert_eq!(camera.target, glam::Vec3::ZERO);
+
+        camera.orbit(0.3, 0.4);
+        assert!(((camera.eye - camera.target).length() - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_orbit_pitch_is_clamped() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.orbit(0.0, 10.0);
+
+        let direction = (camera.eye - camera.target).normalize();
+        // 真上を越えて反転しないこと
+        assert!(direction.y < 1.0 && direction.y > 0.99);
+        assert!(direction.z > 0.0, "極を越えて裏側に回り込んではいけない");
+    }
+
+    #[test]
+    fn test_zoom_changes_radius_with_minimum() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.zoom(1.0);
+        assert!((camera.eye.z - 2.0).abs() < 1e-5);
+
+        camera.zoom(-2.0);
+        assert!((camera.eye.z - 4.0).abs() < 1e-5);
+
+        camera.zoom(100.0);
+        assert!((camera.eye.z - MIN_ORBIT_RADIUS).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_orthographic_has_no_foreshortening() {
+        let mut config = AppConfig::default();
+        config.camera.projection = ProjectionConfig::Orthographic { height: 4.0 };
+        let camera = Camera::new(2.0, &config.camera);
+        let matrix = camera.build_view_proj_matrix();
+
+        // 奥行きが違っても同じ画面位置に投影される
+        let near = matrix.project_point3(glam::vec3(1.0, 1.0, 0.0));
+        let far = matrix.project_point3(glam::vec3(1.0, 1.0, -10.0));
+        assert!((near.truncate() - far.truncate()).length() < 1e-5);
+
+        // 高さ4の半分 → NDC 1.0、幅はアスペクト比で広がる
+        assert!((near.y - 0.5).abs() < 1e-5);
+        assert!((near.x - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_zoom_fov_is_clamped() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+        let Projection::Perspective { fovy } = camera.projection else {
+            panic!("既定は透視投影");
+        };
+
+        camera.zoom_fov(5.0);
+        let Projection::Perspective { fovy: zoomed } = camera.projection else {
+            unreachable!();
+        };
+        assert!((zoomed.to_degrees() - (fovy.to_degrees() - 5.0)).abs() < 1e-3);
+
+        camera.zoom_fov(1000.0);
+        assert_eq!(
+            camera.projection,
+            Projection::Perspective {
+                fovy: MIN_FOV_DEGREES.to_radians()
+            }
+        );
+        camera.zoom_fov(-1000.0);
+        assert_eq!(
+            camera.projection,
+            Projection::Perspective {
+                fovy: MAX_FOV_DEGREES.to_radians()
+            }
+        );
+    }
+
+    #[test]
+    fn test_zoom_fov_scales_orthographic_height() {
+        let mut config = AppConfig::default();
+        config.camera.projection = ProjectionConfig::Orthographic { height: 4.0 };
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.zoom_fov(50.0);
+
+        assert_eq!(camera.projection, Projection::Orthographic { height: 2.0 });
+    }
+
+    #[test]
+    fn test_look_at_sets_eye_target_and_up() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.look_at(
+            glam::vec3(5.0, 1.0, 0.0),
+            glam::vec3(0.0, 1.0, 0.0),
+            glam::Vec3::Z,
+        );
+
+        assert_eq!(camera.eye, glam::vec3(5.0, 1.0, 0.0));
+        assert_eq!(camera.target, glam::vec3(0.0, 1.0, 0.0));
+        assert_eq!(camera.up, glam::Vec3::Z);
+        assert!(camera.forward().abs_diff_eq(glam::Vec3::NEG_X, 1e-6));
+    }
+
+    #[test]
+    fn test_screen_to_ray_through_center_looks_forward() {
+        let config = AppConfig::default();
+        let camera = Camera::new(1.0, &config.camera);
+        let viewport = glam::vec2(800.0, 800.0);
+
+        let (origin, direction) = camera.screen_to_ray(viewport * 0.5, viewport);
+
+        assert!(direction.abs_diff_eq(glam::Vec3::NEG_Z, 1e-5));
+        // 原点はニア平面上にある
+        assert!(origin.abs_diff_eq(camera.eye - glam::vec3(0.0, 0.0, camera.znear), 1e-4));
+    }
+
+    #[test]
+    fn test_screen_to_ray_at_edges_follows_fov() {
+        let config = AppConfig::default();
+        let camera = Camera::new(1.0, &config.camera);
+        let viewport = glam::vec2(800.0, 800.0);
+        let half_fov_tan = (config.camera.fov_degrees.to_radians() * 0.5).tan();
+
+        let (_, right) = camera.screen_to_ray(glam::vec2(800.0, 400.0), viewport);
+        assert!((right.x / -right.z - half_fov_tan).abs() < 1e-4);
+
+        // 画面の上端は +Y 方向
+        let (_, top) = camera.screen_to_ray(glam::vec2(400.0, 0.0), viewport);
+        assert!((top.y / -top.z - half_fov_tan).abs() < 1e-4);
+    }
+
+    fn fov_degrees(camera: &Camera) -> f32 {
+        let Projection::Perspective { fovy } = camera.projection else {
+            panic!("透視投影ではない");
+        };
+        fovy.to_degrees()
+    }
+
+    #[test]
+    fn test_target_fov_is_reached_smoothly() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.set_target_fov(30.0);
+        assert_eq!(fov_degrees(&camera), 45.0, "すぐには変わらない");
+
+        camera.update_zoom(1.0 / 60.0);
+        let first = fov_degrees(&camera);
+        assert!(first < 45.0 && first > 30.0, "{}", first);
+
+        for _ in 0..120 {
+            camera.update_zoom(1.0 / 60.0);
+        }
+        assert!((fov_degrees(&camera) - 30.0).abs() < 1e-4);
+        assert_eq!(
+            camera.target_fov(),
+            Some(fov_degrees(&camera)),
+            "到達後は待機状態"
+        );
+    }
+
+    #[test]
+    fn test_zoom_is_frame_rate_independent() {
+        let config = AppConfig::default();
+        let mut coarse = Camera::new(1.0, &config.camera);
+        let mut fine = Camera::new(1.0, &config.camera);
+        coarse.set_target_fov(90.0);
+        fine.set_target_fov(90.0);
+
+        coarse.update_zoom(0.1);
+        for _ in 0..10 {
+            fine.update_zoom(0.01);
+        }
+
+        assert!((fov_degrees(&coarse) - fov_degrees(&fine)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_target_fov_is_clamped() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.set_target_fov(1.0);
+        assert_eq!(camera.target_fov(), Some(MIN_FOV_DEGREES));
+        camera.set_target_fov(500.0);
+        assert_eq!(camera.target_fov(), Some(MAX_FOV_DEGREES));
+    }
+
+    #[test]
+    fn test_forward_is_normalized_target_minus_eye() {
+        let config = AppConfig::default();
+        let mut camera = Camera::new(1.0, &config.camera);
+
+        camera.set_eye(glam::vec3(1.0, 2.0, 3.0));
+        camera.set_target(glam::vec3(4.0, -2.0, 3.0));
+
+        assert_eq!(camera.distance_to_target(), 5.0);
+        assert!(
+            camera
+                .forward()
+                .abs_diff_eq((camera.target - camera.eye).normalize(), 1e-6)
+        );
+        assert!(
+            camera
+                .forward()
+                .abs_diff_eq(glam::vec3(0.6, -0.8, 0.0), 1e-6)
+        );
+    }
+
+    /// Returns `true` if every corner of `[min, max]` projects inside the view.
+    fn box_in_view(camera: &Camera, min: glam::Vec3, max: glam::Vec3) -> bool {
+        let view_proj = camera.build_unjittered_view_proj_matrix();
+        (0..8).all(|i| {
+            let corner = glam::vec3(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            );
+            let ndc = view_proj.project_point3(corner);
+            ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && (0.0..=1.0).contains(&ndc.z)
+        })
+    }
+
+    #[test]
+    fn test_frame_bounds_fits_box_in_view() {
+        let config = AppConfig::default();
+        let min = glam::vec3(8.0, -1.0, -3.0);
+        let max = glam::vec3(12.0, 3.0, 1.0);
+
+        // 横長・縦長どちらの画面でも収まる
+        for aspect in [16.0 / 9.0, 0.5] {
+            let mut camera = Camera::new(aspect, &config.camera);
+            assert!(!box_in_view(&camera, min, max));
+
+            camera.frame_bounds(min, max);
+
+            assert_eq!(camera.target, glam::vec3(10.0, 1.0, -1.0));
+            assert!(
+                camera.forward().abs_diff_eq(glam::Vec3::NEG_Z, 1e-6),
+                "視線の向きは維持する"
+            );
+            assert!(box_in_view(&camera, min, max), "aspect {}", aspect);
+        }
+    }
+
+    #[test]
+    fn test_frame_bounds_resizes_orthographic_view() {
+        let mut config = AppConfig::default();
+        config.camera.projection = ProjectionConfig::Orthographic { height: 1.0 };
+        let mut camera = Camera::new(1.0, &config.camera);
+        let (min, max) = (glam::Vec3::splat(-2.0), glam::Vec3::splat(2.0));
+
+        camera.frame_bounds(min, max);
+
+        assert!(box_in_view(&camera, min, max));
+    }
 }
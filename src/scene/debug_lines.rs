@@ -0,0 +1,108 @@
+use crate::scene::transform::Transform;
+
+/// Colors of the X, Y and Z axes drawn by [`DebugLines::draw_axes`].
+pub const AXIS_COLORS: [[f32; 4]; 3] = [
+    [1.0, 0.0, 0.0, 1.0],
+    [0.0, 1.0, 0.0, 1.0],
+    [0.0, 0.0, 1.0, 1.0],
+];
+
+/// End point of a debug line segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLineVertex {
+    pub position: glam::Vec3,
+    pub color: [f32; 4],
+}
+
+/// World-space line segments submitted during a frame (transform axes, rays)
+/// and drawn on top of the scene. The engine clears them after each frame.
+#[derive(Debug, Clone, Default)]
+pub struct DebugLines {
+    vertices: Vec<DebugLineVertex>,
+}
+
+impl DebugLines {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn draw_line(&mut self, start: glam::Vec3, end: glam::Vec3, color: [f32; 4]) {
+        self.vertices.push(DebugLineVertex {
+            position: start,
+            color,
+        });
+        self.vertices.push(DebugLineVertex {
+            position: end,
+            color,
+        });
+    }
+
+    /// Draws the local X, Y and Z axes of `transform` in red, green and blue.
+    ///
+    /// Each axis is one unit long before scaling, so the lines also show the
+    /// transform's scale.
+    pub fn draw_axes(&mut self, transform: &Transform) {
+        let matrix = transform.matrix();
+        let origin = transform.position;
+        let axes = [matrix.x_axis, matrix.y_axis, matrix.z_axis];
+
+        for (axis, color) in axes.into_iter().zip(AXIS_COLORS) {
+            self.draw_line(origin, origin + axis.truncate(), color);
+        }
+    }
+
+    /// Line end points, two per segment.
+    pub fn vertices(&self) -> &[DebugLineVertex] {
+        &self.vertices
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_line_adds_two_vertices() {
+        let mut lines = DebugLines::new();
+        lines.draw_line(glam::Vec3::ZERO, glam::Vec3::X, [1.0; 4]);
+
+        assert_eq!(lines.vertices().len(), 2);
+        assert_eq!(lines.vertices()[1].position, glam::Vec3::X);
+
+        lines.clear();
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn test_draw_axes_follows_transform() {
+        let transform = Transform::new()
+            .with_position(glam::vec3(1.0, 2.0, 3.0))
+            .with_rotation(glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+            .with_scale(glam::Vec3::splat(2.0));
+        let mut lines = DebugLines::new();
+
+        lines.draw_axes(&transform);
+
+        let vertices = lines.vertices();
+        assert_eq!(vertices.len(), 6);
+        assert!(
+            vertices
+                .iter()
+                .step_by(2)
+                .all(|v| v.position == transform.position)
+        );
+        // Z軸まわりに90°回すと X軸は +Y を向き、長さはスケール倍になる
+        let x_end = vertices[1].position;
+        assert!(x_end.abs_diff_eq(glam::vec3(1.0, 4.0, 3.0), 1e-5));
+        assert_eq!(vertices[1].color, AXIS_COLORS[0]);
+        assert_eq!(vertices[5].color, AXIS_COLORS[2]);
+    }
+}
@@ -1,34 +1,151 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use crate::{
-    core::config::{AppConfig, MovementConfig},
-    input::InputState,
+    core::{
+        config::{
+            Action, AppConfig, CameraMode, InputMap, MovementConfig, ProjectileConfig,
+            ProjectionConfig,
+        },
+        error::{EngineError, EngineResult},
+    },
+    input::{InputState, gamepad::GamepadAxis},
     resources::{
         manager::{ResourceId, ResourceManager},
+        mesh::{Aabb, Mesh},
+        pipeline::PipelineOptions,
         primitives::{
-            ObjectType, Primitive, cube::Cube, quad::Quad, sphere::Sphere, triangle::Triangle,
+            ObjectType, Primitive,
+            capsule::Capsule,
+            cone::Cone,
+            cube::Cube,
+            grid::Grid,
+            plane,
+            quad::{Quad, TexturedQuad},
+            sphere::Sphere,
+            torus::Torus,
+            triangle::Triangle,
         },
-        uniforms::CameraUniform,
-        vertex::{ColorVertex, VertexTrait},
+        texture::Texture,
+        uniforms::{CameraUniform, GlobalUniform, LightUniform, MAX_POINT_LIGHTS, ShadowUniform},
+        vertex::{ColorVertex, NormalVertex, TextureVertex, VertexTrait},
     },
     scene::{
         Scene,
-        camera::Camera,
+        camera::{Camera, Projection},
+        debug_lines::DebugLines,
+        light::{DirectionalLight, PointLight, ShadowPass, Sun},
+        physics::{FixedTimestep, Rigidbody},
         render_object::{ObjectId, RenderObject},
-        transform::Transform,
+        snapshot::{ObjectDescriptor, SceneSnapshot},
+        transform::{Plane, Transform},
+        viewport::{SceneView, Viewport},
     },
 };
 
+/// Orbit radius change per mouse-wheel line.
+const ORBIT_ZOOM_STEP: f32 = 0.5;
+/// Field-of-view change per mouse-wheel line in FPS mode, in degrees.
+const FOV_ZOOM_STEP_DEGREES: f32 = 2.0;
+/// White 1x1 texture used by `ObjectType::TexturedQuad` until a texture is assigned.
+const DEFAULT_TEXTURE: &str = "default_white_texture";
+/// Distance in front of the camera at which projectiles spawn.
+const PROJECTILE_SPAWN_DISTANCE: f32 = 1.0;
+/// Shader files read at startup (and reloaded with F5) when hot-reload is enabled.
+// ホットリロード時のシェーダーファイル（`[debug] shader_dir` からの相対パス）
+const BASIC_SHADER_PATH: &str = "basic/triangle.wgsl";
+const TEXTURED_SHADER_PATH: &str = "basic/textured.wgsl";
+const INSTANCED_SHADER_PATH: &str = "basic/instanced.wgsl";
+const PULSE_SHADER_PATH: &str = "basic/pulse.wgsl";
+const GRID_SHADER_PATH: &str = "basic/grid.wgsl";
+/// Width and height of the directional light's shadow map, in texels.
+const SHADOW_MAP_SIZE: u32 = 2048;
+/// Offset towards the light applied before the shadow map comparison, in
+/// world units, so surfaces do not shadow themselves (shadow acne).
+const SHADOW_BIAS: f32 = 0.02;
+
+/// Extra camera drawn into its own viewport, with its own camera uniform.
+struct CameraView {
+    camera: Camera,
+    viewport: Viewport,
+    uniform: CameraUniform,
+    buffer: Option<Arc<wgpu::Buffer>>,
+    bind_group: Option<Arc<wgpu::BindGroup>>,
+}
+
 pub struct DemoScene {
     render_objects: Vec<RenderObject>,
     camera: Camera,
     camera_uniform: CameraUniform,
     camera_buffer: Option<Arc<wgpu::Buffer>>,
     camera_bind_group: Option<Arc<wgpu::BindGroup>>,
+    camera_bind_group_layout: Option<wgpu::BindGroupLayout>,
+    /// Region of the render target the main camera draws into.
+    main_viewport: Viewport,
+    /// Cameras drawn next to the main one (split screen); not moved by input.
+    camera_views: Vec<CameraView>,
+    /// Aspect ratio of the whole render target; each camera takes its
+    /// viewport's share of it.
+    target_aspect: f32,
+    global_uniform: GlobalUniform,
+    /// Time and resolution, bound at group 0, binding 1 next to the camera.
+    global_buffer: Option<Arc<wgpu::Buffer>>,
+    light_uniform: LightUniform,
+    light_buffer: Option<Arc<wgpu::Buffer>>,
+    light_bind_group: Option<Arc<wgpu::BindGroup>>,
+    /// Render a shadow map from the directional light and sample it when lighting.
+    shadows: bool,
+    shadow_uniform: ShadowUniform,
+    /// Light view-projection and bias, shared by the light bind group and the
+    /// shadow pass bind group.
+    shadow_buffer: Option<Arc<wgpu::Buffer>>,
+    /// Group 0 of the shadow pipelines.
+    shadow_bind_group: Option<Arc<wgpu::BindGroup>>,
+    /// Depth texture sampled by lit shaders; 1x1 and never rendered while
+    /// shadows are off.
+    shadow_map: Option<Arc<Texture>>,
+    model_bind_group_layout: Option<wgpu::BindGroupLayout>,
+    texture_bind_group_layout: Option<wgpu::BindGroupLayout>,
     initialized: bool,
     config: MovementConfig,
+    keybindings: InputMap,
     resource_manager: Option<ResourceManager>,
+    /// Shared primitive meshes this scene holds a reference to (see
+    /// `ResourceManager::retain_mesh`), released once none of its objects
+    /// draws them. Meshes registered by the caller are left to
+    /// `ResourceManager::remove_mesh`.
+    owned_meshes: HashSet<ResourceId>,
     pipeline_id: ResourceId,
+    /// Line-mode variant of the basic pipeline; `None` if the device cannot draw lines.
+    wireframe_pipeline_id: Option<ResourceId>,
+    /// Non-culling variant of the basic pipeline for flat primitives; `None`
+    /// unless `double_sided_flat` is enabled.
+    double_sided_pipeline_id: Option<ResourceId>,
+    /// Draw both sides of flat primitives (triangles, quads, planes).
+    double_sided_flat: bool,
+    textured_pipeline_id: ResourceId,
+    /// Basic pipeline variant whose color cycles with the global time.
+    pulse_pipeline_id: ResourceId,
+    /// Line-list pipeline and mesh of the reference grid overlay.
+    grid_pipeline_id: ResourceId,
+    grid_mesh_id: ResourceId,
+    grid_size: u32,
+    grid_visible: bool,
+    wireframe: bool,
+    /// Load shaders from disk so F5 can reload them.
+    shader_hot_reload: bool,
+    /// Directory the hot-reloaded shader files are read from.
+    shader_dir: std::path::PathBuf,
+    pipeline_options: PipelineOptions,
+    light: DirectionalLight,
+    point_lights: Vec<PointLight>,
+    sun: Sun,
+    projectile: ProjectileConfig,
+    /// Left button state last frame, so holding it fires only once.
+    fire_was_pressed: bool,
+    physics_timestep: FixedTimestep,
+    /// Objects to spawn once the scene is initialized.
+    pending_snapshot: Option<SceneSnapshot>,
+    debug_lines: DebugLines,
 }
 
 impl DemoScene {
@@ -39,23 +156,90 @@ impl DemoScene {
             camera_uniform: CameraUniform::new(),
             camera_buffer: None,
             camera_bind_group: None,
+            camera_bind_group_layout: None,
+            main_viewport: Viewport::FULL,
+            camera_views: Vec::new(),
+            target_aspect: aspect,
+            global_uniform: GlobalUniform::default(),
+            global_buffer: None,
+            light_uniform: LightUniform::new(glam::Vec3::NEG_Y, glam::Vec3::ONE),
+            light_buffer: None,
+            light_bind_group: None,
+            shadows: config.rendering.shadows,
+            shadow_uniform: ShadowUniform::default(),
+            shadow_buffer: None,
+            shadow_bind_group: None,
+            shadow_map: None,
+            model_bind_group_layout: None,
+            texture_bind_group_layout: None,
             initialized: false,
             config: config.movement.clone(),
+            keybindings: config.keybindings.clone(),
             resource_manager: None,
+            owned_meshes: HashSet::new(),
             pipeline_id: ResourceId::new("basic_pipeline"),
+            wireframe_pipeline_id: None,
+            double_sided_pipeline_id: None,
+            double_sided_flat: config.rendering.double_sided_flat,
+            textured_pipeline_id: ResourceId::new("textured_pipeline"),
+            pulse_pipeline_id: ResourceId::new("pulse_pipeline"),
+            grid_pipeline_id: ResourceId::new("grid_pipeline"),
+            grid_mesh_id: ResourceId::new("grid_mesh"),
+            grid_size: config.debug.grid_size,
+            grid_visible: config.debug.show_grid,
+            wireframe: config.rendering.wireframe,
+            shader_hot_reload: config.debug.shader_hot_reload,
+            shader_dir: config.debug.shader_dir.clone().into(),
+            pipeline_options: PipelineOptions {
+                conservative: config.rendering.conservative_raster,
+                ..Default::default()
+            },
+            light: DirectionalLight::from_config(&config.lighting),
+            point_lights: Vec::new(),
+            sun: Sun::new(&config.lighting.sun),
+            projectile: config.projectile.clone(),
+            fire_was_pressed: false,
+            physics_timestep: FixedTimestep::new(
+                config.physics.fixed_timestep,
+                config.physics.max_steps_per_frame,
+            ),
+            pending_snapshot: None,
+            debug_lines: DebugLines::new(),
         }
     }
 
-    fn add_quad(&mut self, position: glam::Vec3) -> ObjectId {
-        let quad_mesh = Quad::create_mesh(self.get_resource_manager_mut().get_device());
+    /// Creates a scene that recreates the objects of `snapshot` when it is
+    /// initialized. Object ids are newly generated.
+    #[allow(dead_code)]
+    pub fn from_snapshot(aspect: f32, config: Arc<AppConfig>, snapshot: SceneSnapshot) -> Self {
+        let mut scene = Self::new(aspect, config);
+        scene.pending_snapshot = Some(snapshot);
+        scene
+    }
+
+    /// Spawns the objects described by `snapshot`.
+    fn spawn_snapshot(&mut self, snapshot: &SceneSnapshot) {
+        for descriptor in &snapshot.objects {
+            let id = self.add_object(descriptor.object_type, glam::Vec3::ZERO);
+            if let Some(object) = self.render_objects.iter_mut().find(|obj| obj.id == id) {
+                object.set_transform(descriptor.transform());
+                object.visible = descriptor.visible;
+            }
+        }
+    }
 
-        let mesh_id = ResourceId::new(&format!("quad_mesh_{}", self.render_objects.len()));
-        self.get_resource_manager_mut()
-            .register_mesh(mesh_id, Arc::new(quad_mesh));
+    /// Adds a primitive drawn with the vertex-color pipeline.
+    ///
+    /// Objects of the same primitive share one mesh, registered as
+    /// `mesh_name` on first use; color them individually with
+    /// `set_object_color`.
+    fn add_primitive<P: Primitive>(&mut self, mesh_name: &str, position: glam::Vec3) -> ObjectId {
+        let mesh_id = ResourceId::new(mesh_name);
+        self.retain_scene_mesh(mesh_id, P::create_mesh);
 
         let transform = Transform::new().with_position(position);
         let mut render_object =
-            RenderObject::new(mesh_id, self.pipeline_id).with_transform(transform);
+            RenderObject::new(mesh_id, self.active_pipeline_id(false)).with_transform(transform);
         let render_object_id = render_object.id;
 
         self.create_model_resource(&mut render_object);
@@ -64,58 +248,344 @@ impl DemoScene {
         render_object_id
     }
 
-    fn add_triangle(&mut self, position: glam::Vec3) -> ObjectId {
-        let triangle_mesh = Triangle::create_mesh(self.get_resource_manager_mut().get_device());
+    /// Registers the shared mesh `mesh_id` if no scene has yet and takes this
+    /// scene's reference to it on first use.
+    fn retain_scene_mesh(
+        &mut self,
+        mesh_id: ResourceId,
+        create_mesh: impl FnOnce(Arc<wgpu::Device>) -> Mesh,
+    ) {
+        let resource_manager = self
+            .resource_manager
+            .as_mut()
+            .expect("Scene not initialized");
+        if resource_manager.get_mesh(&mesh_id).is_none() {
+            let mesh = create_mesh(resource_manager.get_device());
+            resource_manager.register_mesh(mesh_id, Arc::new(mesh));
+        }
+        if self.owned_meshes.insert(mesh_id) {
+            resource_manager.retain_mesh(mesh_id);
+        }
+    }
+
+    /// Pipeline new objects are drawn with, depending on the wireframe toggle
+    /// and, for `flat` primitives, the double-sided setting.
+    fn active_pipeline_id(&self, flat: bool) -> ResourceId {
+        match (self.wireframe_pipeline_id, self.double_sided_pipeline_id) {
+            (Some(wireframe_pipeline_id), _) if self.wireframe => wireframe_pipeline_id,
+            (_, Some(double_sided_pipeline_id)) if flat => double_sided_pipeline_id,
+            _ => self.pipeline_id,
+        }
+    }
 
-        let mesh_id = ResourceId::new(&format!("triangle_mesh_{}", self.render_objects.len()));
-        self.get_resource_manager_mut()
-            .register_mesh(mesh_id, Arc::new(triangle_mesh));
+    fn update_wireframe_toggle(&mut self, input: &InputState) {
+        use winit::keyboard::KeyCode;
 
-        let transform = Transform::new().with_position(position);
-        let mut render_object =
-            RenderObject::new(mesh_id, self.pipeline_id).with_transform(transform);
-        let render_object_id = render_object.id;
+        if input.is_key_just_pressed(KeyCode::F1) {
+            if self.wireframe_pipeline_id.is_some() {
+                self.wireframe = !self.wireframe;
+                let pipeline_id = self.active_pipeline_id(false);
+                let flat_pipeline_id = self.active_pipeline_id(true);
+                // 頂点カラーのオブジェクトのみ切り替える（テクスチャ付きは対象外）
+                for object in &mut self.render_objects {
+                    if object.pipeline_id == self.pipeline_id
+                        || Some(object.pipeline_id) == self.wireframe_pipeline_id
+                        || Some(object.pipeline_id) == self.double_sided_pipeline_id
+                    {
+                        object.pipeline_id = if object.object_type.is_some_and(ObjectType::is_flat)
+                        {
+                            flat_pipeline_id
+                        } else {
+                            pipeline_id
+                        };
+                    }
+                }
+                log::info!("Wireframe rendering: {}", self.wireframe);
+            } else {
+                log::error!("Wireframe rendering is not supported by this device");
+            }
+        }
+    }
 
-        self.create_model_resource(&mut render_object);
-        self.render_objects.push(render_object);
+    /// Toggles the reference grid overlay on F6.
+    fn update_grid_toggle(&mut self, input: &InputState) {
+        use winit::keyboard::KeyCode;
 
-        render_object_id
+        if input.is_key_just_pressed(KeyCode::F6) {
+            self.grid_visible = !self.grid_visible;
+            log::info!("Grid overlay: {}", self.grid_visible);
+        }
     }
 
-    fn add_cube(&mut self, position: glam::Vec3) -> ObjectId {
-        let cube_mesh = Cube::create_mesh(self.get_resource_manager_mut().get_device());
+    /// Reloads the scene's shaders from disk on F5 (hot-reload only).
+    fn update_shader_reload(&mut self, input: &InputState) {
+        use winit::keyboard::KeyCode;
+
+        if input.is_key_just_pressed(KeyCode::F5) {
+            if self.shader_hot_reload {
+                for name in [
+                    "basic_shader",
+                    "instanced_shader",
+                    "textured_shader",
+                    "pulse_shader",
+                    "grid_shader",
+                ] {
+                    // 失敗時は以前のシェーダーとパイプラインのまま続行する
+                    match self
+                        .get_resource_manager_mut()
+                        .reload_shader(ResourceId::new(name))
+                    {
+                        Ok(count) => log::info!("Reloaded {} ({} pipelines)", name, count),
+                        Err(e) => log::error!("Failed to reload {}: {}", name, e),
+                    }
+                }
+            } else {
+                log::warn!("Shader hot-reload is disabled ([debug] shader_hot_reload)");
+            }
+        }
+    }
 
-        let mesh_id = ResourceId::new(&format!("cube_mesh_{}", self.render_objects.len()));
+    /// Creates a scene shader, from `path` under the shader directory when
+    /// hot-reload is enabled and from the embedded `source` otherwise (or if
+    /// the file cannot be read).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::ShaderCompilation` if the shader file is invalid.
+    fn create_scene_shader(
+        &mut self,
+        id: ResourceId,
+        path: &str,
+        source: &str,
+        label: &str,
+    ) -> EngineResult<()> {
+        if self.shader_hot_reload {
+            let path = self.shader_dir.join(path);
+            match self
+                .get_resource_manager_mut()
+                .create_shader_from_path(id, &path, Some(label))
+            {
+                Ok(_) => return Ok(()),
+                Err(EngineError::ResourceNotFound(e)) => log::error!(
+                    "Failed to load {}, using embedded shader: {}",
+                    path.display(),
+                    e
+                ),
+                Err(e) => return Err(e),
+            }
+        }
         self.get_resource_manager_mut()
-            .register_mesh(mesh_id, Arc::new(cube_mesh));
+            .create_shader(id, source, Some(label))
+            .map(|_| ())
+    }
+
+    fn add_textured_quad_with(&mut self, position: glam::Vec3, texture: Arc<Texture>) -> ObjectId {
+        let texture_bind_group_layout = self
+            .texture_bind_group_layout
+            .take()
+            .expect("Scene not initialized");
+
+        // テクスチャはマテリアル側で持つので、メッシュはすべての四角形で共有する
+        let mesh_id = ResourceId::new("textured_quad_mesh");
+        self.retain_scene_mesh(mesh_id, TexturedQuad::create_mesh);
 
         let transform = Transform::new().with_position(position);
         let mut render_object =
-            RenderObject::new(mesh_id, self.pipeline_id).with_transform(transform);
+            RenderObject::new(mesh_id, self.textured_pipeline_id).with_transform(transform);
         let render_object_id = render_object.id;
 
+        let material_bind_group = self
+            .get_resource_manager_mut()
+            .create_bind_group(
+                ResourceId::new(&format!(
+                    "material_bind_group_{}",
+                    render_object_id.as_u32()
+                )),
+                &texture_bind_group_layout,
+                &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&texture.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&texture.sampler),
+                    },
+                ],
+            )
+            .expect("Failed to create material bind group");
+        render_object.material_bind_group = Some(material_bind_group);
+        self.texture_bind_group_layout = Some(texture_bind_group_layout);
+
         self.create_model_resource(&mut render_object);
         self.render_objects.push(render_object);
 
         render_object_id
     }
 
-    fn add_sphere(&mut self, position: glam::Vec3) -> ObjectId {
-        let sphere_mesh = Sphere::create_mesh(self.get_resource_manager_mut().get_device());
+    fn update_fps_camera(&mut self, dt: f32, input: &InputState) {
+        let move_speed = self.config.move_speed * dt;
+        let rotation_speed = self.config.rotation_speed * dt;
+        let active = |action| self.keybindings.is_action_active(action, input);
 
-        let mesh_id = ResourceId::new(&format!("sphere_mesh_{}", self.render_objects.len()));
-        self.get_resource_manager_mut()
-            .register_mesh(mesh_id, Arc::new(sphere_mesh));
+        // 割り当てキー（既定は WASD）でカメラ移動
+        if active(Action::MoveForward) {
+            self.camera.move_forward(move_speed);
+        }
+        if active(Action::MoveBackward) {
+            self.camera.move_forward(-move_speed);
+        }
+        if active(Action::MoveLeft) {
+            self.camera.move_right(-move_speed);
+        }
+        if active(Action::MoveRight) {
+            self.camera.move_right(move_speed);
+        }
 
-        let transform = Transform::new().with_position(position);
-        let mut render_object =
-            RenderObject::new(mesh_id, self.pipeline_id).with_transform(transform);
-        let render_object_id = render_object.id;
+        // 上下移動（既定は Q/E）
+        if active(Action::MoveDown) {
+            self.camera.move_up(-move_speed);
+        }
+        if active(Action::MoveUp) {
+            self.camera.move_up(move_speed);
+        }
 
-        self.create_model_resource(&mut render_object);
-        self.render_objects.push(render_object);
+        // 回転（既定は矢印キー）
+        if active(Action::TurnLeft) {
+            self.camera.rotate_horizontal(rotation_speed);
+        }
+        if active(Action::TurnRight) {
+            self.camera.rotate_horizontal(-rotation_speed);
+        }
+        if active(Action::LookUp) {
+            self.camera.rotate_vertical(rotation_speed);
+        }
+        if active(Action::LookDown) {
+            self.camera.rotate_vertical(-rotation_speed);
+        }
 
-        render_object_id
+        // 左スティックで移動、右スティックで回転
+        let move_stick = glam::vec2(
+            input.gamepad_axis(GamepadAxis::LeftStickX),
+            input.gamepad_axis(GamepadAxis::LeftStickY),
+        );
+        if move_stick != glam::Vec2::ZERO {
+            self.camera.move_forward(move_stick.y * move_speed);
+            self.camera.move_right(move_stick.x * move_speed);
+        }
+        let look_stick = glam::vec2(
+            input.gamepad_axis(GamepadAxis::RightStickX),
+            input.gamepad_axis(GamepadAxis::RightStickY),
+        );
+        if look_stick != glam::Vec2::ZERO {
+            self.camera
+                .rotate_horizontal(-look_stick.x * rotation_speed);
+            self.camera.rotate_vertical(look_stick.y * rotation_speed);
+        }
+
+        // カーソルキャプチャ中はマウス移動で視点を回転
+        if input.is_cursor_captured() {
+            let delta = input.mouse_delta() * self.config.mouse_sensitivity;
+            self.camera.rotate_horizontal(-delta.x);
+            self.camera.rotate_vertical(-delta.y);
+        }
+
+        // ホイールで視野角の目標を変え、毎フレーム滑らかに近づける（正射影は即時）
+        let scroll = input.scroll_delta();
+        if scroll != 0.0 {
+            match self.camera.target_fov() {
+                Some(target) => self
+                    .camera
+                    .set_target_fov(target - scroll * FOV_ZOOM_STEP_DEGREES),
+                None => self.camera.zoom_fov(scroll * FOV_ZOOM_STEP_DEGREES),
+            }
+        }
+    }
+
+    fn update_orbit_camera(&mut self, input: &InputState) {
+        use winit::event::MouseButton;
+
+        // 右ドラッグでターゲット周りを回転
+        if input.is_mouse_button_pressed(MouseButton::Right) {
+            let delta = input.mouse_delta() * self.config.mouse_sensitivity;
+            self.camera.orbit(-delta.x, delta.y);
+        }
+
+        // ホイールで距離を変更
+        let scroll = input.scroll_delta();
+        if scroll != 0.0 {
+            self.camera.zoom(scroll * ORBIT_ZOOM_STEP);
+        }
+    }
+
+    /// Fires projectiles on click and advances physics in fixed steps.
+    fn update_projectiles(&mut self, dt: f32, input: &InputState) {
+        use winit::event::MouseButton;
+
+        // キャプチャ中の左クリックでカメラ前方へ発射（押しっぱなしは1発）
+        let fire_pressed = input.is_mouse_button_pressed(MouseButton::Left);
+        if fire_pressed && !self.fire_was_pressed && input.is_cursor_captured() {
+            let forward = self.camera.forward();
+            self.spawn_projectile(
+                ObjectType::Cube,
+                self.camera.eye + forward * PROJECTILE_SPAWN_DISTANCE,
+                forward * self.projectile.speed,
+            );
+        }
+        self.fire_was_pressed = fire_pressed;
+
+        // 固定ステップで進め、描画時は残り時間の割合で補間する
+        let step = self.physics_timestep.step();
+        let ground_height = self.projectile.ground_height;
+        for _ in 0..self.physics_timestep.advance(dt) {
+            for object in &mut self.render_objects {
+                if object.is_static {
+                    continue;
+                }
+                object.step_physics(step);
+            }
+            let (expired, alive): (Vec<_>, Vec<_>) = std::mem::take(&mut self.render_objects)
+                .into_iter()
+                .partition(|object| object.is_expired(ground_height));
+            self.render_objects = alive;
+            self.release_object_resources(expired);
+        }
+    }
+
+    /// Unregisters the GPU resources of objects that were removed from the scene.
+    ///
+    /// Only this scene's reference to a shared primitive mesh is released,
+    /// and only once no remaining object draws it (mirrored copies and
+    /// `add_mesh_object` instances share their mesh); the mesh itself goes
+    /// away when no scene holds it.
+    fn release_object_resources(&mut self, removed: Vec<RenderObject>) {
+        if removed.is_empty() {
+            return;
+        }
+
+        let in_use: HashSet<ResourceId> = self
+            .render_objects
+            .iter()
+            .map(|object| object.mesh_id)
+            .collect();
+        let owned_meshes = &mut self.owned_meshes;
+        let resource_manager = self
+            .resource_manager
+            .as_mut()
+            .expect("Scene not initialized");
+
+        for object in removed {
+            let id = object.id.as_u32();
+            resource_manager.remove_buffer(&ResourceId::new(&format!("model_buffer_{}", id)));
+            resource_manager.remove_buffer(&ResourceId::new(&format!("material_buffer_{}", id)));
+            resource_manager
+                .remove_bind_group(&ResourceId::new(&format!("model_bind_group_{}", id)));
+            resource_manager
+                .remove_bind_group(&ResourceId::new(&format!("material_bind_group_{}", id)));
+            if !in_use.contains(&object.mesh_id) && owned_meshes.remove(&object.mesh_id) {
+                resource_manager.release_mesh(&object.mesh_id);
+            }
+        }
     }
 
     fn get_resource_manager_mut(&mut self) -> &mut ResourceManager {
@@ -125,6 +595,10 @@ impl DemoScene {
     }
 
     fn create_model_resource(&mut self, render_object: &mut RenderObject) {
+        let model_bind_group_layout = self
+            .model_bind_group_layout
+            .take()
+            .expect("Scene not initialized");
         let resource_manager = self.get_resource_manager_mut();
 
         let model_uniform = render_object.get_model_uniform_data();
@@ -137,22 +611,14 @@ impl DemoScene {
 
         render_object.model_buffer = Some(model_buffer.clone());
 
-        // Create model bind group layout
-        let model_bind_group_layout = resource_manager.get_device().create_bind_group_layout(
-            &wgpu::BindGroupLayoutDescriptor {
-                label: Some("Model Uniform Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            },
-        );
+        let material_buffer = resource_manager
+            .create_uniform_buffer(
+                ResourceId::new(&format!("material_buffer_{}", render_object.id.as_u32())),
+                &render_object.material,
+            )
+            .expect("Failed to create material buffer");
+        render_object.take_material_upload();
+        render_object.material_buffer = Some(material_buffer.clone());
 
         // Create model bind group
         let model_bind_group_id =
@@ -162,82 +628,366 @@ impl DemoScene {
             .create_bind_group(
                 model_bind_group_id,
                 &model_bind_group_layout,
-                &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: model_buffer.as_entire_binding(),
-                }],
+                &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: model_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: material_buffer.as_entire_binding(),
+                    },
+                ],
             )
             .expect("Failed to create model bind group");
 
         render_object.model_bind_group = Some(model_bind_group);
+        self.model_bind_group_layout = Some(model_bind_group_layout);
+    }
+
+    /// Creates the shadow pass bind group and the depth-only variants of the
+    /// object pipelines.
+    fn create_shadow_pipelines(
+        &mut self,
+        shadow_buffer: &wgpu::Buffer,
+        model_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> EngineResult<()> {
+        let shadow_shader_id = ResourceId::new("shadow_shader");
+        self.get_resource_manager_mut().create_shader(
+            shadow_shader_id,
+            include_str!("../../assets/shaders/shadow/depth.wgsl"),
+            Some("Shadow Shader"),
+        )?;
+        let shadow_bind_group_layout = self
+            .get_resource_manager_mut()
+            .create_uniform_bind_group_layout(
+                "Shadow Pass Bind Group Layout",
+                wgpu::ShaderStages::VERTEX,
+            );
+        let shadow_bind_group = self.get_resource_manager_mut().create_bind_group(
+            ResourceId::new("shadow_bind_group"),
+            &shadow_bind_group_layout,
+            &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: shadow_buffer.as_entire_binding(),
+            }],
+        )?;
+        self.shadow_bind_group = Some(shadow_bind_group);
+
+        // オブジェクトを描くパイプラインごとに、同じ頂点レイアウトの深度のみの派生を作る
+        let base_ids = [
+            Some(self.pipeline_id),
+            self.double_sided_pipeline_id,
+            self.wireframe_pipeline_id,
+            Some(self.pulse_pipeline_id),
+            Some(self.textured_pipeline_id),
+        ];
+        for base_id in base_ids.into_iter().flatten() {
+            self.get_resource_manager_mut().create_shadow_pipeline(
+                base_id,
+                shadow_shader_id,
+                &[&shadow_bind_group_layout, model_bind_group_layout],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Shadow parameters for the current light, fitted to the visible objects.
+    fn compute_shadow_uniform(&self) -> ShadowUniform {
+        let Some(resource_manager) = self.resource_manager.as_ref() else {
+            return ShadowUniform::default();
+        };
+        let corners = self
+            .render_objects
+            .iter()
+            .filter(|obj| obj.visible)
+            .filter_map(|obj| {
+                let mesh = resource_manager.get_mesh(&obj.mesh_id)?;
+                let aabb = obj.world_aabb(&mesh);
+                Some([aabb.min, aabb.max])
+            })
+            .flatten();
+        let bounds = Aabb::from_points(corners);
+        let center = (bounds.min + bounds.max) * 0.5;
+        let radius = ((bounds.max - bounds.min).length() * 0.5).max(1.0);
+
+        // 深度は光の方向に 2 * radius の範囲を 0〜1 に割り当てている
+        ShadowUniform::new(
+            self.light.view_proj(center, radius),
+            SHADOW_BIAS / (2.0 * radius),
+            self.shadows,
+        )
+    }
+
+    /// Creates the uniform buffer and camera bind group of extra camera
+    /// `index`, sharing the global uniform with the main camera.
+    fn create_camera_view_bind_group(&mut self, index: usize) -> EngineResult<()> {
+        let view = &mut self.camera_views[index];
+        view.uniform.update_view_proj(&view.camera);
+        let uniform = view.uniform;
+
+        let (Some(layout), Some(global_buffer), Some(resource_manager)) = (
+            self.camera_bind_group_layout.as_ref(),
+            self.global_buffer.as_ref(),
+            self.resource_manager.as_mut(),
+        ) else {
+            return Err(EngineError::ResourceNotFound(
+                "Camera bind group layout not created".to_string(),
+            ));
+        };
+        let buffer = resource_manager.create_uniform_buffer(
+            ResourceId::new(&format!("camera_view_{}_buffer", index)),
+            &uniform,
+        )?;
+        let bind_group = resource_manager.create_bind_group(
+            ResourceId::new(&format!("camera_view_{}_bind_group", index)),
+            layout,
+            &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: global_buffer.as_entire_binding(),
+                },
+            ],
+        )?;
+
+        let view = &mut self.camera_views[index];
+        view.buffer = Some(buffer);
+        view.bind_group = Some(bind_group);
+        Ok(())
     }
 }
 
 impl Scene for DemoScene {
-    fn initialize(&mut self, resource_manager: ResourceManager) {
+    fn initialize(&mut self, resource_manager: ResourceManager) -> EngineResult<()> {
         if self.initialized {
-            return;
+            return Ok(());
         }
 
         self.resource_manager = Some(resource_manager);
 
         let shader_id = ResourceId::new("basic_shader");
-        if let Err(e) = self.get_resource_manager_mut().create_shader(
+        self.create_scene_shader(
             shader_id,
+            BASIC_SHADER_PATH,
             include_str!("../../assets/shaders/basic/triangle.wgsl"),
-            Some("Basic Shader"),
-        ) {
-            log::error!("Failed to create shader: {}", e);
-            return;
-        };
+            "Basic Shader",
+        )?;
 
+        // binding 0 がカメラ、binding 1 が時間と解像度
         let camera_bind_group_layout = self
             .get_resource_manager_mut()
-            .get_device()
-            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Camera Uniform Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            });
+            .create_uniforms_bind_group_layout(
+                "Camera Uniform Bind Group Layout",
+                &[
+                    wgpu::ShaderStages::VERTEX,
+                    wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ],
+            );
 
+        // binding 0 がモデル行列、binding 1 がマテリアル色
         let model_bind_group_layout = self
             .get_resource_manager_mut()
-            .get_device()
-            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Model Uniform Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            });
+            .create_uniforms_bind_group_layout(
+                "Model Uniform Bind Group Layout",
+                &[wgpu::ShaderStages::VERTEX, wgpu::ShaderStages::FRAGMENT],
+            );
+
+        // binding 0 がライト、binding 1〜3 がシャドウマップ
+        let light_bind_group_layout = self
+            .get_resource_manager_mut()
+            .create_light_bind_group_layout("Light Bind Group Layout");
 
         let pipeline_id = self.pipeline_id;
         let surface_format = self.get_resource_manager_mut().get_surface_format();
+        self.pipeline_options.sample_count = self.get_resource_manager_mut().get_sample_count();
+        self.pipeline_options.depth_format = self.get_resource_manager_mut().get_depth_format();
+        let pipeline_options = self.pipeline_options;
 
-        if let Err(e) = self.get_resource_manager_mut().create_pipeline(
+        self.get_resource_manager_mut().create_pipeline(
             pipeline_id,
             shader_id,
+            NormalVertex::desc(),
+            surface_format,
+            &[
+                &camera_bind_group_layout,
+                &model_bind_group_layout,
+                &light_bind_group_layout,
+            ],
+            &pipeline_options,
+        )?;
+
+        // 同じメッシュの頂点カラーオブジェクトをまとめて描くインスタンス版（group 1 がライト）
+        let instanced_shader_id = ResourceId::new("instanced_shader");
+        self.create_scene_shader(
+            instanced_shader_id,
+            INSTANCED_SHADER_PATH,
+            include_str!("../../assets/shaders/basic/instanced.wgsl"),
+            "Instanced Shader",
+        )?;
+        self.get_resource_manager_mut().create_instanced_pipeline(
+            pipeline_id,
+            instanced_shader_id,
+            NormalVertex::desc(),
+            &[&camera_bind_group_layout, &light_bind_group_layout],
+            &pipeline_options,
+        )?;
+
+        // 平面の図形を裏からも見えるようにする背面カリングなしの派生パイプライン
+        let double_sided_options = PipelineOptions {
+            cull_mode: None,
+            ..pipeline_options
+        };
+        if self.double_sided_flat {
+            let double_sided_pipeline_id = ResourceId::new("basic_double_sided_pipeline");
+            self.get_resource_manager_mut().create_pipeline(
+                double_sided_pipeline_id,
+                shader_id,
+                NormalVertex::desc(),
+                surface_format,
+                &[
+                    &camera_bind_group_layout,
+                    &model_bind_group_layout,
+                    &light_bind_group_layout,
+                ],
+                &double_sided_options,
+            )?;
+            self.get_resource_manager_mut().create_instanced_pipeline(
+                double_sided_pipeline_id,
+                instanced_shader_id,
+                NormalVertex::desc(),
+                &[&camera_bind_group_layout, &light_bind_group_layout],
+                &double_sided_options,
+            )?;
+            self.double_sided_pipeline_id = Some(double_sided_pipeline_id);
+        }
+
+        // 時間で色が変わる頂点カラーのパイプライン（group 0 binding 1 の時間を使う）
+        let pulse_shader_id = ResourceId::new("pulse_shader");
+        self.create_scene_shader(
+            pulse_shader_id,
+            PULSE_SHADER_PATH,
+            include_str!("../../assets/shaders/basic/pulse.wgsl"),
+            "Pulse Shader",
+        )?;
+        let pulse_pipeline_id = self.pulse_pipeline_id;
+        self.get_resource_manager_mut().create_pipeline(
+            pulse_pipeline_id,
+            pulse_shader_id,
+            NormalVertex::desc(),
+            surface_format,
+            &[
+                &camera_bind_group_layout,
+                &model_bind_group_layout,
+                &light_bind_group_layout,
+            ],
+            &pipeline_options,
+        )?;
+
+        // 目盛りグリッド（線リスト、group 0 のカメラのみ使う）
+        let grid_shader_id = ResourceId::new("grid_shader");
+        self.create_scene_shader(
+            grid_shader_id,
+            GRID_SHADER_PATH,
+            include_str!("../../assets/shaders/basic/grid.wgsl"),
+            "Grid Shader",
+        )?;
+        let grid_pipeline_id = self.grid_pipeline_id;
+        let grid_options = PipelineOptions {
+            topology: wgpu::PrimitiveTopology::LineList,
+            cull_mode: None,
+            ..pipeline_options
+        };
+        self.get_resource_manager_mut().create_pipeline(
+            grid_pipeline_id,
+            grid_shader_id,
             ColorVertex::desc(),
             surface_format,
-            &[&camera_bind_group_layout, &model_bind_group_layout],
-        ) {
-            log::error!("Failed to create pipeline: {}", e);
-            return;
+            &[&camera_bind_group_layout],
+            &grid_options,
+        )?;
+        let grid_mesh =
+            Grid::new(self.grid_size).create_mesh(self.get_resource_manager_mut().get_device());
+        let grid_mesh_id = self.grid_mesh_id;
+        self.get_resource_manager_mut()
+            .register_mesh(grid_mesh_id, Arc::new(grid_mesh));
+
+        // テクスチャ付きパイプライン（group 3 にテクスチャとサンプラー）
+        let textured_shader_id = ResourceId::new("textured_shader");
+        self.create_scene_shader(
+            textured_shader_id,
+            TEXTURED_SHADER_PATH,
+            include_str!("../../assets/shaders/basic/textured.wgsl"),
+            "Textured Shader",
+        )?;
+        let texture_bind_group_layout = self
+            .get_resource_manager_mut()
+            .create_texture_bind_group_layout("Texture Bind Group Layout");
+        let textured_pipeline_id = self.textured_pipeline_id;
+        // テクスチャ付きパイプラインは板ポリゴン専用
+        let textured_options = if self.double_sided_flat {
+            double_sided_options
+        } else {
+            pipeline_options
         };
+        self.get_resource_manager_mut().create_pipeline(
+            textured_pipeline_id,
+            textured_shader_id,
+            TextureVertex::desc(),
+            surface_format,
+            &[
+                &camera_bind_group_layout,
+                &model_bind_group_layout,
+                &light_bind_group_layout,
+                &texture_bind_group_layout,
+            ],
+            &textured_options,
+        )?;
+        self.texture_bind_group_layout = Some(texture_bind_group_layout);
+
+        // テクスチャ未指定の TexturedQuad 用の白テクスチャ
+        let device = self.get_resource_manager_mut().get_device();
+        let queue = self.get_resource_manager_mut().get_queue();
+        let default_texture = Texture::from_rgba8(
+            &device,
+            &queue,
+            &image::RgbaImage::from_pixel(1, 1, image::Rgba([255, 255, 255, 255])),
+            Some("Default White Texture"),
+        );
+        self.get_resource_manager_mut()
+            .register_texture(ResourceId::new(DEFAULT_TEXTURE), Arc::new(default_texture));
+
+        // ワイヤーフレーム用の派生パイプライン（対応デバイスのみ）
+        let line_supported = self
+            .get_resource_manager_mut()
+            .get_device()
+            .features()
+            .contains(wgpu::Features::POLYGON_MODE_LINE);
+        if line_supported {
+            let wireframe_pipeline_id = ResourceId::new("basic_wireframe_pipeline");
+            let wireframe_options = PipelineOptions {
+                polygon_mode: wgpu::PolygonMode::Line,
+                ..pipeline_options
+            };
+            match self.get_resource_manager_mut().create_pipeline(
+                wireframe_pipeline_id,
+                shader_id,
+                NormalVertex::desc(),
+                surface_format,
+                &[
+                    &camera_bind_group_layout,
+                    &model_bind_group_layout,
+                    &light_bind_group_layout,
+                ],
+                &wireframe_options,
+            ) {
+                Ok(_) => self.wireframe_pipeline_id = Some(wireframe_pipeline_id),
+                Err(e) => log::error!("Failed to create wireframe pipeline: {}", e),
+            }
+        }
 
         // カメラユニフォームバッファ作成
         self.camera_uniform.update_view_proj(&self.camera);
@@ -246,36 +996,184 @@ impl Scene for DemoScene {
         let camera_uniform = self.camera_uniform;
         let camera_buffer = self
             .get_resource_manager_mut()
-            .create_uniform_buffer(camera_buffer_id, &camera_uniform)
-            .expect("Failed to create camera buffer");
+            .create_uniform_buffer(camera_buffer_id, &camera_uniform)?;
         self.camera_buffer = Some(camera_buffer.clone());
 
+        let global_uniform = self.global_uniform;
+        let global_buffer = self
+            .get_resource_manager_mut()
+            .create_uniform_buffer(ResourceId::new("global_buffer"), &global_uniform)?;
+        self.global_buffer = Some(global_buffer.clone());
+
         // BindGroup作成
         let bind_group_id = ResourceId::new("camera_bind_group");
-        let camera_bind_group = self
-            .get_resource_manager_mut()
-            .create_bind_group(
-                bind_group_id,
-                &camera_bind_group_layout,
-                &[wgpu::BindGroupEntry {
+        let camera_bind_group = self.get_resource_manager_mut().create_bind_group(
+            bind_group_id,
+            &camera_bind_group_layout,
+            &[
+                wgpu::BindGroupEntry {
                     binding: 0,
                     resource: camera_buffer.as_entire_binding(),
-                }],
-            )
-            .expect("Failed to create camera bind group");
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: global_buffer.as_entire_binding(),
+                },
+            ],
+        )?;
         self.camera_bind_group = Some(camera_bind_group);
+        self.camera_bind_group_layout = Some(camera_bind_group_layout);
+        for index in 0..self.camera_views.len() {
+            self.create_camera_view_bind_group(index)?;
+        }
+
+        // ライトユニフォームバッファとBindGroup作成
+        self.light_uniform = self.light.uniform().with_point_lights(&self.point_lights);
+        let light_uniform = self.light_uniform;
+        let light_buffer = self
+            .get_resource_manager_mut()
+            .create_uniform_buffer(ResourceId::new("light_buffer"), &light_uniform)?;
+        self.shadow_uniform = self.compute_shadow_uniform();
+        let shadow_uniform = self.shadow_uniform;
+        let shadow_buffer = self
+            .get_resource_manager_mut()
+            .create_uniform_buffer(ResourceId::new("shadow_buffer"), &shadow_uniform)?;
+        let shadow_map_size = if self.shadows { SHADOW_MAP_SIZE } else { 1 };
+        let shadow_map = Arc::new(Texture::shadow_map(
+            &self.get_resource_manager_mut().get_device(),
+            shadow_map_size,
+            Some("Shadow Map"),
+        ));
+        self.get_resource_manager_mut()
+            .register_texture(ResourceId::new("shadow_map"), shadow_map.clone());
+        let light_bind_group = self.get_resource_manager_mut().create_bind_group(
+            ResourceId::new("light_bind_group"),
+            &light_bind_group_layout,
+            &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: light_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: shadow_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&shadow_map.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(&shadow_map.sampler),
+                },
+            ],
+        )?;
+        self.light_buffer = Some(light_buffer);
+        self.light_bind_group = Some(light_bind_group);
+        self.shadow_map = Some(shadow_map);
+        if self.shadows {
+            self.create_shadow_pipelines(&shadow_buffer, &model_bind_group_layout)?;
+        }
+        self.shadow_buffer = Some(shadow_buffer);
+        self.model_bind_group_layout = Some(model_bind_group_layout);
 
         self.initialized = true;
+
+        if let Some(snapshot) = self.pending_snapshot.take() {
+            self.spawn_snapshot(&snapshot);
+        }
+        Ok(())
+    }
+
+    fn take_resource_manager(&mut self) -> Option<ResourceManager> {
+        // 再度 initialize した際にカメラとライトのバインドグループを作り直す
+        self.initialized = false;
+        self.resource_manager.take()
     }
 
     fn get_render_objects(&self) -> &[RenderObject] {
         &self.render_objects
     }
 
+    fn to_snapshot(&self) -> SceneSnapshot {
+        SceneSnapshot {
+            objects: self
+                .render_objects
+                .iter()
+                .filter_map(ObjectDescriptor::from_object)
+                .collect(),
+        }
+    }
+
     fn get_camera_bind_group(&self) -> Option<&Arc<wgpu::BindGroup>> {
         self.camera_bind_group.as_ref()
     }
 
+    fn apply_config(&mut self, config: &AppConfig) {
+        self.config = config.movement.clone();
+        self.keybindings = config.keybindings.clone();
+        self.camera.projection = Projection::from_config(&config.camera);
+        self.camera.zoom_speed = config.camera.zoom_speed;
+        self.camera.movement = config.camera.movement;
+    }
+
+    fn export_config(&self, config: &mut AppConfig) {
+        config.movement = self.config.clone();
+        config.keybindings = self.keybindings.clone();
+        match self.camera.projection {
+            Projection::Perspective { .. } => {
+                config.camera.projection = ProjectionConfig::Perspective;
+                // ズーム中は到達予定の視野角を保存する
+                if let Some(fov) = self.camera.target_fov() {
+                    config.camera.fov_degrees = fov;
+                }
+            }
+            Projection::Orthographic { height } => {
+                config.camera.projection = ProjectionConfig::Orthographic { height };
+            }
+        }
+        config.camera.zoom_speed = self.camera.zoom_speed;
+        config.camera.movement = self.camera.movement;
+        // 太陽のアニメーション中は向きと色が毎フレーム上書きされるので保存しない
+        if !self.sun.is_animated() {
+            config.lighting.direction = self.light.direction.to_array();
+            config.lighting.color = self.light.color.to_array();
+        }
+        config.rendering.wireframe = self.wireframe;
+        config.debug.show_grid = self.grid_visible;
+    }
+
+    fn set_light_direction(&mut self, direction: glam::Vec3) {
+        self.light.set_direction(direction);
+    }
+
+    fn add_point_light(&mut self, light: PointLight) -> Option<usize> {
+        if self.point_lights.len() >= MAX_POINT_LIGHTS {
+            log::warn!(
+                "Point light limit ({}) reached; dropping light at {:?}",
+                MAX_POINT_LIGHTS,
+                light.position
+            );
+            return None;
+        }
+        self.point_lights.push(light);
+        Some(self.point_lights.len() - 1)
+    }
+
+    fn set_point_light_position(&mut self, index: usize, position: glam::Vec3) -> bool {
+        match self.point_lights.get_mut(index) {
+            Some(light) => {
+                light.position = position;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn get_light_bind_group(&self) -> Option<&Arc<wgpu::BindGroup>> {
+        self.light_bind_group.as_ref()
+    }
+
     fn get_camera_buffer(&self) -> Option<&Arc<wgpu::Buffer>> {
         self.camera_buffer.as_ref()
     }
@@ -295,12 +1193,67 @@ impl Scene for DemoScene {
         object_type: crate::resources::primitives::ObjectType,
         position: glam::Vec3,
     ) -> ObjectId {
-        match object_type {
-            ObjectType::Quad => self.add_quad(position),
-            ObjectType::Triangle => self.add_triangle(position),
-            ObjectType::Cube => self.add_cube(position),
-            ObjectType::Sphere => self.add_sphere(position),
+        let object_id = match object_type {
+            ObjectType::Quad => self.add_primitive::<Quad>("quad_mesh", position),
+            ObjectType::Triangle => self.add_primitive::<Triangle>("triangle_mesh", position),
+            ObjectType::Cube => self.add_primitive::<Cube>("cube_mesh", position),
+            ObjectType::Sphere => self.add_primitive::<Sphere>("sphere_mesh", position),
+            ObjectType::Plane => self.add_primitive::<plane::Plane>("plane_mesh", position),
+            ObjectType::Cone => self.add_primitive::<Cone>("cone_mesh", position),
+            ObjectType::Torus => self.add_primitive::<Torus>("torus_mesh", position),
+            ObjectType::Capsule => self.add_primitive::<Capsule>("capsule_mesh", position),
+            ObjectType::TexturedQuad => {
+                let texture = self
+                    .get_resource_manager()
+                    .get_texture(&ResourceId::new(DEFAULT_TEXTURE))
+                    .expect("Scene not initialized");
+                self.add_textured_quad_with(position, texture)
+            }
+        };
+        let pipeline_id = self.active_pipeline_id(object_type.is_flat());
+        if let Some(object) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            object.object_type = Some(object_type);
+            // 平面の図形は設定に応じて両面描画のパイプラインにする
+            if object.pipeline_id == self.pipeline_id {
+                object.pipeline_id = pipeline_id;
+            }
         }
+        object_id
+    }
+
+    fn add_objects(&mut self, objects: &[(ObjectType, glam::Vec3)]) -> Vec<ObjectId> {
+        self.render_objects.reserve(objects.len());
+        objects
+            .iter()
+            .map(|&(object_type, position)| self.add_object(object_type, position))
+            .collect()
+    }
+
+    fn add_mesh_object(&mut self, mesh_id: ResourceId, position: glam::Vec3) -> Option<ObjectId> {
+        self.get_resource_manager().get_mesh(&mesh_id)?;
+
+        let transform = Transform::new().with_position(position);
+        let mut render_object =
+            RenderObject::new(mesh_id, self.active_pipeline_id(false)).with_transform(transform);
+        let render_object_id = render_object.id;
+
+        self.create_model_resource(&mut render_object);
+        self.render_objects.push(render_object);
+
+        Some(render_object_id)
+    }
+
+    fn add_textured_quad(
+        &mut self,
+        position: glam::Vec3,
+        texture_id: ResourceId,
+    ) -> Option<ObjectId> {
+        let texture = self.get_resource_manager().get_texture(&texture_id)?;
+        Some(self.add_textured_quad_with(position, texture))
     }
 
     fn move_object(&mut self, object_id: ObjectId, position: glam::Vec3) -> bool {
@@ -309,87 +1262,959 @@ impl Scene for DemoScene {
             .iter_mut()
             .find(|obj| obj.id == object_id)
         {
-            obj.transform.set_position(position);
+            obj.set_position(position);
             true
         } else {
             false
         }
     }
 
-    fn remove_object(&mut self, object_id: ObjectId) -> bool {
-        let before_len = self.render_objects.len();
-        self.render_objects.retain(|obj| obj.id != object_id);
-        self.render_objects.len() < before_len
+    fn rotate_object(&mut self, object_id: ObjectId, rotation: glam::Quat) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.set_rotation(rotation);
+            true
+        } else {
+            false
+        }
     }
 
-    fn set_object_visible(&mut self, object_id: ObjectId, visible: bool) -> bool {
+    fn set_object_color(&mut self, object_id: ObjectId, color: [f32; 4]) -> bool {
         if let Some(obj) = self
             .render_objects
             .iter_mut()
             .find(|obj| obj.id == object_id)
         {
-            obj.set_visible(visible);
+            obj.set_color(color);
             true
         } else {
             false
         }
     }
 
-    fn update_camera_uniform(&mut self) {
-        self.camera_uniform.update_view_proj(&self.camera);
-
-        if let (Some(camera_buffer), Some(resource_manager)) =
-            (self.camera_buffer.as_ref(), self.resource_manager.as_mut())
+    fn scale_object(&mut self, object_id: ObjectId, scale: glam::Vec3) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
         {
-            resource_manager.update_uniform_buffer(camera_buffer.as_ref(), &self.camera_uniform);
+            obj.set_scale(scale);
+            true
+        } else {
+            false
         }
     }
 
-    fn update(&mut self, dt: f32, input: &InputState) {
-        use winit::keyboard::KeyCode;
+    fn get_object_transform(&self, object_id: ObjectId) -> Option<Transform> {
+        self.render_objects
+            .iter()
+            .find(|obj| obj.id == object_id)
+            .map(|obj| obj.transform)
+    }
 
-        log::debug!("DemoScene::update called with dt={}", dt);
+    fn set_object_transform(&mut self, object_id: ObjectId, transform: Transform) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.set_transform(transform);
+            true
+        } else {
+            false
+        }
+    }
 
-        let move_speed = self.config.move_speed * dt;
-        let rotation_speed = self.config.rotation_speed * dt;
+    fn remove_object(&mut self, object_id: ObjectId) -> bool {
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.render_objects)
+            .into_iter()
+            .partition(|obj| obj.id == object_id);
+        self.render_objects = kept;
 
-        // WASD でカメラ移動
-        if input.is_key_pressed(KeyCode::KeyW) {
-            log::debug!("W key pressed! Moving forward by {}", move_speed);
-            log::debug!("Camera position before: {:?}", self.camera.eye);
-            self.camera.move_forward(move_speed);
-            log::debug!("Camera position after: {:?}", self.camera.eye);
+        let found = !removed.is_empty();
+        self.release_object_resources(removed);
+        found
+    }
+
+    fn clear_objects(&mut self) {
+        let removed = std::mem::take(&mut self.render_objects);
+        self.release_object_resources(removed);
+    }
+
+    fn object_count(&self) -> usize {
+        self.render_objects.len()
+    }
+
+    fn object_ids(&self) -> Vec<ObjectId> {
+        self.render_objects.iter().map(|obj| obj.id).collect()
+    }
+
+    fn set_object_visible(&mut self, object_id: ObjectId, visible: bool) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.set_visible(visible);
+            true
+        } else {
+            false
         }
-        if input.is_key_pressed(KeyCode::KeyS) {
-            self.camera.move_forward(-move_speed);
+    }
+
+    fn set_object_layers(&mut self, object_id: ObjectId, layer_mask: u32) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.set_layer_mask(layer_mask);
+            true
+        } else {
+            false
         }
-        if input.is_key_pressed(KeyCode::KeyA) {
-            self.camera.move_right(-move_speed);
+    }
+
+    fn mirror_object(&mut self, object_id: ObjectId, plane: Plane) -> Option<ObjectId> {
+        let mut mirrored = self
+            .render_objects
+            .iter()
+            .find(|obj| obj.id == object_id)?
+            .mirrored(plane);
+        let mirrored_id = mirrored.id;
+
+        self.create_model_resource(&mut mirrored);
+        self.render_objects.push(mirrored);
+
+        Some(mirrored_id)
+    }
+
+    fn attach_to_camera(&mut self, object_id: ObjectId, offset: Transform) -> bool {
+        let camera_matrix = self.camera.world_matrix();
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.camera_offset = Some(offset);
+            obj.follow_camera(camera_matrix);
+            true
+        } else {
+            false
         }
-        if input.is_key_pressed(KeyCode::KeyD) {
-            self.camera.move_right(move_speed);
+    }
+
+    fn detach_from_camera(&mut self, object_id: ObjectId) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.camera_offset = None;
+            true
+        } else {
+            false
         }
+    }
 
-        // Q/E で上下移動
-        if input.is_key_pressed(KeyCode::KeyQ) {
-            self.camera.move_up(-move_speed);
+    fn set_object_static(&mut self, object_id: ObjectId, is_static: bool) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.set_static(is_static);
+            true
+        } else {
+            false
         }
-        if input.is_key_pressed(KeyCode::KeyE) {
-            self.camera.move_up(move_speed);
+    }
+
+    fn set_object_pulse(&mut self, object_id: ObjectId, pulse: bool) -> bool {
+        let pulse_pipeline_id = self.pulse_pipeline_id;
+        let basic_pipeline_id = self.active_pipeline_id(false);
+        let flat_pipeline_id = self.active_pipeline_id(true);
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            // テクスチャ付きオブジェクトは頂点形式が違うので対象外
+            if obj.pipeline_id == self.textured_pipeline_id {
+                return false;
+            }
+            obj.pipeline_id = if pulse {
+                pulse_pipeline_id
+            } else if obj.object_type.is_some_and(ObjectType::is_flat) {
+                flat_pipeline_id
+            } else {
+                basic_pipeline_id
+            };
+            true
+        } else {
+            false
         }
+    }
 
-        // 矢印キーで回転
-        if input.is_key_pressed(KeyCode::ArrowLeft) {
-            self.camera.rotate_horizontal(rotation_speed);
+    fn set_object_spin(&mut self, object_id: ObjectId, angular_velocity: glam::Vec3) -> bool {
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.angular_velocity =
+                (angular_velocity != glam::Vec3::ZERO).then_some(angular_velocity);
+            true
+        } else {
+            false
         }
-        if input.is_key_pressed(KeyCode::ArrowRight) {
-            self.camera.rotate_horizontal(-rotation_speed);
+    }
+
+    fn spawn_projectile(
+        &mut self,
+        object_type: ObjectType,
+        origin: glam::Vec3,
+        velocity: glam::Vec3,
+    ) -> ObjectId {
+        let object_id = self.add_object(object_type, origin);
+        if let Some(obj) = self
+            .render_objects
+            .iter_mut()
+            .find(|obj| obj.id == object_id)
+        {
+            obj.rigidbody =
+                Some(Rigidbody::new(velocity).with_gravity(self.projectile.use_gravity));
+            obj.lifetime = Some(self.projectile.lifetime_seconds);
         }
-        if input.is_key_pressed(KeyCode::ArrowUp) {
-            self.camera.rotate_vertical(rotation_speed);
+        object_id
+    }
+
+    fn update_global_uniform(&mut self, globals: GlobalUniform) {
+        self.global_uniform = globals;
+
+        if let (Some(global_buffer), Some(resource_manager)) =
+            (self.global_buffer.as_ref(), self.resource_manager.as_mut())
+        {
+            resource_manager.update_uniform_buffer(global_buffer.as_ref(), &self.global_uniform);
         }
-        if input.is_key_pressed(KeyCode::ArrowDown) {
-            self.camera.rotate_vertical(-rotation_speed);
+    }
+
+    fn update_camera_uniform(&mut self) {
+        self.camera_uniform.update_view_proj(&self.camera);
+
+        let Some(resource_manager) = self.resource_manager.as_mut() else {
+            return;
+        };
+        if let Some(camera_buffer) = self.camera_buffer.as_ref() {
+            resource_manager.update_uniform_buffer(camera_buffer.as_ref(), &self.camera_uniform);
+        }
+        for view in &mut self.camera_views {
+            view.uniform.update_view_proj(&view.camera);
+            if let Some(buffer) = view.buffer.as_ref() {
+                resource_manager.update_uniform_buffer(buffer.as_ref(), &view.uniform);
+            }
+        }
+    }
+
+    fn update_light_uniform(&mut self) {
+        self.light_uniform = self.light.uniform().with_point_lights(&self.point_lights);
+        self.shadow_uniform = self.compute_shadow_uniform();
+
+        if let (Some(light_buffer), Some(resource_manager)) =
+            (self.light_buffer.as_ref(), self.resource_manager.as_mut())
+        {
+            resource_manager.update_uniform_buffer(light_buffer.as_ref(), &self.light_uniform);
+            if let Some(shadow_buffer) = self.shadow_buffer.as_ref() {
+                resource_manager
+                    .update_uniform_buffer(shadow_buffer.as_ref(), &self.shadow_uniform);
+            }
+        }
+    }
+
+    fn shadow_pass(&self) -> Option<ShadowPass<'_>> {
+        if !self.shadows {
+            return None;
+        }
+        Some(ShadowPass {
+            view: &self.shadow_map.as_ref()?.view,
+            bind_group: self.shadow_bind_group.as_ref()?,
+        })
+    }
+
+    fn update_model_uniforms(&mut self) {
+        let Some(resource_manager) = self.resource_manager.as_mut() else {
+            return;
+        };
+        let alpha = self.physics_timestep.alpha();
+
+        for object in &mut self.render_objects {
+            if let Some(model_buffer) = object.model_buffer.clone()
+                && let Some(model_uniform) = object.take_model_upload(alpha)
+            {
+                resource_manager.update_uniform_buffer(model_buffer.as_ref(), &model_uniform);
+            }
+            if let Some(material_buffer) = object.material_buffer.clone()
+                && let Some(material) = object.take_material_upload()
+            {
+                resource_manager.update_uniform_buffer(material_buffer.as_ref(), &material);
+            }
+        }
+    }
+
+    fn set_projection_jitter(&mut self, jitter: glam::Vec2) {
+        self.camera.jitter = jitter;
+    }
+
+    fn camera_view_proj(&self) -> glam::Mat4 {
+        self.camera.build_unjittered_view_proj_matrix()
+    }
+
+    fn camera_eye(&self) -> glam::Vec3 {
+        self.camera.eye
+    }
+
+    fn views(&self) -> Vec<SceneView<'_>> {
+        let main = self.camera_bind_group.as_ref().map(|bind_group| SceneView {
+            viewport: self.main_viewport,
+            camera_bind_group: bind_group,
+            view_proj: self.camera_view_proj(),
+            eye: self.camera.eye,
+        });
+        let extra = self.camera_views.iter().filter_map(|view| {
+            Some(SceneView {
+                viewport: view.viewport,
+                camera_bind_group: view.bind_group.as_ref()?,
+                view_proj: view.camera.build_unjittered_view_proj_matrix(),
+                eye: view.camera.eye,
+            })
+        });
+        main.into_iter().chain(extra).collect()
+    }
+
+    fn set_main_viewport(&mut self, viewport: Viewport) {
+        self.main_viewport = viewport;
+        self.camera.aspect = viewport.aspect(self.target_aspect);
+        self.update_camera_uniform();
+    }
+
+    fn add_camera_view(&mut self, mut camera: Camera, viewport: Viewport) -> EngineResult<usize> {
+        camera.aspect = viewport.aspect(self.target_aspect);
+        self.camera_views.push(CameraView {
+            camera,
+            viewport,
+            uniform: CameraUniform::new(),
+            buffer: None,
+            bind_group: None,
+        });
+        let index = self.camera_views.len() - 1;
+        if self.initialized
+            && let Err(e) = self.create_camera_view_bind_group(index)
+        {
+            self.camera_views.pop();
+            return Err(e);
         }
+        Ok(index)
+    }
+
+    fn screen_to_ray(
+        &self,
+        mouse_pos: glam::Vec2,
+        viewport_size: glam::Vec2,
+    ) -> (glam::Vec3, glam::Vec3) {
+        self.camera.screen_to_ray(mouse_pos, viewport_size)
+    }
+
+    fn grid_overlay(&self) -> Option<(ResourceId, ResourceId)> {
+        self.grid_visible
+            .then_some((self.grid_pipeline_id, self.grid_mesh_id))
+    }
+
+    fn set_grid_visible(&mut self, visible: bool) {
+        self.grid_visible = visible;
+    }
+
+    fn debug_lines(&self) -> &DebugLines {
+        &self.debug_lines
+    }
+
+    fn debug_lines_mut(&mut self) -> &mut DebugLines {
+        &mut self.debug_lines
+    }
+
+    fn pick(&self, origin: glam::Vec3, direction: glam::Vec3) -> Option<ObjectId> {
+        let resource_manager = self.resource_manager.as_ref()?;
+
+        self.render_objects
+            .iter()
+            .filter(|obj| obj.visible)
+            .filter_map(|obj| {
+                let mesh = resource_manager.get_mesh(&obj.mesh_id)?;
+                let distance = obj.world_aabb(&mesh).intersect_ray(origin, direction)?;
+                Some((distance, obj.id))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, id)| id)
+    }
+
+    fn frame_object(&mut self, object_id: ObjectId) -> bool {
+        let Some(object) = self.render_objects.iter().find(|obj| obj.id == object_id) else {
+            return false;
+        };
+        let Some(mesh) = self.get_resource_manager().get_mesh(&object.mesh_id) else {
+            return false;
+        };
+        let aabb = object.world_aabb(&mesh);
+        self.camera.frame_bounds(aabb.min, aabb.max);
+        true
+    }
+
+    fn on_resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.target_aspect = width as f32 / height as f32;
+        let fit = |camera: &mut Camera, viewport: Viewport| {
+            let [_, _, viewport_width, viewport_height] = viewport.pixel_rect(width, height);
+            camera.set_viewport(
+                viewport_width.round() as u32,
+                viewport_height.round() as u32,
+            );
+        };
+        fit(&mut self.camera, self.main_viewport);
+        for view in &mut self.camera_views {
+            fit(&mut view.camera, view.viewport);
+        }
+        self.update_camera_uniform();
+    }
+
+    fn update(&mut self, dt: f32, input: &InputState) {
+        log::debug!("DemoScene::update called with dt={}", dt);
+
+        self.sun.update(dt);
+        if self.sun.is_animated() {
+            self.light = DirectionalLight::new(self.sun.direction(), self.sun.color());
+        }
+        self.update_wireframe_toggle(input);
+        self.update_grid_toggle(input);
+        self.update_shader_reload(input);
+
+        match self.camera.mode {
+            CameraMode::Fps => self.update_fps_camera(dt, input),
+            CameraMode::Orbit => self.update_orbit_camera(input),
+        }
+
+        self.camera.update_zoom(dt);
+
+        if let Some([min, max]) = self.config.bounds {
+            self.camera
+                .clamp_to_bounds(glam::Vec3::from(min), glam::Vec3::from(max));
+        }
+
+        // 回転速度を持つオブジェクトを回し、カメラに取り付けたオブジェクトを追従させる
+        // （静的オブジェクトは更新しない）
+        let camera_matrix = self.camera.world_matrix();
+        for object in &mut self.render_objects {
+            if object.is_static {
+                continue;
+            }
+            object.spin(dt);
+            object.follow_camera(camera_matrix);
+        }
+
+        self.update_projectiles(dt, input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graphics::context::GpuContext, resources::import::ImportOptions};
+
+    /// Initializes a scene on the software adapter, or `None` if there is none.
+    fn create_initialized_scene() -> Option<DemoScene> {
+        create_initialized_scene_with(AppConfig::default())
+    }
+
+    fn create_initialized_scene_with(config: AppConfig) -> Option<DemoScene> {
+        let context = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())).ok()?;
+        let resource_manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let mut scene = DemoScene::new(1.0, Arc::new(config));
+        scene.initialize(resource_manager).unwrap();
+        Some(scene)
+    }
+
+    #[test]
+    fn test_missing_shader_files_fall_back_to_embedded() {
+        let Ok(context) = pollster::block_on(GpuContext::new(true, wgpu::Features::empty())) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let resource_manager = ResourceManager::new(
+            context.device.clone(),
+            context.queue.clone(),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig::default();
+        config.debug.shader_hot_reload = true;
+        config.debug.shader_dir = dir.path().join("missing").to_string_lossy().into_owned();
+        let mut scene = DemoScene::new(1.0, Arc::new(config));
+
+        scene.initialize(resource_manager).unwrap();
+
+        assert!(
+            scene
+                .get_resource_manager()
+                .get_pipeline(&ResourceId::new("basic_pipeline"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_removing_objects_releases_resources() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let before = scene.get_resource_manager().stats();
+
+        let ids: Vec<ObjectId> = (0..1000)
+            .map(|_| scene.add_object(ObjectType::Quad, glam::Vec3::ZERO))
+            .collect();
+        assert!(scene.get_resource_manager().stats().meshes > before.meshes);
+
+        for id in ids {
+            assert!(scene.remove_object(id));
+        }
+
+        let after = scene.get_resource_manager().stats();
+        assert_eq!(after.meshes, before.meshes, "メッシュが解放されていない");
+        assert_eq!(after.buffers, before.buffers, "バッファが解放されていない");
+        assert_eq!(after.bind_groups, before.bind_groups);
+    }
+
+    #[test]
+    fn test_add_objects_shares_one_mesh_per_type() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let before = scene.get_resource_manager().stats();
+
+        let quads: Vec<_> = (0..100)
+            .map(|i| (ObjectType::Quad, glam::vec3(i as f32, 0.0, 0.0)))
+            .collect();
+        let ids = scene.add_objects(&quads);
+
+        assert_eq!(ids.len(), 100);
+        assert_eq!(scene.object_ids(), ids, "入力と同じ順序で返す");
+        assert_eq!(
+            scene.get_object_transform(ids[42]).unwrap().position,
+            glam::vec3(42.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            scene.get_resource_manager().stats().meshes,
+            before.meshes + 1,
+            "四角形のメッシュは1つだけ登録する"
+        );
+
+        let textured = scene.add_objects(&[
+            (ObjectType::TexturedQuad, glam::Vec3::ZERO),
+            (ObjectType::TexturedQuad, glam::Vec3::ONE),
+        ]);
+        assert_eq!(textured.len(), 2);
+        assert_eq!(
+            scene.get_resource_manager().stats().meshes,
+            before.meshes + 2
+        );
+    }
+
+    #[test]
+    fn test_clear_objects_releases_all_objects_and_meshes() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let before = scene.get_resource_manager().stats();
+
+        let ids = vec![
+            scene.add_object(ObjectType::Cube, glam::Vec3::ZERO),
+            scene.add_object(ObjectType::Sphere, glam::Vec3::X),
+            scene.add_object(ObjectType::Cube, glam::Vec3::Y),
+            scene.add_object(ObjectType::TexturedQuad, glam::Vec3::Z),
+        ];
+        assert_eq!(scene.object_count(), 4);
+        assert_eq!(scene.object_ids(), ids);
+
+        scene.clear_objects();
+
+        assert_eq!(scene.object_count(), 0);
+        assert!(scene.object_ids().is_empty());
+        let after = scene.get_resource_manager().stats();
+        assert_eq!(after.meshes, before.meshes, "メッシュが解放されていない");
+        assert_eq!(after.buffers, before.buffers, "バッファが解放されていない");
+        assert_eq!(after.bind_groups, before.bind_groups);
+    }
+
+    #[test]
+    fn test_removing_last_instance_keeps_loaded_mesh() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mesh_id = ResourceId::new("pyramid");
+        scene
+            .get_resource_manager_mut()
+            .load_obj(
+                mesh_id,
+                "assets/models/pyramid.obj",
+                &ImportOptions::default(),
+            )
+            .unwrap();
+
+        let id = scene.add_mesh_object(mesh_id, glam::Vec3::ZERO).unwrap();
+        assert!(scene.remove_object(id));
+
+        // 読み込んだメッシュは呼び出し側の所有なので解放しない
+        assert!(scene.get_resource_manager().get_mesh(&mesh_id).is_some());
+        assert!(scene.add_mesh_object(mesh_id, glam::Vec3::ONE).is_some());
+    }
+
+    #[test]
+    fn test_shared_mesh_outlives_one_scene_removing_its_objects() {
+        let Some(mut first) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let mesh_id = ResourceId::new("cube_mesh");
+        let first_cube = first.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+
+        // シーン切り替えと同じくリソースマネージャーを受け渡す
+        let mut second = DemoScene::new(1.0, Arc::new(AppConfig::default()));
+        second
+            .initialize(first.take_resource_manager().unwrap())
+            .unwrap();
+        let second_cube = second.add_object(ObjectType::Cube, glam::Vec3::ONE);
+        assert!(second.remove_object(second_cube));
+        assert!(
+            second.get_resource_manager().get_mesh(&mesh_id).is_some(),
+            "他のシーンが使っているメッシュは残す"
+        );
+
+        first
+            .initialize(second.take_resource_manager().unwrap())
+            .unwrap();
+        assert!(first.remove_object(first_cube));
+        assert!(first.get_resource_manager().get_mesh(&mesh_id).is_none());
+    }
+
+    #[test]
+    fn test_pulse_toggle_keeps_flat_objects_double_sided() {
+        let mut config = AppConfig::default();
+        config.rendering.double_sided_flat = true;
+        let Some(mut scene) = create_initialized_scene_with(config) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let double_sided = scene.double_sided_pipeline_id.unwrap();
+        let quad = scene.add_object(ObjectType::Quad, glam::Vec3::ZERO);
+        let cube = scene.add_object(ObjectType::Cube, glam::Vec3::ONE);
+        assert_eq!(scene.render_objects[0].pipeline_id, double_sided);
+
+        for id in [quad, cube] {
+            assert!(scene.set_object_pulse(id, true));
+            assert!(scene.set_object_pulse(id, false));
+        }
+
+        assert_eq!(
+            scene.render_objects[0].pipeline_id, double_sided,
+            "平面の図形は両面描画に戻る"
+        );
+        assert_eq!(scene.render_objects[1].pipeline_id, scene.pipeline_id);
+    }
+
+    #[test]
+    fn test_rotate_object_updates_transform() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::vec3(1.0, 2.0, 3.0));
+        let rotation = glam::Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+
+        assert!(scene.rotate_object(id, rotation));
+        assert!(!scene.rotate_object(ObjectId::generate(), rotation));
+
+        let transform = scene.get_render_objects()[0].transform;
+        assert_eq!(transform.rotation, rotation);
+        assert_eq!(
+            transform.position,
+            glam::vec3(1.0, 2.0, 3.0),
+            "位置は変わらない"
+        );
+        assert!(transform.forward().abs_diff_eq(glam::Vec3::NEG_X, 1e-6));
+    }
+
+    #[test]
+    fn test_object_transform_round_trips_and_reuploads_model() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::vec3(1.0, 2.0, 3.0));
+        assert_eq!(
+            scene.get_object_transform(id).unwrap().position,
+            glam::vec3(1.0, 2.0, 3.0)
+        );
+        assert!(scene.get_object_transform(ObjectId::generate()).is_none());
+
+        // 静的オブジェクトは変更があったときだけモデル行列を転送する
+        scene.set_object_static(id, true);
+        scene.update_model_uniforms();
+        let transform = Transform::new()
+            .with_position(glam::vec3(-1.0, 0.5, 0.0))
+            .with_rotation(glam::Quat::from_rotation_x(0.5))
+            .with_scale(glam::vec3(2.0, 1.0, 0.5));
+        assert!(scene.set_object_transform(id, transform));
+        assert!(!scene.set_object_transform(ObjectId::generate(), transform));
+
+        let stored = scene.get_object_transform(id).unwrap();
+        assert_eq!(stored.position, transform.position);
+        assert_eq!(stored.rotation, transform.rotation);
+        assert_eq!(stored.scale, transform.scale);
+        let upload = scene.render_objects[0]
+            .take_model_upload(0.0)
+            .expect("変更後はモデル行列を転送し直す");
+        assert_eq!(upload.model, transform.matrix().to_cols_array_2d());
+    }
+
+    #[test]
+    fn test_static_object_skips_spin_and_uploads() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        assert!(scene.set_object_spin(id, glam::vec3(0.0, 2.0, 0.0)));
+        assert!(scene.set_object_static(id, true));
+        scene.update_model_uniforms();
+
+        for _ in 0..3 {
+            scene.update(0.1, &InputState::new());
+        }
+
+        let object = &mut scene.render_objects[0];
+        assert_eq!(
+            object.transform.rotation,
+            glam::Quat::IDENTITY,
+            "静的オブジェクトは回転しない"
+        );
+        assert!(
+            object.take_model_upload(1.0).is_none(),
+            "変更がなければモデル行列を転送しない"
+        );
+    }
+
+    #[test]
+    fn test_spinning_cube_rotates_with_update() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let input = InputState::new();
+
+        assert!(scene.set_object_spin(id, glam::vec3(0.0, 1.0, 0.0)));
+        assert!(!scene.set_object_spin(ObjectId::generate(), glam::Vec3::ONE));
+        for _ in 0..4 {
+            scene.update(0.25, &input);
+        }
+
+        let rotation = scene.get_render_objects()[0].transform.rotation;
+        assert!(rotation.abs_diff_eq(glam::Quat::from_rotation_y(1.0), 1e-5));
+
+        // ゼロを渡すと停止する
+        assert!(scene.set_object_spin(id, glam::Vec3::ZERO));
+        scene.update(0.25, &input);
+        assert_eq!(scene.get_render_objects()[0].transform.rotation, rotation);
+    }
+
+    #[test]
+    fn test_frame_object_centers_camera_on_it() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::vec3(20.0, 0.0, -5.0));
+        scene.scale_object(id, glam::Vec3::splat(4.0));
+
+        assert!(scene.frame_object(id));
+        assert!(!scene.frame_object(ObjectId::generate()));
+
+        assert_eq!(scene.camera.target, glam::vec3(20.0, 0.0, -5.0));
+        // 中心を通る視線上のオブジェクトが選択できる
+        let (origin, direction) =
+            scene.screen_to_ray(glam::vec2(50.0, 50.0), glam::vec2(100.0, 100.0));
+        assert_eq!(scene.pick(origin, direction), Some(id));
+    }
+
+    #[test]
+    fn test_pick_returns_nearest_hit_object() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        // カメラ (0,0,3) から見て奥と手前に重なる立方体と、横にずれた立方体
+        let far = scene.add_object(ObjectType::Cube, glam::vec3(0.0, 0.0, -2.0));
+        let near = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let side = scene.add_object(ObjectType::Cube, glam::vec3(3.0, 0.0, 0.0));
+        let viewport = glam::vec2(100.0, 100.0);
+
+        let (origin, direction) = scene.screen_to_ray(viewport * 0.5, viewport);
+        assert_eq!(scene.pick(origin, direction), Some(near));
+
+        scene.set_object_visible(near, false);
+        assert_eq!(
+            scene.pick(origin, direction),
+            Some(far),
+            "非表示のオブジェクトは選ばれない"
+        );
+
+        assert_eq!(scene.pick(origin, glam::Vec3::Z), None, "背後には何もない");
+        assert_eq!(
+            scene.pick(glam::vec3(3.0, 0.0, 3.0), glam::Vec3::NEG_Z),
+            Some(side)
+        );
+    }
+
+    #[test]
+    fn test_scale_object_updates_model_matrix() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let id = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+
+        assert!(scene.scale_object(id, glam::vec3(2.0, 1.0, 0.5)));
+        assert!(!scene.scale_object(ObjectId::generate(), glam::Vec3::ONE));
+
+        let matrix = scene.get_render_objects()[0].transform.matrix();
+        assert_eq!(
+            matrix.transform_point3(glam::Vec3::ONE),
+            glam::vec3(2.0, 1.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_objects_share_mesh_with_separate_colors() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let red = scene.add_object(ObjectType::Quad, glam::vec3(-1.0, 0.0, 0.0));
+        let blue = scene.add_object(ObjectType::Quad, glam::vec3(1.0, 0.0, 0.0));
+
+        assert!(scene.set_object_color(red, [1.0, 0.0, 0.0, 1.0]));
+        assert!(scene.set_object_color(blue, [0.0, 0.0, 1.0, 1.0]));
+        assert!(!scene.set_object_color(ObjectId::generate(), [1.0; 4]));
+
+        let objects = scene.get_render_objects();
+        assert_eq!(
+            objects[0].mesh_id, objects[1].mesh_id,
+            "メッシュは共有される"
+        );
+        assert_eq!(objects[0].material.color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(objects[1].material.color, [0.0, 0.0, 1.0, 1.0]);
+        assert!(!Arc::ptr_eq(
+            objects[0].material_buffer.as_ref().unwrap(),
+            objects[1].material_buffer.as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_shared_mesh_survives_until_last_user_is_removed() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let original = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let mirrored = scene.mirror_object(original, Plane::X(0.0)).unwrap();
+        let mesh_id = scene.get_render_objects()[0].mesh_id;
+
+        scene.remove_object(original);
+        assert!(
+            scene.get_resource_manager().get_mesh(&mesh_id).is_some(),
+            "ミラーが使っているメッシュは残すべき"
+        );
+
+        scene.remove_object(mirrored);
+        assert!(scene.get_resource_manager().get_mesh(&mesh_id).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_save_clear_and_reload() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let quad = scene.add_object(ObjectType::Quad, glam::vec3(1.0, 0.0, 0.0));
+        let triangle = scene.add_object(ObjectType::Triangle, glam::vec3(-1.0, 2.0, 0.0));
+        scene.add_object(ObjectType::Quad, glam::vec3(0.0, 0.0, -3.0));
+        scene.render_objects[1].set_transform(
+            Transform::new()
+                .with_position(glam::vec3(-1.0, 2.0, 0.0))
+                .with_rotation(glam::Quat::from_rotation_z(0.25))
+                .with_scale(glam::vec3(2.0, 2.0, 1.0)),
+        );
+        scene.set_object_visible(quad, false);
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("scene.toml");
+        let snapshot = scene.to_snapshot();
+        snapshot.save_to_file(path.to_str().unwrap()).unwrap();
+
+        // すべてのオブジェクトを削除してから読み込み直す
+        for id in [quad, triangle] {
+            scene.remove_object(id);
+        }
+        let loaded = SceneSnapshot::load_from_file(path.to_str().unwrap()).unwrap();
+        let resource_manager = scene.take_resource_manager().unwrap();
+        let mut restored = DemoScene::from_snapshot(1.0, Arc::new(AppConfig::default()), loaded);
+        restored.initialize(resource_manager).unwrap();
+
+        assert_eq!(restored.get_render_objects().len(), 3);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        let ids: Vec<ObjectId> = restored.render_objects.iter().map(|obj| obj.id).collect();
+        assert!(!ids.contains(&quad), "IDは読み込み時に振り直される");
+    }
+
+    #[test]
+    fn test_snapshot_skips_custom_meshes() {
+        let Some(mut scene) = create_initialized_scene() else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+        let cube = scene.add_object(ObjectType::Cube, glam::Vec3::ZERO);
+        let mesh_id = scene.render_objects[0].mesh_id;
+        scene.add_mesh_object(mesh_id, glam::Vec3::ONE).unwrap();
+        scene.mirror_object(cube, Plane::X(1.0)).unwrap();
+
+        let snapshot = scene.to_snapshot();
+
+        // メッシュ指定のオブジェクトは含まれず、鏡映コピーは元の種類を引き継ぐ
+        assert_eq!(snapshot.objects.len(), 2);
+        assert!(
+            snapshot
+                .objects
+                .iter()
+                .all(|descriptor| descriptor.object_type == ObjectType::Cube)
+        );
     }
 }
@@ -0,0 +1,80 @@
+use glam::{Mat4, Vec3, Vec4};
+
+/// The six clipping planes of a camera, used to skip objects that cannot be
+/// on screen.
+///
+/// Each plane is stored as `(normal, distance)` with the normal pointing into
+/// the frustum, so a point `p` is inside when `normal.dot(p) + distance >= 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from a view-projection matrix with wgpu's 0..1
+    /// clip-space depth range.
+    pub fn from_view_proj(view_proj: Mat4) -> Self {
+        let row = |i| view_proj.row(i);
+        let planes = [
+            row(3) + row(0), // 左
+            row(3) - row(0), // 右
+            row(3) + row(1), // 下
+            row(3) - row(1), // 上
+            row(2),          // 手前（深度 0）
+            row(3) - row(2), // 奥（深度 1）
+        ]
+        .map(|plane| plane / plane.truncate().length());
+
+        Self { planes }
+    }
+
+    /// Returns `false` only if the sphere lies entirely outside one of the
+    /// planes. Spheres near a corner may be kept even though they are not
+    /// visible, which is harmless for culling.
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera_frustum() -> Frustum {
+        // 原点から -Z を向くカメラ
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let proj = Mat4::perspective_rh(90f32.to_radians(), 1.0, 0.1, 100.0);
+        Frustum::from_view_proj(proj * view)
+    }
+
+    #[test]
+    fn test_sphere_in_front_is_inside() {
+        assert!(camera_frustum().intersects_sphere(Vec3::new(0.0, 0.0, -5.0), 0.5));
+    }
+
+    #[test]
+    fn test_sphere_behind_camera_is_outside() {
+        assert!(!camera_frustum().intersects_sphere(Vec3::new(0.0, 0.0, 5.0), 0.5));
+    }
+
+    #[test]
+    fn test_sphere_beyond_far_plane_is_outside() {
+        assert!(!camera_frustum().intersects_sphere(Vec3::new(0.0, 0.0, -200.0), 1.0));
+    }
+
+    #[test]
+    fn test_sphere_outside_side_planes() {
+        let frustum = camera_frustum();
+        // 視野角 90° なので z=-5 での可視範囲は |x| <= 5
+        assert!(!frustum.intersects_sphere(Vec3::new(8.0, 0.0, -5.0), 1.0));
+        assert!(!frustum.intersects_sphere(Vec3::new(0.0, -8.0, -5.0), 1.0));
+    }
+
+    #[test]
+    fn test_sphere_overlapping_edge_is_kept() {
+        // 中心は視野外だが半径が境界にかかっている
+        assert!(camera_frustum().intersects_sphere(Vec3::new(6.0, 0.0, -5.0), 1.0));
+    }
+}
@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use crate::{
+    core::config::{LightConfig, SunConfig},
+    resources::uniforms::{LightUniform, PointLightUniform},
+};
+
+/// Light color at sunrise and sunset.
+const HORIZON_COLOR: glam::Vec3 = glam::vec3(1.0, 0.55, 0.25);
+/// Light color with the sun overhead.
+const NOON_COLOR: glam::Vec3 = glam::Vec3::ONE;
+
+/// Light arriving from a single direction with no falloff (sunlight).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirectionalLight {
+    /// Normalized direction the light travels.
+    pub direction: glam::Vec3,
+    /// Linear RGB color (may exceed 1 for brighter light).
+    pub color: glam::Vec3,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: glam::Vec3, color: glam::Vec3) -> Self {
+        Self {
+            direction: direction.normalize_or(glam::Vec3::NEG_Y),
+            color,
+        }
+    }
+
+    pub fn from_config(config: &LightConfig) -> Self {
+        Self::new(
+            glam::Vec3::from(config.direction),
+            glam::Vec3::from(config.color),
+        )
+    }
+
+    /// Points the light along `direction`; a zero vector is ignored.
+    pub fn set_direction(&mut self, direction: glam::Vec3) {
+        if let Some(direction) = direction.try_normalize() {
+            self.direction = direction;
+        }
+    }
+
+    pub fn uniform(&self) -> LightUniform {
+        LightUniform::new(self.direction, self.color)
+    }
+
+    /// Orthographic view-projection looking along the light that covers a
+    /// sphere of `radius` around `center`, with depth 0 on the side the
+    /// light comes from. Used to render and look up the shadow map.
+    pub fn view_proj(&self, center: glam::Vec3, radius: f32) -> glam::Mat4 {
+        let radius = radius.max(f32::EPSILON);
+        // 真上・真下からの光では Y を上方向にできない
+        let up = if self.direction.y.abs() > 0.99 {
+            glam::Vec3::Z
+        } else {
+            glam::Vec3::Y
+        };
+        let eye = center - self.direction * radius;
+        let view = glam::Mat4::look_at_rh(eye, center, up);
+        let projection =
+            glam::Mat4::orthographic_rh(-radius, radius, -radius, radius, 0.0, 2.0 * radius);
+        projection * view
+    }
+}
+
+/// What the renderer needs to draw the directional light's shadow map.
+pub struct ShadowPass<'a> {
+    /// Depth texture the shadow casters are rendered into.
+    pub view: &'a wgpu::TextureView,
+    /// Light view-projection (group 0 of the shadow pipelines).
+    pub bind_group: &'a Arc<wgpu::BindGroup>,
+}
+
+/// Light radiating from a position, fading to nothing at `radius`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    pub position: glam::Vec3,
+    /// Linear RGB color at the light's position.
+    pub color: glam::Vec3,
+    /// Distance at which the contribution reaches zero.
+    pub radius: f32,
+}
+
+impl PointLight {
+    pub fn new(position: glam::Vec3, color: glam::Vec3, radius: f32) -> Self {
+        Self {
+            position,
+            color,
+            radius: radius.max(f32::EPSILON),
+        }
+    }
+
+    /// Attenuation factor at `distance`, matching the shader: a smooth
+    /// `(1 - (d / r)^2)^2` falloff that is 1 at the light and 0 at `radius`.
+    #[allow(dead_code)]
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        let ratio = (distance / self.radius).clamp(0.0, 1.0);
+        let falloff = 1.0 - ratio * ratio;
+        falloff * falloff
+    }
+
+    pub fn uniform(&self) -> PointLightUniform {
+        PointLightUniform {
+            position: self.position.to_array(),
+            radius: self.radius,
+            color: self.color.to_array(),
+            _padding: 0.0,
+        }
+    }
+}
+
+/// Directional light animated like a sun over a day.
+///
+/// The sun rises in the east (+X) at `t = 0`, is overhead at half the day
+/// length and sets in the west (-X) at the end of the day, after which the
+/// cycle repeats. Color shifts from warm at the horizon to white at noon.
+pub struct Sun {
+    animate: bool,
+    day_length: f32,
+    time: f32,
+}
+
+impl Sun {
+    pub fn new(config: &SunConfig) -> Self {
+        let day_length = config.day_length_seconds.max(f32::EPSILON);
+        Self {
+            animate: config.animate,
+            day_length,
+            // アニメーションしない場合は正午で固定
+            time: if config.animate {
+                0.0
+            } else {
+                day_length * 0.5
+            },
+        }
+    }
+
+    pub fn is_animated(&self) -> bool {
+        self.animate
+    }
+
+    /// Advances the time of day by `dt` seconds (no-op unless animating).
+    pub fn update(&mut self, dt: f32) {
+        if self.animate {
+            self.time = (self.time + dt) % self.day_length;
+        }
+    }
+
+    /// Sun angle above the eastern horizon, in radians (`0..PI`).
+    fn angle(&self) -> f32 {
+        std::f32::consts::PI * (self.time / self.day_length)
+    }
+
+    /// Normalized direction the light travels (from the sun towards the scene).
+    pub fn direction(&self) -> glam::Vec3 {
+        let angle = self.angle();
+        -glam::vec3(angle.cos(), angle.sin(), 0.0)
+    }
+
+    /// Light color for the current sun elevation.
+    pub fn color(&self) -> glam::Vec3 {
+        let elevation = self.angle().sin().clamp(0.0, 1.0);
+        HORIZON_COLOR.lerp(NOON_COLOR, elevation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animated_sun(day_length_seconds: f32) -> Sun {
+        Sun::new(&SunConfig {
+            animate: true,
+            day_length_seconds,
+        })
+    }
+
+    #[test]
+    fn test_dawn_is_on_horizon_and_warm() {
+        let sun = animated_sun(60.0);
+
+        let direction = sun.direction();
+        assert!(
+            direction.y.abs() < 1e-5,
+            "夜明けの光は水平であるべき: {:?}",
+            direction
+        );
+
+        let color = sun.color();
+        assert!(
+            color.x > color.z + 0.5,
+            "夜明けは暖色であるべき: {:?}",
+            color
+        );
+    }
+
+    #[test]
+    fn test_midday_is_overhead_and_white() {
+        let mut sun = animated_sun(60.0);
+        sun.update(30.0);
+
+        let direction = sun.direction();
+        assert!(
+            (direction - glam::Vec3::NEG_Y).length() < 1e-5,
+            "{:?}",
+            direction
+        );
+        assert!((sun.color() - glam::Vec3::ONE).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_day_cycle_wraps() {
+        let mut sun = animated_sun(60.0);
+        sun.update(75.0);
+
+        let mut expected = animated_sun(60.0);
+        expected.update(15.0);
+        assert!((sun.direction() - expected.direction()).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_directional_light_normalizes_direction() {
+        let mut light = DirectionalLight::new(glam::vec3(0.0, -2.0, 0.0), glam::Vec3::ONE);
+        assert_eq!(light.direction, glam::Vec3::NEG_Y);
+
+        light.set_direction(glam::vec3(3.0, 0.0, 4.0));
+        assert!((light.direction - glam::vec3(0.6, 0.0, 0.8)).length() < 1e-6);
+
+        // ゼロベクトルは無視される
+        light.set_direction(glam::Vec3::ZERO);
+        assert!((light.direction - glam::vec3(0.6, 0.0, 0.8)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_directional_light_from_default_config() {
+        let light = DirectionalLight::from_config(&LightConfig::default());
+
+        assert!((light.direction.length() - 1.0).abs() < 1e-6);
+        assert!(light.direction.y < 0.0, "デフォルトは上から照らすべき");
+        assert_eq!(light.uniform().color, [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_light_view_proj_covers_bounds_along_light() {
+        let center = glam::vec3(1.0, 2.0, 0.0);
+        for direction in [glam::Vec3::NEG_Y, glam::vec3(1.0, -1.0, 0.5)] {
+            let light = DirectionalLight::new(direction, glam::Vec3::ONE);
+            let view_proj = light.view_proj(center, 4.0);
+
+            let ndc = view_proj.project_point3(center);
+            assert!(ndc.truncate().length() < 1e-5, "{:?}", ndc);
+            assert!((ndc.z - 0.5).abs() < 1e-5, "{:?}", ndc);
+
+            // 光源側が手前（深度が小さい）
+            let near = view_proj.project_point3(center - light.direction * 3.0);
+            let far = view_proj.project_point3(center + light.direction * 3.0);
+            assert!(near.z < ndc.z && ndc.z < far.z);
+            assert!(near.z >= 0.0 && far.z <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_point_light_attenuation_falls_off_to_radius() {
+        let light = PointLight::new(glam::Vec3::ZERO, glam::Vec3::ONE, 4.0);
+
+        assert_eq!(light.attenuation(0.0), 1.0);
+        assert!(light.attenuation(1.0) > light.attenuation(2.0));
+        assert_eq!(light.attenuation(4.0), 0.0);
+        assert_eq!(light.attenuation(10.0), 0.0, "半径の外は寄与しない");
+    }
+
+    #[test]
+    fn test_static_sun_stays_at_noon() {
+        let mut sun = Sun::new(&SunConfig::default());
+        sun.update(10.0);
+
+        assert!((sun.direction() - glam::Vec3::NEG_Y).length() < 1e-5);
+    }
+}
@@ -1,14 +1,30 @@
 use crate::{
+    core::{config::AppConfig, error::EngineResult},
     input::InputState,
-    resources::{manager::ResourceManager, primitives::ObjectType},
-    scene::render_object::{ObjectId, RenderObject},
+    resources::{
+        manager::{ResourceId, ResourceManager},
+        primitives::ObjectType,
+    },
+    scene::{
+        debug_lines::DebugLines,
+        render_object::{ObjectId, RenderObject},
+        snapshot::SceneSnapshot,
+        transform::{Plane, Transform},
+        viewport::{SceneView, Viewport},
+    },
 };
 
 pub mod camera;
+pub mod debug_lines;
 pub mod demo_scene;
+pub mod frustum;
+pub mod light;
 pub mod manager;
+pub mod physics;
 pub mod render_object;
+pub mod snapshot;
 pub mod transform;
+pub mod viewport;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SceneId(u64);
@@ -35,25 +51,71 @@ impl SceneId {
 ///
 /// 1. `initialize()` - Set up GPU resources and objects
 /// 2. `update()` - Handle input and animations each frame
-/// 3. `update_camera_uniform()` - Sync camera data to GPU
+/// 3. `update_global_uniform()` / `update_camera_uniform()` /
+///    `update_light_uniform()` / `update_model_uniforms()` - Sync time,
+///    camera, light and object transforms to GPU
 /// 4. Rendering methods provide access to render data
 pub trait Scene {
     /// Initialize scene resources using the provided resource manager.
     ///
     /// Creates meshes, shaders, pipelines, and other GPU resources needed
     /// for rendering this scene's objects.
-    fn initialize(&mut self, resource_manager: ResourceManager);
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if a shader, pipeline or uniform resource cannot
+    /// be created; the scene is then left uninitialized.
+    fn initialize(&mut self, resource_manager: ResourceManager) -> EngineResult<()>;
+
+    /// Hands the resource manager back so another scene can be initialized
+    /// with it (scene switching).
+    ///
+    /// The scene keeps its objects, whose GPU resources stay registered in the
+    /// manager; `initialize()` must run again before it is rendered.
+    fn take_resource_manager(&mut self) -> Option<ResourceManager>;
 
     /// Returns the list of objects to be rendered in this scene.
     fn get_render_objects(&self) -> &[RenderObject];
 
+    /// Captures the type, transform and visibility of the scene's primitive
+    /// objects for saving.
+    #[allow(dead_code)]
+    fn to_snapshot(&self) -> SceneSnapshot;
+
     /// Returns the camera's bind group for shader uniform binding.
+    #[allow(dead_code)]
     fn get_camera_bind_group(&self) -> Option<&std::sync::Arc<wgpu::BindGroup>>;
 
+    /// Applies live-reloadable settings (movement speeds, camera projection).
+    fn apply_config(&mut self, config: &AppConfig);
+
+    /// Writes the scene's live settings (camera projection, movement speeds,
+    /// light, wireframe and grid toggles) back into `config` for saving.
+    fn export_config(&self, config: &mut AppConfig);
+
+    /// Points the scene's directional light along `direction` (the direction
+    /// the light travels). Overridden each frame while the sun is animating.
+    #[allow(dead_code)]
+    fn set_light_direction(&mut self, direction: glam::Vec3);
+
+    /// Adds a point light and returns its index.
+    ///
+    /// Returns `None` (and logs a warning) once `MAX_POINT_LIGHTS` lights exist.
+    fn add_point_light(&mut self, light: light::PointLight) -> Option<usize>;
+
+    /// Moves the point light at `index`. Returns `false` if it does not exist.
+    #[allow(dead_code)]
+    fn set_point_light_position(&mut self, index: usize, position: glam::Vec3) -> bool;
+
+    /// Returns the light bind group (group 2 of lit pipelines).
+    fn get_light_bind_group(&self) -> Option<&std::sync::Arc<wgpu::BindGroup>>;
+
     /// Returns the camera's uniform buffer for GPU data updates.
+    #[allow(dead_code)]
     fn get_camera_buffer(&self) -> Option<&std::sync::Arc<wgpu::Buffer>>;
 
     /// Returns the current camera uniform data.
+    #[allow(dead_code)]
     fn get_camera_uniform(&self) -> &crate::resources::uniforms::CameraUniform;
 
     /// Update scene state based on delta time and user input.
@@ -64,15 +126,206 @@ pub trait Scene {
     /// * `input` - Current input state (keyboard, mouse, etc.)
     fn update(&mut self, dt: f32, input: &InputState);
 
+    /// Upload the per-frame time and resolution, visible to shaders at
+    /// group 0, binding 1.
+    fn update_global_uniform(&mut self, globals: crate::resources::uniforms::GlobalUniform);
+
     /// Update camera uniform data from current camera state.
     ///
     /// Should be called after camera modifications to sync view/projection
     /// matrices with GPU uniform buffer.
     fn update_camera_uniform(&mut self);
 
+    /// Upload the current light state to the light uniform buffer.
+    fn update_light_uniform(&mut self);
+
+    /// Depth target and light bind group of the directional light's shadow
+    /// map, rendered before the scene pass; `None` while shadows are off.
+    fn shadow_pass(&self) -> Option<light::ShadowPass<'_>>;
+
+    /// Upload object model matrices to their per-object uniform buffers.
+    ///
+    /// Called every frame after `update()` so transform changes made through
+    /// `move_object` and friends are visible in the next rendered frame.
+    /// Static objects are only uploaded when their transform changed.
+    fn update_model_uniforms(&mut self);
+
+    /// Adapts the scene to a new viewport size.
+    ///
+    /// Updates the camera aspect ratio and re-uploads the camera uniform so the
+    /// projection matches the resized surface.
+    fn on_resize(&mut self, width: u32, height: u32);
+
+    /// Sets the subpixel projection offset (NDC units) applied to the camera
+    /// uniform, used by temporal anti-aliasing. Takes effect on the next
+    /// `update_camera_uniform()`.
+    fn set_projection_jitter(&mut self, jitter: glam::Vec2);
+
+    /// Returns the camera view-projection matrix without projection jitter.
+    fn camera_view_proj(&self) -> glam::Mat4;
+
+    /// Returns the world-space camera position.
+    #[allow(dead_code)]
+    fn camera_eye(&self) -> glam::Vec3;
+
+    /// Cameras to render, each into its own viewport and with its own camera
+    /// bind group; the main camera comes first. Empty until initialized.
+    fn views(&self) -> Vec<SceneView<'_>>;
+
+    /// Sets the region of the render target the main camera draws into; its
+    /// aspect ratio follows the viewport.
+    #[allow(dead_code)]
+    fn set_main_viewport(&mut self, viewport: Viewport);
+
+    /// Adds a camera rendered into `viewport` after the main one (split
+    /// screen) and returns its index. Added cameras are not moved by input,
+    /// and picking and temporal anti-aliasing still follow the main camera.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError` if the camera's uniform resources cannot be created.
+    #[allow(dead_code)]
+    fn add_camera_view(
+        &mut self,
+        camera: camera::Camera,
+        viewport: Viewport,
+    ) -> EngineResult<usize>;
+
+    /// Returns the world-space ray `(origin, direction)` under a screen
+    /// position, see `Camera::screen_to_ray`.
+    fn screen_to_ray(
+        &self,
+        mouse_pos: glam::Vec2,
+        viewport_size: glam::Vec2,
+    ) -> (glam::Vec3, glam::Vec3);
+
+    /// Returns the nearest visible object whose world AABB the ray hits.
+    fn pick(&self, origin: glam::Vec3, direction: glam::Vec3) -> Option<ObjectId>;
+
+    /// Moves the camera so the object's world AABB fills the view, keeping
+    /// the view direction. Returns `false` if the object does not exist.
+    #[allow(dead_code)]
+    fn frame_object(&mut self, object_id: ObjectId) -> bool;
+
+    /// Pipeline and mesh of the reference grid, drawn in the main pass before
+    /// the objects (not an object itself), or `None` while the grid is hidden.
+    /// Without a depth buffer (TAA off) objects simply paint over it.
+    fn grid_overlay(&self) -> Option<(ResourceId, ResourceId)>;
+
+    /// Shows or hides the reference grid on the XZ plane.
+    #[allow(dead_code)]
+    fn set_grid_visible(&mut self, visible: bool);
+
+    /// Debug lines drawn over the next rendered frame.
+    fn debug_lines(&self) -> &DebugLines;
+
+    /// Lines submitted here (e.g. during `update()`) are drawn over the next
+    /// frame; the engine clears them after rendering it.
+    fn debug_lines_mut(&mut self) -> &mut DebugLines;
+
     fn get_resource_manager(&self) -> &ResourceManager;
     fn add_object(&mut self, object_type: ObjectType, position: glam::Vec3) -> ObjectId;
+    /// Adds one object per `(type, position)` pair and returns their IDs in
+    /// the same order. Objects of the same type share one mesh.
+    #[allow(dead_code)]
+    fn add_objects(&mut self, objects: &[(ObjectType, glam::Vec3)]) -> Vec<ObjectId>;
+
+    /// Adds an object drawing a mesh already registered in the resource
+    /// manager (e.g. one loaded with `ResourceManager::load_obj`).
+    ///
+    /// Returns `None` if no mesh is registered under `mesh_id`.
+    fn add_mesh_object(&mut self, mesh_id: ResourceId, position: glam::Vec3) -> Option<ObjectId>;
+
+    /// Adds a quad showing a texture created with `ResourceManager::create_texture`.
+    ///
+    /// Returns `None` if no texture is registered under `texture_id`.
+    fn add_textured_quad(
+        &mut self,
+        position: glam::Vec3,
+        texture_id: ResourceId,
+    ) -> Option<ObjectId>;
+    #[allow(dead_code)]
     fn remove_object(&mut self, object_id: ObjectId) -> bool;
+    /// Removes every object and releases its GPU resources, including the
+    /// meshes the scene created (meshes registered by the caller are kept).
+    #[allow(dead_code)]
+    fn clear_objects(&mut self);
+    /// Returns how many objects the scene contains, hidden ones included.
+    #[allow(dead_code)]
+    fn object_count(&self) -> usize;
+    /// Returns the IDs of all objects in draw order.
+    #[allow(dead_code)]
+    fn object_ids(&self) -> Vec<ObjectId>;
+    #[allow(dead_code)]
     fn move_object(&mut self, object_id: ObjectId, position: glam::Vec3) -> bool;
+    /// Sets the rotation of an object; the counterpart of `move_object`.
+    #[allow(dead_code)]
+    fn rotate_object(&mut self, object_id: ObjectId, rotation: glam::Quat) -> bool;
+    /// Sets the tint color an object's vertex or texture color is multiplied by.
+    #[allow(dead_code)]
+    fn set_object_color(&mut self, object_id: ObjectId, color: [f32; 4]) -> bool;
+    /// Sets the per-axis scale of an object.
+    #[allow(dead_code)]
+    fn scale_object(&mut self, object_id: ObjectId, scale: glam::Vec3) -> bool;
+    /// Returns the current transform of an object, or `None` if it does not exist.
+    #[allow(dead_code)]
+    fn get_object_transform(&self, object_id: ObjectId) -> Option<Transform>;
+    /// Replaces the whole transform of an object (without interpolation).
+    #[allow(dead_code)]
+    fn set_object_transform(&mut self, object_id: ObjectId, transform: Transform) -> bool;
+    #[allow(dead_code)]
     fn set_object_visible(&mut self, object_id: ObjectId, visible: bool) -> bool;
+
+    /// Sets the render-layer bitmask of an object.
+    ///
+    /// Passes only draw objects whose mask shares a bit with the pass mask.
+    #[allow(dead_code)]
+    fn set_object_layers(&mut self, object_id: ObjectId, layer_mask: u32) -> bool;
+
+    /// Creates a mirrored copy of an object across an axis-aligned plane.
+    ///
+    /// The copy reuses the source mesh; its transform has a negative
+    /// determinant, so it is drawn with reversed winding. Returns `None` if the
+    /// source object does not exist.
+    #[allow(dead_code)]
+    fn mirror_object(&mut self, object_id: ObjectId, plane: Plane) -> Option<ObjectId>;
+
+    /// Attaches an object to the camera so it moves and rotates with it.
+    ///
+    /// Each frame the object's world transform becomes `camera * offset`,
+    /// where the camera transform is derived from eye/target/up.
+    #[allow(dead_code)]
+    fn attach_to_camera(&mut self, object_id: ObjectId, offset: Transform) -> bool;
+
+    /// Detaches an object from the camera; it stays where it was last placed.
+    #[allow(dead_code)]
+    fn detach_from_camera(&mut self, object_id: ObjectId) -> bool;
+
+    /// Marks an object as static (never animated).
+    ///
+    /// Static objects skip per-frame model uniform uploads; moving one with
+    /// `move_object` still schedules a single upload.
+    #[allow(dead_code)]
+    fn set_object_static(&mut self, object_id: ObjectId, is_static: bool) -> bool;
+
+    /// Draws the object with the pulse shader, whose color cycles with the
+    /// global time, or back with the basic one. Returns `false` if the object
+    /// does not exist or is textured.
+    fn set_object_pulse(&mut self, object_id: ObjectId, pulse: bool) -> bool;
+
+    /// Spins an object continuously at `angular_velocity` (world-space,
+    /// radians per second); `Vec3::ZERO` stops it.
+    #[allow(dead_code)]
+    fn set_object_spin(&mut self, object_id: ObjectId, angular_velocity: glam::Vec3) -> bool;
+
+    /// Spawns an object with a rigidbody moving at `velocity`.
+    ///
+    /// The projectile despawns after the scene's configured lifetime or when it
+    /// falls to the ground height.
+    fn spawn_projectile(
+        &mut self,
+        object_type: ObjectType,
+        origin: glam::Vec3,
+        velocity: glam::Vec3,
+    ) -> ObjectId;
 }
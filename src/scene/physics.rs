@@ -0,0 +1,124 @@
+/// Gravitational acceleration applied to rigidbodies with `use_gravity` set.
+pub const GRAVITY: glam::Vec3 = glam::Vec3::new(0.0, -9.81, 0.0);
+
+/// Minimal point-mass body integrated once per frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rigidbody {
+    pub velocity: glam::Vec3,
+    pub use_gravity: bool,
+}
+
+impl Rigidbody {
+    pub fn new(velocity: glam::Vec3) -> Self {
+        Self {
+            velocity,
+            use_gravity: false,
+        }
+    }
+
+    pub fn with_gravity(mut self, use_gravity: bool) -> Self {
+        self.use_gravity = use_gravity;
+        self
+    }
+
+    /// Advances the body by `dt` seconds and returns the new position.
+    ///
+    /// Uses semi-implicit Euler: velocity is updated first, then position.
+    pub fn integrate(&mut self, position: glam::Vec3, dt: f32) -> glam::Vec3 {
+        if self.use_gravity {
+            self.velocity += GRAVITY * dt;
+        }
+        position + self.velocity * dt
+    }
+}
+
+/// Fraction of a step treated as rounding error when counting whole steps.
+const STEP_TOLERANCE: f32 = 1e-4;
+
+/// Fixed-timestep accumulator: converts variable frame deltas into a whole
+/// number of fixed steps and reports the leftover fraction for interpolation.
+pub struct FixedTimestep {
+    step: f32,
+    max_steps: u32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub fn new(step: f32, max_steps: u32) -> Self {
+        Self {
+            step: step.max(f32::EPSILON),
+            max_steps: max_steps.max(1),
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Adds `dt` and returns how many fixed steps to run this frame.
+    ///
+    /// At most `max_steps` are returned; any further backlog is dropped so a
+    /// long hitch does not snowball into ever longer frames.
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        self.accumulator += dt.max(0.0);
+        // 浮動小数の誤差でステップを取りこぼさないよう許容幅を加える
+        let steps = ((self.accumulator / self.step + STEP_TOLERANCE) as u32).min(self.max_steps);
+        self.accumulator = (self.accumulator - steps as f32 * self.step).max(0.0);
+        if steps == self.max_steps {
+            self.accumulator = self.accumulator.min(self.step);
+        }
+        steps
+    }
+
+    /// Fraction of a step left in the accumulator, in `[0, 1]`.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_timestep_counts_whole_steps() {
+        let mut timestep = FixedTimestep::new(0.1, 5);
+
+        assert_eq!(timestep.advance(0.25), 2);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-4);
+
+        assert_eq!(timestep.advance(0.05), 1);
+        assert!(timestep.alpha() < 1e-4);
+    }
+
+    #[test]
+    fn test_fixed_timestep_drops_backlog() {
+        let mut timestep = FixedTimestep::new(0.1, 3);
+
+        // 長いヒッチでも上限ステップ数に抑える
+        assert_eq!(timestep.advance(2.0), 3);
+        assert!(timestep.alpha() <= 1.0);
+        assert!(timestep.advance(0.0) <= 1);
+    }
+
+    #[test]
+    fn test_integrate_without_gravity_is_linear() {
+        let mut body = Rigidbody::new(glam::vec3(2.0, 0.0, -1.0));
+
+        let position = body.integrate(glam::Vec3::ZERO, 0.5);
+
+        assert_eq!(position, glam::vec3(1.0, 0.0, -0.5));
+        assert_eq!(body.velocity, glam::vec3(2.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_gravity_accelerates_downward() {
+        let mut body = Rigidbody::new(glam::Vec3::ZERO).with_gravity(true);
+
+        let position = body.integrate(glam::Vec3::ZERO, 1.0);
+
+        assert_eq!(body.velocity, GRAVITY);
+        assert!(position.y < 0.0, "重力で下に落ちるべき");
+    }
+}
@@ -4,12 +4,26 @@ use std::sync::{
 };
 
 use crate::{
-    resources::{manager::ResourceId, uniforms::ModelUniform},
-    scene::transform::Transform,
+    resources::{
+        manager::ResourceId,
+        mesh::{Aabb, Mesh},
+        primitives::ObjectType,
+        uniforms::{MaterialUniform, ModelUniform},
+        vertex::InstanceData,
+    },
+    scene::{
+        physics::Rigidbody,
+        transform::{Plane, Transform},
+    },
 };
 
 static NEXT_OBJECT_ID: AtomicU32 = AtomicU32::new(1);
 
+/// Layer bit assigned to newly created objects.
+pub const LAYER_DEFAULT: u32 = 1 << 0;
+/// Mask matching every layer; used by the main scene pass.
+pub const LAYER_ALL: u32 = u32::MAX;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ObjectId(u32);
 
@@ -26,10 +40,40 @@ pub struct RenderObject {
     pub mesh_id: ResourceId,
     pub pipeline_id: ResourceId,
     pub transform: Transform,
+    /// Transform before the last fixed physics step, blended with `transform`
+    /// when uploading so motion stays smooth between steps.
+    previous_transform: Transform,
     pub visible: bool,
+    /// Bitmask of render layers this object belongs to.
+    pub layer_mask: u32,
+    /// Static objects never animate; their model uniform is uploaded only
+    /// after an explicit transform change instead of every frame.
+    pub is_static: bool,
+    /// Set when the transform changed since the last model uniform upload.
+    model_dirty: bool,
+    /// Matrix of the last model upload, reused as instance data.
+    uploaded_model: Option<glam::Mat4>,
+    /// Tint uploaded to `material_buffer`.
+    pub material: MaterialUniform,
+    /// Set when the material changed since the last upload.
+    material_dirty: bool,
+    /// Moves the object every frame when present.
+    pub rigidbody: Option<Rigidbody>,
+    /// World-space angular velocity in radians per second; spins the object
+    /// every frame when present.
+    pub angular_velocity: Option<glam::Vec3>,
+    /// Seconds left before the object despawns; `None` lives forever.
+    pub lifetime: Option<f32>,
+    /// Offset from the camera while attached to it (HUD/held items).
+    pub camera_offset: Option<Transform>,
     pub id: ObjectId,
+    /// Primitive the object was created from; `None` for custom meshes.
+    pub object_type: Option<ObjectType>,
     pub model_buffer: Option<Arc<wgpu::Buffer>>,
     pub model_bind_group: Option<Arc<wgpu::BindGroup>>,
+    pub material_buffer: Option<Arc<wgpu::Buffer>>,
+    /// Texture and sampler bound at group 3 by textured pipelines.
+    pub material_bind_group: Option<Arc<wgpu::BindGroup>>,
 }
 
 impl RenderObject {
@@ -38,29 +82,528 @@ impl RenderObject {
             mesh_id,
             pipeline_id,
             transform: Transform::new(),
+            previous_transform: Transform::new(),
             visible: true,
+            layer_mask: LAYER_DEFAULT,
+            is_static: false,
+            model_dirty: true,
+            uploaded_model: None,
+            material: MaterialUniform::default(),
+            material_dirty: false,
+            rigidbody: None,
+            angular_velocity: None,
+            lifetime: None,
+            camera_offset: None,
             id: ObjectId::generate(),
+            object_type: None,
             model_buffer: None,
             model_bind_group: None,
+            material_buffer: None,
+            material_bind_group: None,
         }
     }
 
     pub fn with_transform(mut self, transform: Transform) -> Self {
         self.transform = transform;
+        self.previous_transform = transform;
         self
     }
 
+    #[allow(dead_code)]
     pub fn get_model_matrix(&self) -> glam::Mat4 {
         self.transform.matrix()
     }
 
+    #[allow(dead_code)]
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible
     }
 
+    pub fn set_layer_mask(&mut self, layer_mask: u32) {
+        self.layer_mask = layer_mask;
+    }
+
+    /// Returns `true` if the object shares at least one layer bit with `pass_mask`.
+    pub fn in_layers(&self, pass_mask: u32) -> bool {
+        self.layer_mask & pass_mask != 0
+    }
+
+    /// Creates a copy of this object reflected across `plane`.
+    ///
+    /// The copy shares the mesh and pipeline, gets a new id and has no GPU
+    /// resources yet; the scene creates its model uniform.
+    pub fn mirrored(&self, plane: Plane) -> Self {
+        let mut object = RenderObject::new(self.mesh_id, self.pipeline_id)
+            .with_transform(self.transform.mirrored(plane));
+        object.visible = self.visible;
+        object.layer_mask = self.layer_mask;
+        object.is_static = self.is_static;
+        object.object_type = self.object_type;
+        object.material = self.material;
+        object.material_bind_group = self.material_bind_group.clone();
+        object
+    }
+
+    pub fn set_static(&mut self, is_static: bool) {
+        self.is_static = is_static;
+        self.model_dirty = true;
+    }
+
+    /// Moves the object and schedules a model uniform upload.
+    ///
+    /// The move is a teleport: it is not interpolated.
+    pub fn set_position(&mut self, position: glam::Vec3) {
+        self.transform.set_position(position);
+        self.previous_transform = self.transform;
+        self.model_dirty = true;
+    }
+
+    /// Rotates the object in place and schedules a model uniform upload.
+    pub fn set_rotation(&mut self, rotation: glam::Quat) {
+        self.transform.rotation = rotation;
+        self.previous_transform = self.transform;
+        self.model_dirty = true;
+    }
+
+    /// Rescales the object in place and schedules a model uniform upload.
+    pub fn set_scale(&mut self, scale: glam::Vec3) {
+        self.transform.scale = scale;
+        self.previous_transform = self.transform;
+        self.model_dirty = true;
+    }
+
+    /// Replaces the transform (without interpolation) and schedules a model
+    /// uniform upload.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+        self.previous_transform = transform;
+        self.model_dirty = true;
+    }
+
+    /// Blends the previous and current fixed-step transforms; `alpha` 0 is
+    /// the previous state and 1 the current one.
+    pub fn interpolated_transform(&self, alpha: f32) -> Transform {
+        let (previous, current) = (&self.previous_transform, &self.transform);
+        Transform {
+            position: previous.position.lerp(current.position, alpha),
+            rotation: previous.rotation.slerp(current.rotation, alpha),
+            scale: previous.scale.lerp(current.scale, alpha),
+        }
+    }
+
+    /// Places an attached object at `camera_matrix * offset`; does nothing
+    /// when the object is not attached to the camera.
+    pub fn follow_camera(&mut self, camera_matrix: glam::Mat4) {
+        if let Some(offset) = self.camera_offset {
+            self.set_transform(Transform::from_matrix(camera_matrix * offset.matrix()));
+        }
+    }
+
+    /// Rotates the object by its angular velocity over `dt` seconds.
+    ///
+    /// The previous fixed-step rotation turns by the same amount, so physics
+    /// interpolation is unaffected.
+    pub fn spin(&mut self, dt: f32) {
+        if let Some(angular_velocity) = self.angular_velocity {
+            let delta = glam::Quat::from_scaled_axis(angular_velocity * dt);
+            self.transform.rotation = (delta * self.transform.rotation).normalize();
+            self.previous_transform.rotation =
+                (delta * self.previous_transform.rotation).normalize();
+            self.model_dirty = true;
+        }
+    }
+
+    /// Integrates the rigidbody and counts down the lifetime by `dt` seconds.
+    ///
+    /// Call once per fixed step; the state before the step is kept for
+    /// interpolation.
+    pub fn step_physics(&mut self, dt: f32) {
+        self.previous_transform = self.transform;
+        if let Some(rigidbody) = self.rigidbody.as_mut() {
+            self.transform.position = rigidbody.integrate(self.transform.position, dt);
+            self.model_dirty = true;
+        }
+        if let Some(lifetime) = self.lifetime.as_mut() {
+            *lifetime -= dt;
+        }
+    }
+
+    /// Returns `true` once a limited-lifetime object should despawn: its
+    /// lifetime ran out or it fell to `ground_height`.
+    pub fn is_expired(&self, ground_height: f32) -> bool {
+        self.lifetime
+            .is_some_and(|lifetime| lifetime <= 0.0 || self.transform.position.y <= ground_height)
+    }
+
+    /// Sets the tint color and schedules a material uniform upload.
+    pub fn set_color(&mut self, color: [f32; 4]) {
+        self.material.color = color;
+        self.material_dirty = true;
+    }
+
+    /// Returns the material uniform if it changed since the last upload.
+    pub fn take_material_upload(&mut self) -> Option<MaterialUniform> {
+        std::mem::take(&mut self.material_dirty).then_some(self.material)
+    }
+
+    /// Returns the model uniform to upload this frame, if any.
+    ///
+    /// Dynamic objects are uploaded every frame; static objects only when
+    /// their transform changed since the previous upload. The uploaded matrix
+    /// is interpolated between the last two fixed steps by `alpha`.
+    pub fn take_model_upload(&mut self, alpha: f32) -> Option<ModelUniform> {
+        if self.is_static && !self.model_dirty {
+            return None;
+        }
+        self.model_dirty = false;
+        let model = self.interpolated_transform(alpha).matrix();
+        self.uploaded_model = Some(model);
+        Some(ModelUniform {
+            model: model.to_cols_array_2d(),
+        })
+    }
+
+    /// Per-instance data for instanced draws: the same (interpolated) matrix
+    /// as the last model upload, or the current transform before the first.
+    pub fn instance_data(&self) -> InstanceData {
+        InstanceData {
+            model: self
+                .uploaded_model
+                .unwrap_or_else(|| self.transform.matrix())
+                .to_cols_array_2d(),
+            color: self.material.color,
+        }
+    }
+
+    /// World-space bounds of `mesh` (the object's mesh) under the current
+    /// transform.
+    pub fn world_aabb(&self, mesh: &Mesh) -> Aabb {
+        mesh.aabb().transformed(self.transform.matrix())
+    }
+
     pub fn get_model_uniform_data(&self) -> ModelUniform {
         ModelUniform {
             model: self.transform.matrix().to_cols_array_2d(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_uniform_follows_transform() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_position(glam::vec3(1.0, -2.0, 3.0));
+
+        let model = glam::Mat4::from_cols_array_2d(&object.get_model_uniform_data().model);
+
+        // 平行移動成分がユニフォームに反映されるべき
+        assert_eq!(model.w_axis.truncate(), glam::vec3(1.0, -2.0, 3.0));
+        assert_eq!(
+            model.transform_point3(glam::Vec3::ZERO),
+            glam::vec3(1.0, -2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_static_object_uploads_only_on_change() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_static(true);
+
+        let uploads = (0..5)
+            .filter(|_| object.take_model_upload(1.0).is_some())
+            .count();
+        assert_eq!(
+            uploads, 1,
+            "静的オブジェクトは最初の1回だけアップロードされるべき"
+        );
+
+        object.set_position(glam::vec3(0.0, 1.0, 0.0));
+        let uploads = (0..5)
+            .filter(|_| object.take_model_upload(1.0).is_some())
+            .count();
+        assert_eq!(uploads, 1, "移動後に1回だけ再アップロードされるべき");
+    }
+
+    #[test]
+    fn test_static_object_reuploads_after_rotate_and_scale() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_static(true);
+        object.take_model_upload(1.0);
+
+        object.set_rotation(glam::Quat::from_rotation_z(1.0));
+        assert!(
+            object.take_model_upload(1.0).is_some(),
+            "回転後は再アップロード"
+        );
+        assert!(object.take_model_upload(1.0).is_none());
+
+        object.set_scale(glam::Vec3::splat(3.0));
+        let upload = object
+            .take_model_upload(1.0)
+            .expect("拡縮後は再アップロード");
+        assert_eq!(upload.model, object.transform.matrix().to_cols_array_2d());
+    }
+
+    #[test]
+    fn test_dynamic_object_uploads_every_frame() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+
+        let uploads = (0..5)
+            .filter(|_| object.take_model_upload(1.0).is_some())
+            .count();
+        assert_eq!(uploads, 5);
+    }
+
+    #[test]
+    fn test_mirrored_copy_across_x_plane() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_position(glam::vec3(1.0, 0.0, 0.0));
+
+        let mirrored = object.mirrored(Plane::X(0.0));
+
+        assert_ne!(mirrored.id, object.id);
+        assert_eq!(mirrored.mesh_id, object.mesh_id, "メッシュは共有されるべき");
+        assert_eq!(mirrored.transform.position, glam::vec3(-1.0, 0.0, 0.0));
+        // 行列式が負 → レンダラーは巻き順反転パイプラインを使う
+        assert!(mirrored.get_model_matrix().determinant() < 0.0);
+        assert!(mirrored.transform.is_mirrored());
+    }
+
+    #[test]
+    fn test_projectile_advances_along_velocity() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_position(glam::vec3(0.0, 1.0, 0.0));
+        object.rigidbody = Some(Rigidbody::new(glam::vec3(0.0, 0.0, -10.0)));
+        object.lifetime = Some(2.0);
+
+        object.step_physics(0.1);
+
+        assert!(
+            object
+                .transform
+                .position
+                .abs_diff_eq(glam::vec3(0.0, 1.0, -1.0), 1e-6)
+        );
+        assert!(!object.is_expired(-1.0));
+    }
+
+    #[test]
+    fn test_projectile_despawns_after_lifetime() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.rigidbody = Some(Rigidbody::new(glam::vec3(1.0, 0.0, 0.0)));
+        object.lifetime = Some(0.5);
+
+        object.step_physics(0.3);
+        assert!(!object.is_expired(-1.0));
+        object.step_physics(0.3);
+        assert!(object.is_expired(-1.0), "寿命が尽きたら消えるべき");
+    }
+
+    #[test]
+    fn test_projectile_despawns_on_ground() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.rigidbody = Some(Rigidbody::new(glam::vec3(0.0, -5.0, 0.0)));
+        object.lifetime = Some(10.0);
+
+        object.step_physics(0.5);
+
+        assert!(object.is_expired(-2.0), "地面に当たったら消えるべき");
+        // 寿命のないオブジェクトは地面の下でも残る
+        let mut persistent =
+            RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        persistent.set_position(glam::vec3(0.0, -5.0, 0.0));
+        assert!(!persistent.is_expired(-2.0));
+    }
+
+    #[test]
+    fn test_attached_object_tracks_camera() {
+        let config = crate::core::config::AppConfig::default();
+        let mut camera = crate::scene::camera::Camera::new(1.0, &config.camera);
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        // カメラの右下前方に保持
+        let offset = Transform::new().with_position(glam::vec3(0.3, -0.2, -1.0));
+        object.camera_offset = Some(offset);
+
+        object.follow_camera(camera.world_matrix());
+        let expected = camera.world_matrix().transform_point3(offset.position);
+        assert!(object.transform.position.abs_diff_eq(expected, 1e-5));
+
+        camera.move_forward(2.0);
+        camera.move_right(1.0);
+        object.follow_camera(camera.world_matrix());
+
+        // オフセットはカメラ空間で保たれる
+        let local = camera
+            .world_matrix()
+            .inverse()
+            .transform_point3(object.transform.position);
+        assert!(local.abs_diff_eq(offset.position, 1e-5), "{:?}", local);
+    }
+
+    #[test]
+    fn test_detached_object_stops_following() {
+        let config = crate::core::config::AppConfig::default();
+        let mut camera = crate::scene::camera::Camera::new(1.0, &config.camera);
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.camera_offset = Some(Transform::new());
+        object.follow_camera(camera.world_matrix());
+        let position = object.transform.position;
+
+        object.camera_offset = None;
+        camera.move_forward(5.0);
+        object.follow_camera(camera.world_matrix());
+
+        assert_eq!(object.transform.position, position);
+    }
+
+    #[test]
+    fn test_upload_interpolates_between_fixed_steps() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_position(glam::vec3(0.0, 0.0, 0.0));
+        object.rigidbody = Some(Rigidbody::new(glam::vec3(6.0, 0.0, 0.0)));
+
+        object.step_physics(0.5);
+
+        // アキュムレータが半分消費された状態 → 前回と今回の中間
+        let uniform = object.take_model_upload(0.5).unwrap();
+        let model = glam::Mat4::from_cols_array_2d(&uniform.model);
+        assert!(
+            model
+                .w_axis
+                .truncate()
+                .abs_diff_eq(glam::vec3(1.5, 0.0, 0.0), 1e-6)
+        );
+
+        let uniform = object.take_model_upload(1.0).unwrap();
+        let model = glam::Mat4::from_cols_array_2d(&uniform.model);
+        assert_eq!(model.w_axis.truncate(), glam::vec3(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_teleport_is_not_interpolated() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.rigidbody = Some(Rigidbody::new(glam::vec3(1.0, 0.0, 0.0)));
+        object.step_physics(1.0);
+
+        object.set_position(glam::vec3(10.0, 0.0, 0.0));
+
+        assert_eq!(
+            object.interpolated_transform(0.0).position,
+            glam::vec3(10.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_material_uploads_only_after_color_change() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        assert_eq!(object.material.color, [1.0; 4], "既定は白（色を変えない）");
+        assert!(object.take_material_upload().is_none());
+
+        object.set_color([1.0, 0.0, 0.0, 1.0]);
+        let upload = object
+            .take_material_upload()
+            .expect("色の変更後はアップロード");
+        assert_eq!(upload.color, [1.0, 0.0, 0.0, 1.0]);
+        assert!(object.take_material_upload().is_none());
+
+        // ミラーは色も引き継ぐ
+        let mirrored = object.mirrored(Plane::X(0.0));
+        assert_eq!(mirrored.material, object.material);
+    }
+
+    #[test]
+    fn test_instance_data_matches_model_upload() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"))
+            .with_transform(Transform::new().with_position(glam::vec3(1.0, 2.0, 3.0)));
+        object.set_color([0.5, 0.25, 1.0, 1.0]);
+
+        // アップロード前は現在の変換を使う
+        assert_eq!(
+            object.instance_data().model,
+            object.transform.matrix().to_cols_array_2d()
+        );
+
+        let upload = object.take_model_upload(1.0).unwrap();
+        let instance = object.instance_data();
+        assert_eq!(instance.model, upload.model);
+        assert_eq!(instance.color, [0.5, 0.25, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_world_aabb_follows_translation() {
+        use crate::resources::primitives::{Primitive, cube::Cube};
+
+        let Ok(context) = pollster::block_on(crate::graphics::context::GpuContext::new(
+            true,
+            wgpu::Features::empty(),
+        )) else {
+            eprintln!("Skipping: no software adapter");
+            return;
+        };
+
+        let mesh = Cube::create_mesh(context.device.clone());
+        let mut object = RenderObject::new(ResourceId::new("cube"), ResourceId::new("pipeline"));
+        object.set_position(glam::vec3(2.0, 0.0, -1.0));
+
+        let aabb = object.world_aabb(&mesh);
+
+        assert_eq!(aabb.min, glam::vec3(1.5, -0.5, -1.5));
+        assert_eq!(aabb.max, glam::vec3(2.5, 0.5, -0.5));
+    }
+
+    #[test]
+    fn test_spin_is_frame_rate_independent() {
+        let angular_velocity = glam::vec3(0.0, 1.5, 0.0);
+        let mut coarse = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        let mut fine = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        coarse.angular_velocity = Some(angular_velocity);
+        fine.angular_velocity = Some(angular_velocity);
+
+        coarse.spin(1.0);
+        for _ in 0..60 {
+            fine.spin(1.0 / 60.0);
+        }
+
+        // フレーム間隔によらず同じ角度だけ回る
+        let expected = glam::Quat::from_rotation_y(1.5);
+        assert!(coarse.transform.rotation.abs_diff_eq(expected, 1e-5));
+        assert!(fine.transform.rotation.abs_diff_eq(expected, 1e-4));
+    }
+
+    #[test]
+    fn test_spin_keeps_interpolation_smooth() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.angular_velocity = Some(glam::vec3(0.0, 0.0, 2.0));
+
+        object.spin(0.25);
+
+        // 前回と現在の回転が一緒に回るので、補間率によらず同じ回転になる
+        let expected = glam::Quat::from_rotation_z(0.5);
+        for alpha in [0.0, 0.5, 1.0] {
+            assert!(
+                object
+                    .interpolated_transform(alpha)
+                    .rotation
+                    .abs_diff_eq(expected, 1e-5)
+            );
+        }
+    }
+
+    #[test]
+    fn test_object_without_spin_does_not_rotate() {
+        let mut object = RenderObject::new(ResourceId::new("mesh"), ResourceId::new("pipeline"));
+        object.set_static(true);
+        object.take_model_upload(1.0);
+
+        object.spin(1.0);
+
+        assert_eq!(object.transform.rotation, glam::Quat::IDENTITY);
+        assert!(
+            object.take_model_upload(1.0).is_none(),
+            "再アップロード不要"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    resources::primitives::ObjectType,
+    scene::{render_object::RenderObject, transform::Transform},
+};
+
+/// Serializable description of one primitive object in a scene.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectDescriptor {
+    pub object_type: ObjectType,
+    pub position: [f32; 3],
+    /// Rotation quaternion as `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+    pub visible: bool,
+}
+
+impl ObjectDescriptor {
+    /// Describes `object`, or `None` if it was not created from a primitive
+    /// type (e.g. OBJ meshes or quads with a custom texture).
+    pub fn from_object(object: &RenderObject) -> Option<Self> {
+        let transform = object.transform;
+        Some(Self {
+            object_type: object.object_type?,
+            position: transform.position.to_array(),
+            rotation: transform.rotation.to_array(),
+            scale: transform.scale.to_array(),
+            visible: object.visible,
+        })
+    }
+
+    pub fn transform(&self) -> Transform {
+        Transform::new()
+            .with_position(glam::Vec3::from_array(self.position))
+            .with_rotation(glam::Quat::from_array(self.rotation).normalize())
+            .with_scale(glam::Vec3::from_array(self.scale))
+    }
+}
+
+/// Saved arrangement of a scene's objects. Object ids are not stored; they
+/// are regenerated when the snapshot is loaded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneSnapshot {
+    #[serde(default)]
+    pub objects: Vec<ObjectDescriptor>,
+}
+
+impl SceneSnapshot {
+    /// Loads a snapshot, as JSON if `path` ends in `.json` and TOML otherwise.
+    #[allow(dead_code)]
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let snapshot = if is_json(path) {
+            serde_json::from_str(&content)?
+        } else {
+            toml::from_str(&content)?
+        };
+        Ok(snapshot)
+    }
+
+    /// Saves the snapshot, as JSON if `path` ends in `.json` and TOML otherwise.
+    #[allow(dead_code)]
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path_buf = std::path::Path::new(path);
+        if let Some(parent) = path_buf.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = if is_json(path) {
+            serde_json::to_string_pretty(self)?
+        } else {
+            toml::to_string_pretty(self)?
+        };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[allow(dead_code)]
+fn is_json(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_snapshot() -> SceneSnapshot {
+        SceneSnapshot {
+            objects: vec![
+                ObjectDescriptor {
+                    object_type: ObjectType::Quad,
+                    position: [1.0, 2.0, 3.0],
+                    rotation: glam::Quat::from_rotation_y(0.5).to_array(),
+                    scale: [2.0, 1.0, 1.0],
+                    visible: true,
+                },
+                ObjectDescriptor {
+                    object_type: ObjectType::Triangle,
+                    position: [-1.0, 0.0, 0.0],
+                    rotation: glam::Quat::IDENTITY.to_array(),
+                    scale: [1.0, 1.0, 1.0],
+                    visible: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_toml_and_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let snapshot = create_test_snapshot();
+
+        for name in ["scene.toml", "scene.json"] {
+            let path = temp_dir.path().join(name);
+            let path = path.to_str().unwrap();
+
+            snapshot.save_to_file(path).unwrap();
+            let loaded = SceneSnapshot::load_from_file(path).unwrap();
+
+            assert_eq!(loaded, snapshot, "{} の往復で内容が変わらないこと", name);
+        }
+
+        // 拡張子によって形式が切り替わる
+        let json = std::fs::read_to_string(temp_dir.path().join("scene.json")).unwrap();
+        assert!(json.trim_start().starts_with('{'));
+        let toml = std::fs::read_to_string(temp_dir.path().join("scene.toml")).unwrap();
+        assert!(toml.contains("[[objects]]"));
+    }
+
+    #[test]
+    fn test_descriptor_transform() {
+        let descriptor = &create_test_snapshot().objects[0];
+        let transform = descriptor.transform();
+
+        assert_eq!(transform.position, glam::vec3(1.0, 2.0, 3.0));
+        assert_eq!(transform.scale, glam::vec3(2.0, 1.0, 1.0));
+        assert!(
+            transform
+                .rotation
+                .abs_diff_eq(glam::Quat::from_rotation_y(0.5), 1e-6)
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(SceneSnapshot::load_from_file(path.to_str().unwrap()).is_err());
+    }
+}
@@ -1,3 +1,36 @@
+/// Axis-aligned plane, given by the coordinate it sits at (e.g. `Plane::X(0.0)` is x = 0).
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Plane {
+    X(f32),
+    Y(f32),
+    Z(f32),
+}
+
+impl Plane {
+    /// Unit normal of the plane.
+    pub fn normal(self) -> glam::Vec3 {
+        match self {
+            Plane::X(_) => glam::Vec3::X,
+            Plane::Y(_) => glam::Vec3::Y,
+            Plane::Z(_) => glam::Vec3::Z,
+        }
+    }
+
+    /// Reflects a point across the plane.
+    pub fn reflect_point(self, point: glam::Vec3) -> glam::Vec3 {
+        let (axis, offset) = match self {
+            Plane::X(offset) => (0, offset),
+            Plane::Y(offset) => (1, offset),
+            Plane::Z(offset) => (2, offset),
+        };
+        let mut reflected = point;
+        reflected[axis] = 2.0 * offset - point[axis];
+        reflected
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct Transform {
     pub position: glam::Vec3,
     pub rotation: glam::Quat,
@@ -23,23 +56,63 @@ impl Transform {
         self
     }
 
+    /// Sets the rotation from yaw (around Y), pitch (around X) and roll
+    /// (around Z), in radians, applied in that order.
+    #[allow(dead_code)]
+    pub fn with_euler(mut self, yaw: f32, pitch: f32, roll: f32) -> Self {
+        self.set_rotation_euler(yaw, pitch, roll);
+        self
+    }
+
+    /// Rotates the transform so `forward()` points from its position toward
+    /// `target`, keeping `up()` as close to `up` as possible.
+    ///
+    /// If `target` is the current position the rotation is left unchanged; if
+    /// the direction is parallel to `up`, the shortest rotation is used.
+    #[allow(dead_code)]
+    pub fn looking_at(mut self, target: glam::Vec3, up: glam::Vec3) -> Self {
+        let Some(direction) = (target - self.position).try_normalize() else {
+            return self;
+        };
+        self.rotation = if direction.cross(up).length_squared() > f32::EPSILON {
+            // ビュー行列の回転部分の逆（転置）がワールドでの姿勢になる
+            glam::Quat::from_mat3(&glam::Mat3::look_to_rh(direction, up).transpose())
+        } else {
+            glam::Quat::from_rotation_arc(glam::Vec3::NEG_Z, direction)
+        };
+        self
+    }
+
     pub fn with_scale(mut self, scale: glam::Vec3) -> Self {
         self.scale = scale;
         self
     }
 
+    /// Decomposes an affine matrix into scale, rotation and translation.
+    pub fn from_matrix(matrix: glam::Mat4) -> Self {
+        let (scale, rotation, position) = matrix.to_scale_rotation_translation();
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
     pub fn matrix(&self) -> glam::Mat4 {
         glam::Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.position)
     }
 
+    #[allow(dead_code)]
     pub fn forward(&self) -> glam::Vec3 {
         self.rotation * glam::Vec3::NEG_Z
     }
 
+    #[allow(dead_code)]
     pub fn right(&self) -> glam::Vec3 {
         self.rotation * glam::Vec3::X
     }
 
+    #[allow(dead_code)]
     pub fn up(&self) -> glam::Vec3 {
         self.rotation * glam::Vec3::Y
     }
@@ -47,4 +120,173 @@ impl Transform {
     pub fn set_position(&mut self, position: glam::Vec3) {
         self.position = position;
     }
+
+    /// Replaces the rotation with yaw/pitch/roll angles (see [`Self::with_euler`]).
+    pub fn set_rotation_euler(&mut self, yaw: f32, pitch: f32, roll: f32) {
+        self.rotation = glam::Quat::from_euler(glam::EulerRot::YXZ, yaw, pitch, roll);
+    }
+
+    /// Rotates by `angle` radians around a world-space `axis`, on top of the
+    /// current rotation. A zero axis leaves the rotation unchanged.
+    #[allow(dead_code)]
+    pub fn rotate_axis(&mut self, axis: glam::Vec3, angle: f32) {
+        let Some(axis) = axis.try_normalize() else {
+            return;
+        };
+        // 誤差の蓄積で単位長からずれないよう正規化する
+        self.rotation = (glam::Quat::from_axis_angle(axis, angle) * self.rotation).normalize();
+    }
+
+    /// Returns this transform reflected across `plane`.
+    ///
+    /// The reflection is folded into a negative scale on the plane's normal
+    /// axis, so the resulting matrix has a negative determinant and the
+    /// renderer draws it with reversed winding.
+    pub fn mirrored(&self, plane: Plane) -> Self {
+        let normal = plane.normal();
+        // 鏡映 R による回転の共役 R·Rot·R は軸を -R·axis に写す
+        let axis = glam::Vec3::new(self.rotation.x, self.rotation.y, self.rotation.z);
+        let reflected_axis = axis - 2.0 * axis.dot(normal) * normal;
+        let rotation = glam::Quat::from_xyzw(
+            -reflected_axis.x,
+            -reflected_axis.y,
+            -reflected_axis.z,
+            self.rotation.w,
+        );
+
+        Self {
+            position: plane.reflect_point(self.position),
+            rotation,
+            scale: self.scale * (glam::Vec3::ONE - 2.0 * normal),
+        }
+    }
+
+    /// Returns `true` if the transform mirrors geometry (negative determinant),
+    /// which reverses triangle winding.
+    pub fn is_mirrored(&self) -> bool {
+        self.scale.x * self.scale.y * self.scale.z < 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_across_x_plane() {
+        let transform = Transform::new().with_position(glam::vec3(1.0, 0.0, 0.0));
+
+        let mirrored = transform.mirrored(Plane::X(0.0));
+
+        assert_eq!(mirrored.position, glam::vec3(-1.0, 0.0, 0.0));
+        assert!(
+            mirrored.matrix().determinant() < 0.0,
+            "鏡映変換の行列式は負になるべき"
+        );
+        assert!(mirrored.is_mirrored());
+        assert!(!transform.is_mirrored());
+    }
+
+    #[test]
+    fn test_mirrored_matrix_matches_reflection() {
+        let transform = Transform::new()
+            .with_position(glam::vec3(1.0, 2.0, 3.0))
+            .with_rotation(glam::Quat::from_euler(glam::EulerRot::XYZ, 0.3, 0.7, -0.2))
+            .with_scale(glam::vec3(1.0, 2.0, 0.5));
+        let plane = Plane::Y(1.0);
+        let reflection = glam::Mat4::from_translation(glam::vec3(0.0, 2.0, 0.0))
+            * glam::Mat4::from_scale(glam::vec3(1.0, -1.0, 1.0));
+
+        let expected = reflection * transform.matrix();
+        let actual = transform.mirrored(plane).matrix();
+
+        assert!(
+            expected.abs_diff_eq(actual, 1e-5),
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_mirroring_twice_restores_orientation() {
+        let transform = Transform::new().with_position(glam::vec3(0.5, 0.0, 2.0));
+
+        let restored = transform.mirrored(Plane::Z(1.0)).mirrored(Plane::Z(1.0));
+
+        assert!(restored.position.abs_diff_eq(transform.position, 1e-6));
+        assert!(!restored.is_mirrored());
+    }
+
+    #[test]
+    fn test_basis_vectors_after_90_degree_yaw() {
+        let transform = Transform::new().with_euler(std::f32::consts::FRAC_PI_2, 0.0, 0.0);
+
+        // 左回りに90°向きを変えると前方は -X、右は -Z になる
+        assert!(transform.forward().abs_diff_eq(glam::Vec3::NEG_X, 1e-6));
+        assert!(transform.right().abs_diff_eq(glam::Vec3::NEG_Z, 1e-6));
+        assert!(transform.up().abs_diff_eq(glam::Vec3::Y, 1e-6));
+        assert!(
+            transform
+                .forward()
+                .cross(transform.up())
+                .abs_diff_eq(transform.right(), 1e-6),
+            "基底ベクトルが右手系を保っていない"
+        );
+    }
+
+    #[test]
+    fn test_rotate_axis_accumulates_onto_euler_rotation() {
+        let mut transform = Transform::new();
+        transform.rotate_axis(glam::Vec3::Y, std::f32::consts::FRAC_PI_4);
+        transform.rotate_axis(glam::Vec3::Y * 2.0, std::f32::consts::FRAC_PI_4);
+        transform.rotate_axis(glam::Vec3::ZERO, 1.0);
+
+        let expected = Transform::new().with_euler(std::f32::consts::FRAC_PI_2, 0.0, 0.0);
+        assert!(transform.rotation.abs_diff_eq(expected.rotation, 1e-6));
+    }
+
+    #[test]
+    fn test_euler_pitch_tilts_forward_up() {
+        let mut transform = Transform::new();
+        transform.set_rotation_euler(0.0, std::f32::consts::FRAC_PI_2, 0.0);
+
+        assert!(transform.forward().abs_diff_eq(glam::Vec3::Y, 1e-6));
+        assert!(transform.right().abs_diff_eq(glam::Vec3::X, 1e-6));
+    }
+
+    #[test]
+    fn test_looking_at_aligns_forward_with_target() {
+        let position = glam::vec3(1.0, 2.0, 3.0);
+        let target = glam::vec3(-4.0, 0.5, 7.0);
+
+        let transform = Transform::new()
+            .with_position(position)
+            .looking_at(target, glam::Vec3::Y);
+
+        let expected = (target - position).normalize();
+        assert!(
+            transform.forward().abs_diff_eq(expected, 1e-5),
+            "{:?} != {:?}",
+            transform.forward(),
+            expected
+        );
+        // 右方向は水平に保たれる
+        assert!(transform.right().y.abs() < 1e-5);
+        assert!(transform.up().y > 0.0);
+    }
+
+    #[test]
+    fn test_looking_at_degenerate_directions() {
+        let transform = Transform::new().looking_at(glam::Vec3::ZERO, glam::Vec3::Y);
+        assert_eq!(
+            transform.rotation,
+            glam::Quat::IDENTITY,
+            "自分の位置を向いても変化しない"
+        );
+
+        // 真上を向く場合も前方は目標を指す
+        let transform = Transform::new().looking_at(glam::vec3(0.0, 5.0, 0.0), glam::Vec3::Y);
+        assert!(transform.forward().abs_diff_eq(glam::Vec3::Y, 1e-5));
+    }
 }
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+/// Region of the render target a camera draws into, in fractions of the
+/// target size with the origin at the top-left corner.
+///
+/// Storing fractions keeps split-screen layouts valid across resizes and
+/// render-scale changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+    /// The whole render target.
+    pub const FULL: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+    #[allow(dead_code)]
+    pub const LEFT_HALF: Self = Self::new(0.0, 0.0, 0.5, 1.0);
+    #[allow(dead_code)]
+    pub const RIGHT_HALF: Self = Self::new(0.5, 0.0, 0.5, 1.0);
+
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the `(x, y, width, height)` pixel rectangle of this viewport in
+    /// a `target_width`×`target_height` target, as taken by
+    /// `RenderPass::set_viewport`.
+    pub fn pixel_rect(&self, target_width: u32, target_height: u32) -> [f32; 4] {
+        let (target_width, target_height) = (target_width as f32, target_height as f32);
+        [
+            self.x * target_width,
+            self.y * target_height,
+            self.width * target_width,
+            self.height * target_height,
+        ]
+    }
+
+    /// Aspect ratio of this viewport inside a target of aspect `target_aspect`.
+    pub fn aspect(&self, target_aspect: f32) -> f32 {
+        if self.height <= 0.0 {
+            return target_aspect;
+        }
+        target_aspect * self.width / self.height
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// One camera of a scene and the viewport it is rendered into.
+pub struct SceneView<'a> {
+    pub viewport: Viewport,
+    /// Camera bind group (group 0) used while drawing this view.
+    pub camera_bind_group: &'a Arc<wgpu::BindGroup>,
+    /// View-projection matrix without jitter, used for culling.
+    pub view_proj: glam::Mat4,
+    /// World-space camera position, used to sort translucent objects.
+    pub eye: glam::Vec3,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halves_split_target_pixels() {
+        assert_eq!(
+            Viewport::FULL.pixel_rect(800, 600),
+            [0.0, 0.0, 800.0, 600.0]
+        );
+        assert_eq!(
+            Viewport::LEFT_HALF.pixel_rect(800, 600),
+            [0.0, 0.0, 400.0, 600.0]
+        );
+        assert_eq!(
+            Viewport::RIGHT_HALF.pixel_rect(800, 600),
+            [400.0, 0.0, 400.0, 600.0]
+        );
+    }
+
+    #[test]
+    fn test_viewport_aspect_follows_its_share_of_the_target() {
+        assert_eq!(Viewport::FULL.aspect(2.0), 2.0);
+        assert_eq!(Viewport::LEFT_HALF.aspect(2.0), 1.0);
+        assert_eq!(Viewport::new(0.0, 0.0, 1.0, 0.0).aspect(2.0), 2.0);
+    }
+}
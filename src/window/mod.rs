@@ -15,4 +15,45 @@ impl Window {
     pub fn get_window(&self) -> Arc<WinitWindow> {
         self.window.clone()
     }
+
+    pub fn set_title(&self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    /// Hides and locks (or, where locking is unsupported, confines) the cursor
+    /// for mouse look; `false` releases it again.
+    pub fn set_cursor_captured(&self, captured: bool) {
+        use winit::window::CursorGrabMode;
+
+        let result = if captured {
+            self.window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined))
+        } else {
+            self.window.set_cursor_grab(CursorGrabMode::None)
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to change cursor grab mode: {}", e);
+        }
+
+        self.window.set_cursor_visible(!captured);
+    }
+
+    /// Returns whether the window is currently fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.fullscreen().is_some()
+    }
+
+    /// Switches between windowed and borderless fullscreen on the current
+    /// monitor, returning whether the window is now fullscreen.
+    ///
+    /// The new size arrives as a regular `Resized` event.
+    pub fn toggle_fullscreen(&self) -> bool {
+        use winit::window::Fullscreen;
+
+        let fullscreen = self.window.fullscreen().is_none();
+        self.window
+            .set_fullscreen(fullscreen.then_some(Fullscreen::Borderless(None)));
+        fullscreen
+    }
 }